@@ -0,0 +1,457 @@
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc, Weekday};
+use thiserror::Error;
+
+/// Describes when work happens: on which days of the week, between which hours, and
+/// which dates are holidays. Times are in UTC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "CalendarRepr")
+)]
+pub struct Calendar {
+    /// Whether each day of the week is a working day, starting from Monday.
+    working_days: [bool; 7],
+    /// The time work starts, on working days.
+    day_start: NaiveTime,
+    /// The time work ends, on working days.
+    day_end: NaiveTime,
+    /// The dates on which nobody works, even if they fall on a working day.
+    holidays: BTreeSet<NaiveDate>,
+}
+
+/// Represents an error that occurs when creating a calendar that has no working time.
+#[derive(Error, Debug)]
+pub enum CalendarError {
+    /// Used when none of the days of the week is a working day.
+    #[error("At least one day of the week must be a working day")]
+    NoWorkingDays,
+    /// Used when the working day doesn't end after it starts.
+    #[error("Working hours must end after they start")]
+    EmptyWorkingHours,
+}
+
+/// The serialized form of a [`Calendar`], validated when it's turned back into one.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct CalendarRepr {
+    working_days: [bool; 7],
+    day_start: NaiveTime,
+    day_end: NaiveTime,
+    holidays: BTreeSet<NaiveDate>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CalendarRepr> for Calendar {
+    type Error = CalendarError;
+
+    fn try_from(repr: CalendarRepr) -> Result<Self, Self::Error> {
+        let working_days = std::iter::successors(Some(Weekday::Mon), |day| Some(day.succ()))
+            .zip(repr.working_days)
+            .filter_map(|(day, working)| working.then_some(day));
+        let mut calendar = Calendar::new(working_days, repr.day_start, repr.day_end)?;
+        calendar.holidays = repr.holidays;
+        Ok(calendar)
+    }
+}
+
+/// Monday to Friday, from 9:00 to 17:00, without holidays.
+impl Default for Calendar {
+    fn default() -> Self {
+        Calendar {
+            working_days: [true, true, true, true, true, false, false],
+            day_start: NaiveTime::MIN + Duration::hours(9),
+            day_end: NaiveTime::MIN + Duration::hours(17),
+            holidays: BTreeSet::new(),
+        }
+    }
+}
+
+impl Calendar {
+    /// Creates a new calendar without holidays.
+    ///
+    /// # Arguments
+    ///
+    /// * `working_days` - The days of the week on which work happens.
+    /// * `day_start` - The time work starts, on working days.
+    /// * `day_end` - The time work ends, on working days.
+    ///
+    /// # Errors
+    ///
+    /// * `CalendarError::NoWorkingDays` - If `working_days` is empty.
+    /// * `CalendarError::EmptyWorkingHours` - If `day_end` isn't after `day_start`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, NaiveTime, Weekday};
+    /// use planter_core::calendar::Calendar;
+    ///
+    /// let calendar = Calendar::new(
+    ///     [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu],
+    ///     NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+    ///     NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(calendar.hours_per_day(), Duration::hours(10));
+    /// ```
+    pub fn new(
+        working_days: impl IntoIterator<Item = Weekday>,
+        day_start: NaiveTime,
+        day_end: NaiveTime,
+    ) -> Result<Self, CalendarError> {
+        let mut days = [false; 7];
+        for day in working_days {
+            days[day.num_days_from_monday() as usize] = true;
+        }
+        if !days.contains(&true) {
+            return Err(CalendarError::NoWorkingDays);
+        }
+        if day_end <= day_start {
+            return Err(CalendarError::EmptyWorkingHours);
+        }
+        Ok(Calendar {
+            working_days: days,
+            day_start,
+            day_end,
+            holidays: BTreeSet::new(),
+        })
+    }
+
+    /// Returns how much work fits in a working day.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::calendar::Calendar;
+    ///
+    /// assert_eq!(Calendar::default().hours_per_day(), Duration::hours(8));
+    /// ```
+    pub fn hours_per_day(&self) -> Duration {
+        self.day_end - self.day_start
+    }
+
+    /// Marks a date as a holiday.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date nobody works on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use planter_core::calendar::Calendar;
+    ///
+    /// let christmas = NaiveDate::from_ymd_opt(2025, 12, 25).unwrap();
+    /// let mut calendar = Calendar::default();
+    /// calendar.add_holiday(christmas);
+    /// assert!(!calendar.is_working_day(christmas));
+    /// ```
+    pub fn add_holiday(&mut self, date: NaiveDate) {
+        self.holidays.insert(date);
+    }
+
+    /// Turns a holiday back into a regular day.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date to remove from the holidays.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use planter_core::calendar::Calendar;
+    ///
+    /// let christmas = NaiveDate::from_ymd_opt(2025, 12, 25).unwrap();
+    /// let mut calendar = Calendar::default();
+    /// calendar.add_holiday(christmas);
+    /// calendar.remove_holiday(christmas);
+    /// assert!(calendar.is_working_day(christmas));
+    /// ```
+    pub fn remove_holiday(&mut self, date: NaiveDate) {
+        self.holidays.remove(&date);
+    }
+
+    /// Returns the holidays, in chronological order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use planter_core::calendar::Calendar;
+    ///
+    /// let christmas = NaiveDate::from_ymd_opt(2025, 12, 25).unwrap();
+    /// let mut calendar = Calendar::default();
+    /// calendar.add_holiday(christmas);
+    /// assert_eq!(calendar.holidays().collect::<Vec<_>>(), vec![christmas]);
+    /// ```
+    pub fn holidays(&self) -> impl Iterator<Item = NaiveDate> {
+        self.holidays.iter().copied()
+    }
+
+    /// Checks whether work happens on the given date.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date to check.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use planter_core::calendar::Calendar;
+    ///
+    /// let calendar = Calendar::default();
+    /// let friday = NaiveDate::from_ymd_opt(2025, 6, 6).unwrap();
+    /// assert!(calendar.is_working_day(friday));
+    /// assert!(!calendar.is_working_day(friday.succ_opt().unwrap()));
+    /// ```
+    pub fn is_working_day(&self, date: NaiveDate) -> bool {
+        self.working_days[date.weekday().num_days_from_monday() as usize]
+            && !self.holidays.contains(&date)
+    }
+
+    /// Computes how much working time there is between two dates.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The beginning of the interval.
+    /// * `finish` - The end of the interval.
+    ///
+    /// # Returns
+    ///
+    /// The working time between the dates, negative if `finish` comes before `start`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, NaiveDate};
+    /// use planter_core::calendar::Calendar;
+    ///
+    /// let calendar = Calendar::default();
+    /// let friday = NaiveDate::from_ymd_opt(2025, 6, 6).unwrap();
+    /// let start = friday.and_hms_opt(15, 0, 0).unwrap().and_utc();
+    /// let finish = start + Duration::days(3);
+    ///
+    /// assert_eq!(calendar.working_duration_between(start, finish), Duration::hours(8));
+    /// ```
+    pub fn working_duration_between(
+        &self,
+        start: DateTime<Utc>,
+        finish: DateTime<Utc>,
+    ) -> Duration {
+        if finish < start {
+            return -self.working_duration_between(finish, start);
+        }
+
+        let mut total = Duration::zero();
+        for day in start.date_naive().iter_days() {
+            if day > finish.date_naive() {
+                break;
+            }
+            if let Some((work_start, work_end)) = self.working_hours(day) {
+                let from = work_start.max(start);
+                let to = work_end.min(finish);
+                if from < to {
+                    total += to - from;
+                }
+            }
+        }
+        total
+    }
+
+    /// Moves a date by the given amount of working time, skipping non working days and the
+    /// hours outside the working day. With no working time to add, the date is moved forward
+    /// to the next moment work can happen, if it isn't one already.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date to move.
+    /// * `working_time` - How much working time to move by, backward if negative.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(DateTime<Utc>)` - The moved date.
+    /// * `None` - If the moved date would be out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, NaiveDate};
+    /// use planter_core::calendar::Calendar;
+    ///
+    /// let calendar = Calendar::default();
+    /// let friday = NaiveDate::from_ymd_opt(2025, 6, 6).unwrap();
+    /// let start = friday.and_hms_opt(15, 0, 0).unwrap().and_utc();
+    /// let monday = NaiveDate::from_ymd_opt(2025, 6, 9).unwrap();
+    ///
+    /// assert_eq!(
+    ///     calendar.add_working_time(start, Duration::hours(8)),
+    ///     Some(monday.and_hms_opt(15, 0, 0).unwrap().and_utc())
+    /// );
+    /// ```
+    pub fn add_working_time(
+        &self,
+        date: DateTime<Utc>,
+        working_time: Duration,
+    ) -> Option<DateTime<Utc>> {
+        if working_time < Duration::zero() {
+            return self.subtract_working_time(date, -working_time);
+        }
+
+        let mut remaining = working_time;
+        let mut day = date.date_naive();
+        let mut bound = date;
+        loop {
+            if let Some((work_start, work_end)) = self.working_hours(day) {
+                let from = work_start.max(bound);
+                if from < work_end {
+                    if remaining <= work_end - from {
+                        return Some(from + remaining);
+                    }
+                    remaining -= work_end - from;
+                }
+            }
+            day = day.succ_opt()?;
+            bound = day.and_time(NaiveTime::MIN).and_utc();
+        }
+    }
+
+    /// Moves a date backward by the given amount of working time.
+    fn subtract_working_time(
+        &self,
+        date: DateTime<Utc>,
+        working_time: Duration,
+    ) -> Option<DateTime<Utc>> {
+        let mut remaining = working_time;
+        let mut day = date.date_naive();
+        let mut bound = date;
+        loop {
+            if let Some((work_start, work_end)) = self.working_hours(day) {
+                let to = work_end.min(bound);
+                if work_start < to {
+                    if remaining <= to - work_start {
+                        return Some(to - remaining);
+                    }
+                    remaining -= to - work_start;
+                }
+            }
+            bound = day.and_time(NaiveTime::MIN).and_utc();
+            day = day.pred_opt()?;
+        }
+    }
+
+    /// Returns when work starts and ends on the given date, if it's a working day.
+    fn working_hours(&self, date: NaiveDate) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        self.is_working_day(date).then(|| {
+            (
+                date.and_time(self.day_start).and_utc(),
+                date.and_time(self.day_end).and_utc(),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate, NaiveTime, Weekday};
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        // June 2nd 2025 is a Monday.
+        NaiveDate::from_ymd_opt(2025, 6, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    #[test]
+    fn working_time_skips_weekends_and_holidays() {
+        let mut calendar = Calendar::default();
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2025, 6, 9).unwrap());
+
+        // Friday afternoon, plus a day of work, lands on Tuesday since Monday is a holiday.
+        assert_eq!(
+            calendar.add_working_time(at(6, 15), Duration::hours(8)),
+            Some(at(10, 15))
+        );
+        assert_eq!(
+            calendar.add_working_time(at(10, 15), Duration::hours(-8)),
+            Some(at(6, 15))
+        );
+        // Work ending right at the end of the day doesn't spill over.
+        assert_eq!(
+            calendar.add_working_time(at(6, 9), Duration::hours(8)),
+            Some(at(6, 17))
+        );
+        // Outside working hours, nothing can start until the next working day.
+        assert_eq!(
+            calendar.add_working_time(at(6, 18), Duration::zero()),
+            Some(at(10, 9))
+        );
+        assert_eq!(
+            calendar.working_duration_between(at(6, 15), at(10, 15)),
+            Duration::hours(8)
+        );
+        assert_eq!(
+            calendar.working_duration_between(at(10, 15), at(6, 15)),
+            Duration::hours(-8)
+        );
+    }
+
+    #[test]
+    fn new_rejects_calendars_without_working_time() {
+        let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        let five = NaiveTime::from_hms_opt(17, 0, 0).unwrap();
+
+        assert!(matches!(
+            Calendar::new([], nine, five),
+            Err(CalendarError::NoWorkingDays)
+        ));
+        assert!(matches!(
+            Calendar::new([Weekday::Sat], five, nine),
+            Err(CalendarError::EmptyWorkingHours)
+        ));
+        assert!(Calendar::new([Weekday::Sat], nine, five).is_ok());
+    }
+
+    proptest! {
+        #[test]
+        fn working_time_round_trips(hours in 0..24_i64, minutes in 0..10_000_i64) {
+            let calendar = Calendar::default();
+            let start = at(2, 0) + Duration::hours(hours);
+            let finish = calendar
+                .add_working_time(start, Duration::minutes(minutes))
+                .unwrap();
+
+            assert_eq!(
+                calendar.working_duration_between(start, finish),
+                Duration::minutes(minutes)
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn invalid_calendars_are_rejected() {
+        let calendar = Calendar::default();
+        let json = serde_json::to_string(&calendar).unwrap();
+        assert_eq!(serde_json::from_str::<Calendar>(&json).unwrap(), calendar);
+
+        let no_working_days = json.replace("true", "false");
+        assert_ne!(no_working_days, json);
+        assert!(serde_json::from_str::<Calendar>(&no_working_days).is_err());
+
+        let empty_working_hours = json.replace("17:00:00", "09:00:00");
+        assert_ne!(empty_working_hours, json);
+        assert!(serde_json::from_str::<Calendar>(&empty_working_hours).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use std::ops::Deref;
+use std::{fmt::Display, ops::Deref, str::FromStr};
 
 use chrono::Duration;
 use once_cell::sync::Lazy;
@@ -6,7 +6,7 @@ use regex::bytes::Regex;
 use thiserror::Error;
 
 /// A duration is a unit of time that represents the amount of time required to complete a task.
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PositiveDuration(Duration);
 
 /// Represents an error that occurs when trying to parse a negative duration.
@@ -21,13 +21,30 @@ pub enum DurationError {
     /// Used when trying to parse an invalid string.
     #[error("Input string couldn't be parsed into a PositiveDuration")]
     InvalidInput,
+    /// Used when converting to working days of zero hours.
+    #[error("A working day must last at least one hour")]
+    ZeroHoursPerDay,
 }
 
 impl PositiveDuration {
+    /// A duration of no time at all, like the one of a milestone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::duration::PositiveDuration;
+    ///
+    /// assert_eq!(*PositiveDuration::ZERO, Duration::zero());
+    /// ```
+    pub const ZERO: PositiveDuration = PositiveDuration(Duration::zero());
+
     /// Tries to parse a string and return the corresponding `[PositiveDuration]`
     ///
     /// # Arguments
-    /// * `s` - The string to parse. Currently, only hours are supported in the format "X h".
+    /// * `s` - The string to parse, in the format "X u", where X is a whole number and u is
+    ///   the unit: `m` for minutes, `h` for hours, `d` for days or `w` for weeks. Days and weeks
+    ///   are calendar ones, 24 and 168 hours. Mixing units, like "1 d 2 h", isn't supported.
     ///
     /// # Returns
     /// * `Ok(PositiveDuration)` - If the input string could be parsed into a `PositiveDuration`.
@@ -47,6 +64,9 @@ impl PositiveDuration {
     ///
     /// let duration = PositiveDuration::parse_from_str("8 h").unwrap();
     /// assert_eq!(duration.num_hours(), 8);
+    ///
+    /// let duration = PositiveDuration::parse_from_str("2 w").unwrap();
+    /// assert_eq!(duration.num_days(), 14);
     /// ```
     ///
     /// ```should_panic
@@ -60,20 +80,167 @@ impl PositiveDuration {
     pub fn parse_from_str(s: &str) -> Result<Self, DurationError> {
         let bytes = s.as_bytes();
         static RE: Lazy<Regex> = Lazy::new(|| {
-            Regex::new(r"^[0-9]{1,12} h$")
+            Regex::new(r"^([0-9]{1,12}) ([mhdw])$")
                 .expect("It wasn't possible to compile a hardcoded regex. This is a bug.")
         });
-        if RE.is_match(bytes) {
-            let hours = s.split(' ').next().expect("Expecting to retrieve the hours from the string after matching the regex. This is a bug.").parse::<i64>().expect("Expecting to convert the hours to an i64. This is a bug.");
-            if hours > MAX_DURATION {
-                Err(DurationError::ExceedsMaximumDuration)
-            } else {
-                Ok(PositiveDuration(Duration::hours(hours)))
-            }
-        } else {
-            Err(DurationError::InvalidInput)
+        let captures = RE.captures(bytes).ok_or(DurationError::InvalidInput)?;
+        let amount = std::str::from_utf8(&captures[1])
+            .expect(
+                "Expecting the amount to be ASCII digits after matching the regex. This is a bug.",
+            )
+            .parse::<i64>()
+            .expect("Expecting to convert the amount to an i64. This is a bug.");
+        let duration = match &captures[2] {
+            b"m" => Duration::try_minutes(amount),
+            b"h" => Duration::try_hours(amount),
+            b"d" => Duration::try_days(amount),
+            _ => Duration::try_weeks(amount),
+        };
+        duration
+            .ok_or(DurationError::ExceedsMaximumDuration)?
+            .try_into()
+    }
+
+    /// Returns whether the duration lasts no time at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::duration::PositiveDuration;
+    ///
+    /// assert!(PositiveDuration::ZERO.is_zero());
+    /// assert!(!PositiveDuration::parse_from_str("1 m").unwrap().is_zero());
+    /// ```
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Returns the signed difference `self - other`, which is negative when `other` is longer.
+    /// Useful to report variances, which `PositiveDuration` can't represent.
+    ///
+    /// # Arguments
+    /// * `other` - The duration to subtract from this one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::duration::PositiveDuration;
+    ///
+    /// let planned = PositiveDuration::parse_from_str("8 h").unwrap();
+    /// let actual = PositiveDuration::parse_from_str("10 h").unwrap();
+    /// assert_eq!(planned.diff(&actual), Duration::hours(-2));
+    /// assert_eq!(actual.diff(&planned), Duration::hours(2));
+    /// ```
+    pub fn diff(&self, other: &PositiveDuration) -> Duration {
+        self.0 - other.0
+    }
+
+    /// Adds two durations, keeping the result within the allowed bounds.
+    ///
+    /// # Arguments
+    /// * `other` - The duration to add to this one.
+    ///
+    /// # Errors
+    /// * `DurationError::ExceedsMaximumDuration` - If the sum exceeds the maximum allowed value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::duration::{MAX_DURATION, PositiveDuration};
+    ///
+    /// let eight_hours = PositiveDuration::parse_from_str("8 h").unwrap();
+    /// let total = eight_hours.checked_add(eight_hours).unwrap();
+    /// assert_eq!(total.num_hours(), 16);
+    ///
+    /// let longest = chrono::Duration::milliseconds(MAX_DURATION).try_into().unwrap();
+    /// assert!(eight_hours.checked_add(longest).is_err());
+    /// ```
+    pub fn checked_add(self, other: PositiveDuration) -> Result<Self, DurationError> {
+        self.0
+            .checked_add(&other.0)
+            .ok_or(DurationError::ExceedsMaximumDuration)?
+            .try_into()
+    }
+
+    /// Subtracts a duration from this one, keeping the result within the allowed bounds.
+    ///
+    /// # Arguments
+    /// * `other` - The duration to subtract from this one.
+    ///
+    /// # Errors
+    /// * `DurationError::NegativeDuration` - If `other` is longer than this duration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::duration::PositiveDuration;
+    ///
+    /// let eight_hours = PositiveDuration::parse_from_str("8 h").unwrap();
+    /// let two_hours = PositiveDuration::parse_from_str("2 h").unwrap();
+    /// assert_eq!(eight_hours.checked_sub(two_hours).unwrap().num_hours(), 6);
+    /// assert!(two_hours.checked_sub(eight_hours).is_err());
+    /// ```
+    pub fn checked_sub(self, other: PositiveDuration) -> Result<Self, DurationError> {
+        self.0
+            .checked_sub(&other.0)
+            .ok_or(DurationError::NegativeDuration)?
+            .try_into()
+    }
+
+    /// Returns the whole hours left after the whole working days, reading the duration as
+    /// working time: 84 hours at 8 hours per day are 10 working days and 4 hours.
+    /// Together with [`PositiveDuration::to_working_days`], it shows a duration the way
+    /// planners count it.
+    ///
+    /// # Arguments
+    /// * `hours_per_day` - How many hours a working day lasts. If it's zero, no working day
+    ///   fits in the duration and all of its whole hours are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::duration::PositiveDuration;
+    ///
+    /// let duration = PositiveDuration::parse_from_str("84 h").unwrap();
+    /// assert_eq!(duration.num_working_hours(8), 4);
+    /// assert_eq!(duration.num_working_hours(0), 84);
+    /// ```
+    pub fn num_working_hours(&self, hours_per_day: u8) -> i64 {
+        let hours = self.0.num_hours();
+        match hours_per_day {
+            0 => hours,
+            hours_per_day => hours % i64::from(hours_per_day),
         }
     }
+
+    /// Returns how many working days the duration lasts, reading it as working time: 80 hours
+    /// at 8 hours per day are 10 working days.
+    ///
+    /// # Arguments
+    /// * `hours_per_day` - How many hours a working day lasts.
+    ///
+    /// # Errors
+    /// * `DurationError::ZeroHoursPerDay` - If `hours_per_day` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::duration::PositiveDuration;
+    ///
+    /// let duration = PositiveDuration::parse_from_str("84 h").unwrap();
+    /// assert_eq!(duration.to_working_days(8).unwrap(), 10.5);
+    /// assert!(duration.to_working_days(0).is_err());
+    /// ```
+    pub fn to_working_days(&self, hours_per_day: u8) -> Result<f64, DurationError> {
+        if hours_per_day == 0 {
+            return Err(DurationError::ZeroHoursPerDay);
+        }
+        // Precision loss is irrelevant at the scale of durations.
+        #[allow(clippy::cast_precision_loss)]
+        let hours = self.0.num_milliseconds() as f64 / 3_600_000.0;
+        Ok(hours / f64::from(hours_per_day))
+    }
 }
 
 /// Maximum duration allowed is ~31.68809 years.
@@ -102,14 +269,63 @@ impl Deref for PositiveDuration {
     }
 }
 
+impl FromStr for PositiveDuration {
+    type Err = DurationError;
+
+    /// Parses a string in the format accepted by [`PositiveDuration::parse_from_str`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PositiveDuration::parse_from_str(s)
+    }
+}
+
+/// Formats the duration as [`PositiveDuration::parse_from_str`] expects it, in the largest unit
+/// that represents it exactly. Fractions of a minute are truncated.
+///
+/// ```
+/// use planter_core::duration::PositiveDuration;
+///
+/// let duration: PositiveDuration = "48 h".parse().unwrap();
+/// assert_eq!(duration.to_string(), "2 d");
+/// assert_eq!(duration.to_string().parse::<PositiveDuration>().unwrap(), duration);
+/// ```
+impl Display for PositiveDuration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let minutes = self.0.num_minutes();
+        let (amount, unit) = [(60 * 24 * 7, "w"), (60 * 24, "d"), (60, "h")]
+            .into_iter()
+            .find(|(size, _)| minutes != 0 && minutes % size == 0)
+            .map_or((minutes, "m"), |(size, unit)| (minutes / size, unit));
+        write!(f, "{amount} {unit}")
+    }
+}
+
+/// Durations are serialized as a whole number of milliseconds, the unit of [`MAX_DURATION`].
+#[cfg(feature = "serde")]
+impl serde::Serialize for PositiveDuration {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.0.num_milliseconds())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PositiveDuration {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let milliseconds = i64::deserialize(deserializer)?;
+        Duration::try_milliseconds(milliseconds)
+            .ok_or(DurationError::ExceedsMaximumDuration)
+            .and_then(PositiveDuration::try_from)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 /// Utilities to run tests with duration.
 pub mod test_utils {
     use proptest::prelude::Strategy;
 
-    /// Generate a random duration string.
+    /// Generate a random duration string, in any of the supported units.
     pub fn duration_string() -> impl Strategy<Value = String> {
-        r"[0-9]{1,12} h".prop_map(|s: String| s.to_owned())
+        r"[0-9]{1,12} [mhdw]".prop_map(|s: String| s.to_owned())
     }
 }
 
@@ -119,15 +335,126 @@ mod tests {
     use crate::duration::test_utils::duration_string;
     use proptest::prelude::*;
 
+    #[test]
+    fn parse_from_str_supports_units() {
+        let parse = |s| *PositiveDuration::parse_from_str(s).unwrap();
+
+        assert_eq!(parse("45 m"), Duration::minutes(45));
+        assert_eq!(parse("8 h"), Duration::hours(8));
+        assert_eq!(parse("3 d"), Duration::days(3));
+        assert_eq!(parse("2 w"), Duration::weeks(2));
+        assert!(matches!(
+            PositiveDuration::parse_from_str("1 d 2 h"),
+            Err(DurationError::InvalidInput)
+        ));
+        assert!(matches!(
+            PositiveDuration::parse_from_str("999999999999 w"),
+            Err(DurationError::ExceedsMaximumDuration)
+        ));
+    }
+
+    #[test]
+    fn zero_is_the_default_and_the_smallest() {
+        assert_eq!(PositiveDuration::ZERO, PositiveDuration::default());
+        assert!(PositiveDuration::ZERO.is_zero());
+        assert_eq!(
+            PositiveDuration::try_from(Duration::zero()).unwrap(),
+            PositiveDuration::ZERO
+        );
+        assert!(PositiveDuration::ZERO < Duration::milliseconds(1).try_into().unwrap());
+        assert!(
+            !PositiveDuration::try_from(Duration::milliseconds(1))
+                .unwrap()
+                .is_zero()
+        );
+    }
+
+    #[test]
+    fn diff_is_signed() {
+        let short: PositiveDuration = Duration::hours(3).try_into().unwrap();
+        let long: PositiveDuration = Duration::hours(5).try_into().unwrap();
+
+        assert_eq!(long.diff(&short), Duration::hours(2));
+        assert_eq!(short.diff(&long), Duration::hours(-2));
+        assert_eq!(short.diff(&short), Duration::zero());
+    }
+
+    #[test]
+    fn display_uses_the_largest_exact_unit() {
+        let display = |d: Duration| PositiveDuration::try_from(d).unwrap().to_string();
+
+        assert_eq!(display(Duration::zero()), "0 m");
+        assert_eq!(display(Duration::minutes(90)), "90 m");
+        assert_eq!(display(Duration::hours(25)), "25 h");
+        assert_eq!(display(Duration::days(8)), "8 d");
+        assert_eq!(display(Duration::weeks(3)), "3 w");
+        assert_eq!(display(Duration::seconds(150)), "2 m");
+    }
+
+    #[test]
+    fn working_days_count_hours_of_work() {
+        let duration = PositiveDuration::parse_from_str("80 h").unwrap();
+        assert!((duration.to_working_days(8).unwrap() - 10.0).abs() < f64::EPSILON);
+        assert_eq!(duration.num_working_hours(8), 0);
+        assert_eq!(duration.num_working_hours(7), 3);
+        assert!(matches!(
+            duration.to_working_days(0),
+            Err(DurationError::ZeroHoursPerDay)
+        ));
+
+        let duration = PositiveDuration::parse_from_str("90 m").unwrap();
+        assert!((duration.to_working_days(6).unwrap() - 0.25).abs() < f64::EPSILON);
+        assert_eq!(duration.num_working_hours(6), 1);
+    }
+
     proptest! {
+        #[test]
+        fn display_round_trips(s in duration_string()) {
+            if let Ok(duration) = s.parse::<PositiveDuration>() {
+                assert_eq!(duration.to_string().parse::<PositiveDuration>().unwrap(), duration);
+            }
+        }
+
         #[test]
         fn parse_from_str_works(s in duration_string()) {
-            let hours = s.split(' ').next().unwrap().parse::<i64>().unwrap();
-            if !(0..=MAX_DURATION).contains(&hours) {
-                assert!(PositiveDuration::parse_from_str(&s).is_err());
-            } else {
-                let duration = PositiveDuration::parse_from_str(&s).unwrap();
-                assert_eq!(duration.num_hours(), hours);
+            let (amount, unit) = s.split_once(' ').unwrap();
+            let amount = amount.parse::<i64>().unwrap();
+            let expected = match unit {
+                "m" => Duration::try_minutes(amount),
+                "h" => Duration::try_hours(amount),
+                "d" => Duration::try_days(amount),
+                _ => Duration::try_weeks(amount),
+            };
+            match expected {
+                Some(expected) if expected <= Duration::milliseconds(MAX_DURATION) => {
+                    let duration = PositiveDuration::parse_from_str(&s).unwrap();
+                    assert_eq!(*duration, expected);
+                }
+                _ => assert!(matches!(
+                    PositiveDuration::parse_from_str(&s),
+                    Err(DurationError::ExceedsMaximumDuration)
+                )),
+            }
+        }
+
+        #[test]
+        fn checked_arithmetic_stays_in_bounds(a in 0..=MAX_DURATION, b in 0..=MAX_DURATION) {
+            let left: PositiveDuration = Duration::milliseconds(a).try_into().unwrap();
+            let right: PositiveDuration = Duration::milliseconds(b).try_into().unwrap();
+
+            match left.checked_add(right) {
+                Ok(sum) => assert_eq!(*sum, Duration::milliseconds(a + b)),
+                Err(e) => {
+                    assert!(a + b > MAX_DURATION);
+                    assert!(matches!(e, DurationError::ExceedsMaximumDuration));
+                }
+            }
+            match left.checked_sub(right) {
+                Ok(difference) => assert_eq!(*difference, Duration::milliseconds(a - b)),
+                Err(e) => {
+                    assert!(a < b);
+                    assert!(matches!(e, DurationError::NegativeDuration));
+                }
             }
         }
 
@@ -135,7 +462,7 @@ mod tests {
         fn parse_from_str_fails_with_invalid_input(s in "\\PC*") {
             let bytes = s.as_bytes();
             static RE: Lazy<Regex> = Lazy::new(|| {
-                Regex::new(r"^[0-9]{1,12} h$")
+                Regex::new(r"^[0-9]{1,12} [mhdw]$")
                     .expect("It wasn't possible to compile a hardcoded regex. This is a bug.")
             });
             if !RE.is_match(bytes) {
@@ -143,4 +470,19 @@ mod tests {
             }
         }
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn durations_serialize_as_milliseconds() {
+        let duration: PositiveDuration = Duration::minutes(90).try_into().unwrap();
+        let json = serde_json::to_string(&duration).unwrap();
+
+        assert_eq!(json, "5400000");
+        assert_eq!(
+            serde_json::from_str::<PositiveDuration>(&json).unwrap(),
+            duration
+        );
+        assert!(serde_json::from_str::<PositiveDuration>("-1").is_err());
+        assert!(serde_json::from_str::<PositiveDuration>(&(MAX_DURATION + 1).to_string()).is_err());
+    }
 }
@@ -1,7 +1,17 @@
 //! This is a library with types and behaviour inspired by the PMBOK Guide 7th edition.
+//!
+//! The `serde` feature makes projects, and everything they are made of, serializable, and
+//! exports them as JSON for frontends.
+//! The `csv` feature imports and exports the tasks of a project as CSV, for spreadsheets.
 
+/// Working calendars, describing when work can happen.
+pub mod calendar;
 /// A duration is a unit of time that represents the amount of time required to complete a task.
 pub mod duration;
+/// Amounts of money in a currency, for rates and costs.
+pub mod money;
+/// Interoperability with Microsoft Project, to import plans created with it and export plans to it.
+pub mod ms_project;
 /// A person can either be a resource, a team member or a stakeholder.
 pub mod person;
 /// A project is a set of activities required to transform ideas into reality.
@@ -18,7 +28,11 @@ pub mod person;
 pub mod project;
 /// A resource is a material required in the project to carry out tasks and provide deliverables. Resources have a cost, the sum of which, will concur with the total cost of the project. Project team members can also be considered resources.
 pub mod resources;
+/// A risk is an uncertain event that, if it happens, affects the project. Risks have a probability and an impact.
+pub mod risk;
 /// A stakeholder is a person or organization that has an interest in the project. Stakeholders have a level of influence and a level of interest in the project.
 pub mod stakeholders;
 /// A task is a unit of work that needs to be completed in order to achieve the project's objectives. Tasks have a duration, a start date, an end date, and a status.
 pub mod task;
+/// Validation checks that surface problems in a project, classified by severity.
+pub mod validation;
@@ -0,0 +1,386 @@
+use std::fmt::Display;
+
+use thiserror::Error;
+
+/// A currency, identified by its ISO 4217 code, like EUR or USD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct Currency([u8; 3]);
+
+/// An amount of money in a currency.
+///
+/// The amount is in minor units, hundredths of the currency, like cents for euros and dollars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Money {
+    /// The amount, in hundredths of the currency.
+    amount: i64,
+    /// The currency of the amount.
+    currency: Currency,
+}
+
+/// Represents an error that occurs when handling money.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum MoneyError {
+    /// Used when a currency code isn't made of three uppercase ASCII letters.
+    #[error("{0} isn't a valid currency code")]
+    InvalidCurrency(String),
+    /// Used when adding or subtracting amounts in different currencies.
+    #[error("Amounts in {0} and {1} can't be combined")]
+    MixedCurrencies(Currency, Currency),
+    /// Used when the result of an operation doesn't fit the amount.
+    #[error("The amount of money is too large")]
+    Overflow,
+}
+
+impl Currency {
+    /// Euro.
+    pub const EUR: Currency = Currency(*b"EUR");
+    /// United States dollar.
+    pub const USD: Currency = Currency(*b"USD");
+    /// Pound sterling.
+    pub const GBP: Currency = Currency(*b"GBP");
+
+    /// Creates a currency from its ISO 4217 code.
+    ///
+    /// # Arguments
+    /// * `code` - The code of the currency, three uppercase ASCII letters.
+    ///
+    /// # Errors
+    /// * `MoneyError::InvalidCurrency` - If the code isn't made of three uppercase ASCII letters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::money::Currency;
+    ///
+    /// assert_eq!(Currency::new("EUR").unwrap(), Currency::EUR);
+    /// assert_eq!(Currency::new("CHF").unwrap().code(), "CHF");
+    /// assert!(Currency::new("eur").is_err());
+    /// ```
+    pub fn new(code: &str) -> Result<Self, MoneyError> {
+        match code.as_bytes() {
+            &[a, b, c] if [a, b, c].iter().all(u8::is_ascii_uppercase) => Ok(Currency([a, b, c])),
+            _ => Err(MoneyError::InvalidCurrency(code.to_owned())),
+        }
+    }
+
+    /// Returns the ISO 4217 code of the currency.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::money::Currency;
+    ///
+    /// assert_eq!(Currency::USD.code(), "USD");
+    /// ```
+    pub fn code(&self) -> &str {
+        // The code is checked to be ASCII when the currency is created.
+        std::str::from_utf8(&self.0).unwrap_or_default()
+    }
+}
+
+/// The currency of the amounts given as plain numbers: euros.
+impl Default for Currency {
+    fn default() -> Self {
+        Currency::EUR
+    }
+}
+
+impl Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl TryFrom<String> for Currency {
+    type Error = MoneyError;
+
+    fn try_from(code: String) -> Result<Self, Self::Error> {
+        Currency::new(&code)
+    }
+}
+
+impl From<Currency> for String {
+    fn from(currency: Currency) -> Self {
+        currency.code().to_owned()
+    }
+}
+
+impl Money {
+    /// Creates an amount of money.
+    ///
+    /// # Arguments
+    /// * `amount` - The amount, in hundredths of the currency.
+    /// * `currency` - The currency of the amount.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::money::{Currency, Money};
+    ///
+    /// let price = Money::new(1250, Currency::USD);
+    /// assert_eq!(price.to_string(), "12.50 USD");
+    /// ```
+    pub fn new(amount: i64, currency: Currency) -> Self {
+        Money { amount, currency }
+    }
+
+    /// Creates an amount of zero in the given currency.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::money::{Currency, Money};
+    ///
+    /// assert_eq!(Money::zero(Currency::EUR).amount(), 0);
+    /// ```
+    pub fn zero(currency: Currency) -> Self {
+        Money::new(0, currency)
+    }
+
+    /// Returns the amount, in hundredths of the currency.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::money::Money;
+    ///
+    /// assert_eq!(Money::from(12).amount(), 1200);
+    /// ```
+    pub fn amount(&self) -> i64 {
+        self.amount
+    }
+
+    /// Returns the currency of the amount.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::money::{Currency, Money};
+    ///
+    /// assert_eq!(Money::from(12).currency(), Currency::EUR);
+    /// ```
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Adds two amounts of money. Zero is zero in any currency, so adding it keeps the
+    /// currency of the other amount.
+    ///
+    /// # Arguments
+    /// * `other` - The amount to add to this one.
+    ///
+    /// # Errors
+    /// * `MoneyError::MixedCurrencies` - If both amounts aren't zero and their currencies differ.
+    /// * `MoneyError::Overflow` - If the sum doesn't fit the amount.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::money::{Currency, Money};
+    ///
+    /// let euros = Money::new(150, Currency::EUR);
+    /// assert_eq!(euros.checked_add(euros).unwrap(), Money::new(300, Currency::EUR));
+    ///
+    /// let dollars = Money::new(150, Currency::USD);
+    /// assert!(euros.checked_add(dollars).is_err());
+    /// assert_eq!(Money::zero(Currency::EUR).checked_add(dollars).unwrap(), dollars);
+    /// ```
+    pub fn checked_add(self, other: Money) -> Result<Self, MoneyError> {
+        let currency = self.common_currency(other)?;
+        let amount = self
+            .amount
+            .checked_add(other.amount)
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Money::new(amount, currency))
+    }
+
+    /// Subtracts an amount of money from this one. Like for additions, zero is zero in any
+    /// currency.
+    ///
+    /// # Arguments
+    /// * `other` - The amount to subtract from this one.
+    ///
+    /// # Errors
+    /// * `MoneyError::MixedCurrencies` - If both amounts aren't zero and their currencies differ.
+    /// * `MoneyError::Overflow` - If the difference doesn't fit the amount.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::money::{Currency, Money};
+    ///
+    /// let budget = Money::new(1000, Currency::EUR);
+    /// let spent = Money::new(1500, Currency::EUR);
+    /// assert_eq!(budget.checked_sub(spent).unwrap().amount(), -500);
+    /// assert!(budget.checked_sub(Money::new(1, Currency::GBP)).is_err());
+    /// ```
+    pub fn checked_sub(self, other: Money) -> Result<Self, MoneyError> {
+        let currency = self.common_currency(other)?;
+        let amount = self
+            .amount
+            .checked_sub(other.amount)
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Money::new(amount, currency))
+    }
+
+    /// Multiplies the amount by a factor, like a quantity or a number of hours.
+    ///
+    /// # Arguments
+    /// * `factor` - The factor to multiply the amount by.
+    ///
+    /// # Errors
+    /// * `MoneyError::Overflow` - If the product doesn't fit the amount.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::money::{Currency, Money};
+    ///
+    /// let rate = Money::new(4050, Currency::EUR);
+    /// assert_eq!(rate.checked_mul(8).unwrap(), Money::new(32400, Currency::EUR));
+    /// assert!(rate.checked_mul(i64::MAX).is_err());
+    /// ```
+    pub fn checked_mul(self, factor: i64) -> Result<Self, MoneyError> {
+        let amount = self
+            .amount
+            .checked_mul(factor)
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Money::new(amount, self.currency))
+    }
+
+    /// Returns the amount in whole units of the currency, with the hundredths as the
+    /// fractional part. Useful for statistics, where a few cents of rounding don't matter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::money::{Currency, Money};
+    ///
+    /// assert_eq!(Money::new(1250, Currency::EUR).to_major(), 12.5);
+    /// ```
+    pub fn to_major(&self) -> f64 {
+        // Precision loss is irrelevant at the scale of project costs.
+        #[allow(clippy::cast_precision_loss)]
+        let amount = self.amount as f64;
+        amount / 100.0
+    }
+
+    /// Returns the currency two amounts can be combined in.
+    fn common_currency(self, other: Money) -> Result<Currency, MoneyError> {
+        if self.currency == other.currency || other.amount == 0 {
+            Ok(self.currency)
+        } else if self.amount == 0 {
+            Ok(other.currency)
+        } else {
+            Err(MoneyError::MixedCurrencies(self.currency, other.currency))
+        }
+    }
+}
+
+/// A whole number of units of the default currency, for amounts without a currency.
+impl From<u16> for Money {
+    fn from(units: u16) -> Self {
+        Money::new(i64::from(units) * 100, Currency::default())
+    }
+}
+
+/// Formats the amount with two decimal places, followed by the currency code.
+impl Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.amount < 0 { "-" } else { "" };
+        let amount = self.amount.unsigned_abs();
+        write!(
+            f,
+            "{sign}{}.{:02} {}",
+            amount / 100,
+            amount % 100,
+            self.currency
+        )
+    }
+}
+
+/// Amounts are read either in full, or as a whole number of units of the default currency,
+/// the way they were stored before they had a currency.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Money {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum MoneyRepr {
+            Units(u16),
+            Full { amount: i64, currency: Currency },
+        }
+
+        Ok(match MoneyRepr::deserialize(deserializer)? {
+            MoneyRepr::Units(units) => Money::from(units),
+            MoneyRepr::Full { amount, currency } => Money::new(amount, currency),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn sums_in_one_currency_match_integer_sums(a: i32, b: i32) {
+            let sum = Money::new(a.into(), Currency::USD)
+                .checked_add(Money::new(b.into(), Currency::USD))
+                .unwrap();
+            prop_assert_eq!(sum, Money::new(i64::from(a) + i64::from(b), Currency::USD));
+        }
+    }
+
+    #[test]
+    fn mixed_currencies_and_overflows_are_rejected() {
+        let euros = Money::new(100, Currency::EUR);
+        let pounds = Money::new(100, Currency::GBP);
+        assert_eq!(
+            euros.checked_add(pounds),
+            Err(MoneyError::MixedCurrencies(Currency::EUR, Currency::GBP))
+        );
+        assert_eq!(
+            Money::new(i64::MAX, Currency::EUR).checked_add(euros),
+            Err(MoneyError::Overflow)
+        );
+        assert_eq!(
+            Money::new(i64::MIN, Currency::EUR).checked_sub(euros),
+            Err(MoneyError::Overflow)
+        );
+        assert_eq!(pounds.checked_sub(Money::zero(Currency::EUR)), Ok(pounds));
+    }
+
+    #[test]
+    fn money_is_displayed_with_two_decimals() {
+        assert_eq!(Money::from(7).to_string(), "7.00 EUR");
+        assert_eq!(Money::new(-5, Currency::USD).to_string(), "-0.05 USD");
+        assert_eq!(
+            Money::new(i64::MIN, Currency::GBP).to_string(),
+            "-92233720368547758.08 GBP"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn money_round_trips_and_reads_plain_numbers() {
+        let money = Money::new(1250, Currency::USD);
+        let json = serde_json::to_string(&money).unwrap();
+        assert_eq!(json, r#"{"amount":1250,"currency":"USD"}"#);
+        assert_eq!(serde_json::from_str::<Money>(&json).unwrap(), money);
+
+        assert_eq!(
+            serde_json::from_str::<Money>("40").unwrap(),
+            Money::from(40)
+        );
+        assert!(serde_json::from_str::<Money>(r#"{"amount":1,"currency":"usd"}"#).is_err());
+    }
+}
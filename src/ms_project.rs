@@ -0,0 +1,682 @@
+use std::{collections::HashMap, fmt::Write, io::Read};
+
+use anyhow::bail;
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use roxmltree::{Document, Node};
+use thiserror::Error;
+
+use crate::{
+    duration::MAX_DURATION,
+    project::{Project, TimeRelationship},
+    task::Task,
+};
+
+/// The unit of MSPDI link lags, a tenth of a minute, in milliseconds.
+const LAG_UNIT_MS: i64 = 6_000;
+
+/// The file formats Microsoft Project can save a plan in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MsProjectFormat {
+    /// The Microsoft Project XML interchange format (MSPDI), obtained with "Save as XML".
+    Xml,
+    /// The native binary `.mpp` format.
+    Mpp,
+}
+
+/// Represents an error that occurs when importing a Microsoft Project file.
+#[derive(Error, Debug)]
+pub enum MsProjectError {
+    /// Used when the requested format can't be imported yet.
+    #[error(
+        "{0:?} files are not supported yet. Save the plan as XML from Microsoft Project and import that instead"
+    )]
+    UnsupportedFormat(MsProjectFormat),
+    /// Used when the input couldn't be read.
+    #[error("The input couldn't be read")]
+    Io(#[from] std::io::Error),
+    /// Used when the input isn't well formed XML.
+    #[error("The input isn't valid XML")]
+    InvalidXml(#[from] roxmltree::Error),
+    /// Used when an element holds a value that can't be understood.
+    #[error("Element <{element}> has an invalid value: {value}")]
+    InvalidValue {
+        /// The name of the offending element.
+        element: String,
+        /// The value that couldn't be parsed.
+        value: String,
+    },
+    /// Used when a predecessor link references a task that isn't in the file.
+    #[error("Task {0} references a predecessor that doesn't exist")]
+    MissingPredecessor(String),
+    /// Used when the predecessor links would make tasks depend on themselves.
+    #[error("The predecessor links of task {0} form a cycle")]
+    CyclicDependency(String),
+}
+
+impl Project {
+    /// Imports a project saved by Microsoft Project.
+    ///
+    /// Only the XML format (MSPDI) is currently supported. Tasks are imported with their name,
+    /// dates and duration, outline levels become subtask relationships and predecessor links
+    /// become time relationships, with their lag. When a task has both dates, they take precedence over
+    /// its duration. The project summary task (UID 0) is skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The source to read the file from.
+    /// * `format` - The format the file was saved in.
+    ///
+    /// # Errors
+    ///
+    /// * `MsProjectError::UnsupportedFormat` - If `format` is `MsProjectFormat::Mpp`.
+    /// * `MsProjectError::Io` - If the reader fails.
+    /// * `MsProjectError::InvalidXml` - If the input isn't well formed XML.
+    /// * `MsProjectError::InvalidValue` - If a date, duration or number can't be parsed.
+    /// * `MsProjectError::MissingPredecessor` - If a predecessor link points to an unknown task.
+    /// * `MsProjectError::CyclicDependency` - If the predecessor links form a cycle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{ms_project::MsProjectFormat, project::Project};
+    ///
+    /// let xml = r#"<Project xmlns="http://schemas.microsoft.com/project">
+    ///     <Title>World domination</Title>
+    ///     <Tasks>
+    ///         <Task><UID>1</UID><Name>Find a crowbar</Name><Duration>PT8H0M0S</Duration></Task>
+    ///     </Tasks>
+    /// </Project>"#;
+    ///
+    /// let project = Project::from_ms_project(xml.as_bytes(), MsProjectFormat::Xml).unwrap();
+    /// assert_eq!(project.name(), "World domination");
+    /// assert_eq!(project.task(0).unwrap().duration().unwrap().num_hours(), 8);
+    /// ```
+    pub fn from_ms_project(
+        mut reader: impl Read,
+        format: MsProjectFormat,
+    ) -> Result<Project, MsProjectError> {
+        if format == MsProjectFormat::Mpp {
+            return Err(MsProjectError::UnsupportedFormat(format));
+        }
+
+        let mut xml = String::new();
+        reader.read_to_string(&mut xml)?;
+        let document = Document::parse(&xml)?;
+        let root = document.root_element();
+
+        let name = child_text(root, "Title")
+            .or_else(|| child_text(root, "Name"))
+            .unwrap_or_default();
+        let start_date = child_text(root, "StartDate")
+            .map(|s| parse_date("StartDate", s))
+            .transpose()?;
+        let description = child_text(root, "Subject");
+
+        let mut project = Project::builder()
+            .name(name)
+            .maybe_description(description)
+            .maybe_start_date(start_date)
+            .build();
+
+        let task_nodes = root
+            .children()
+            .filter(|n| n.has_tag_name("Tasks"))
+            .flat_map(|n| n.children())
+            .filter(|n| n.has_tag_name("Task"));
+
+        // Maps MSPDI UIDs to task indices within the project.
+        let mut indices: HashMap<&str, usize> = HashMap::new();
+        // The last task seen at each outline level, to rebuild the hierarchy.
+        let mut outline: Vec<usize> = Vec::new();
+        let mut links = Vec::new();
+
+        for node in task_nodes {
+            let uid = child_text(node, "UID").unwrap_or_default();
+            if uid == "0" || child_text(node, "IsNull") == Some("1") {
+                continue;
+            }
+
+            let task = parse_task(node)?;
+            let index = project.task_count();
+            project.add_task(task);
+            indices.insert(uid, index);
+
+            if let Some(level) = child_text(node, "OutlineLevel") {
+                let level = parse_number("OutlineLevel", level)?.max(1);
+                outline.truncate(level - 1);
+                if let Some(&parent) = outline.last() {
+                    project.add_subtask(parent, index);
+                }
+                outline.push(index);
+            }
+
+            for link in node
+                .children()
+                .filter(|n| n.has_tag_name("PredecessorLink"))
+            {
+                let predecessor = child_text(link, "PredecessorUID").unwrap_or_default();
+                let kind = match child_text(link, "Type").unwrap_or("1") {
+                    "0" => TimeRelationship::FinishToFinish,
+                    "1" => TimeRelationship::FinishToStart,
+                    "2" => TimeRelationship::StartToFinish,
+                    "3" => TimeRelationship::StartToStart,
+                    other => {
+                        return Err(MsProjectError::InvalidValue {
+                            element: "Type".to_owned(),
+                            value: other.to_owned(),
+                        });
+                    }
+                };
+                let lag = match child_text(link, "LinkLag") {
+                    Some(lag) => parse_lag(lag)?,
+                    None => Duration::zero(),
+                };
+                links.push((uid, predecessor, kind, lag));
+            }
+        }
+
+        for (uid, predecessor, kind, lag) in links {
+            let (Some(&successor), Some(&predecessor)) =
+                (indices.get(uid), indices.get(predecessor))
+            else {
+                return Err(MsProjectError::MissingPredecessor(uid.to_owned()));
+            };
+            project
+                .add_time_relationship_with_lag(predecessor, successor, kind, lag)
+                .map_err(|_| MsProjectError::CyclicDependency(uid.to_owned()))?;
+        }
+
+        Ok(project)
+    }
+}
+
+impl Project {
+    /// Exports the project in the Microsoft Project XML interchange format (MSPDI), which
+    /// Microsoft Project opens as a plan.
+    ///
+    /// Tasks are exported with their name, description, dates and duration. Subtasks follow
+    /// their parent task with a deeper outline level, and time relationships become predecessor
+    /// links, with their lag rounded to the nearest tenth of a minute, the precision of MSPDI.
+    /// The UID of a task is its index plus one, since UID 0 is the project summary task. Dates
+    /// are written in UTC, as MSPDI dates don't carry a timezone.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the subtask relationships form a cycle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::{ms_project::MsProjectFormat, project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// let mut task = Task::new("Find a crowbar");
+    /// task.edit_duration(Duration::minutes(90).try_into().unwrap());
+    /// project.add_task(task);
+    /// project.add_task(Task::new("Profit"));
+    /// project.add_time_relationship(0, 1, TimeRelationship::FinishToStart).unwrap();
+    ///
+    /// let xml = project.to_mspdi().unwrap();
+    /// assert!(xml.contains("<Duration>PT1H30M0S</Duration>"));
+    ///
+    /// let imported = Project::from_ms_project(xml.as_bytes(), MsProjectFormat::Xml).unwrap();
+    /// assert_eq!(imported.predecessors_indices(1).collect::<Vec<_>>(), vec![0]);
+    /// ```
+    pub fn to_mspdi(&self) -> anyhow::Result<String> {
+        let mut xml = String::new();
+        writeln!(
+            xml,
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#
+        )?;
+        writeln!(
+            xml,
+            r#"<Project xmlns="http://schemas.microsoft.com/project">"#
+        )?;
+        writeln!(xml, "    <Title>{}</Title>", escape(self.name()))?;
+        if let Some(description) = self.description() {
+            writeln!(xml, "    <Subject>{}</Subject>", escape(description))?;
+        }
+        if let Some(start_date) = self.start_date() {
+            writeln!(
+                xml,
+                "    <StartDate>{}</StartDate>",
+                format_date(start_date)
+            )?;
+        }
+        writeln!(xml, "    <Tasks>")?;
+        for (id, (i, level)) in self.outline()?.into_iter().enumerate() {
+            let Some(task) = self.task(i) else {
+                continue;
+            };
+            writeln!(xml, "        <Task>")?;
+            writeln!(xml, "            <UID>{}</UID>", i + 1)?;
+            writeln!(xml, "            <ID>{}</ID>", id + 1)?;
+            writeln!(xml, "            <Name>{}</Name>", escape(task.name()))?;
+            if !task.description().is_empty() {
+                writeln!(
+                    xml,
+                    "            <Notes>{}</Notes>",
+                    escape(task.description())
+                )?;
+            }
+            writeln!(xml, "            <OutlineLevel>{level}</OutlineLevel>")?;
+            if let Some(start) = task.start() {
+                writeln!(xml, "            <Start>{}</Start>", format_date(start))?;
+            }
+            if let Some(finish) = task.finish() {
+                writeln!(xml, "            <Finish>{}</Finish>", format_date(finish))?;
+            }
+            if let Some(duration) = task.duration() {
+                writeln!(
+                    xml,
+                    "            <Duration>{}</Duration>",
+                    format_duration(*duration)
+                )?;
+            }
+            for predecessor in self.predecessors_indices(i) {
+                let kind = match self.time_relationship(predecessor, i).unwrap_or_default() {
+                    TimeRelationship::FinishToFinish => 0,
+                    TimeRelationship::FinishToStart => 1,
+                    TimeRelationship::StartToFinish => 2,
+                    TimeRelationship::StartToStart => 3,
+                };
+                writeln!(xml, "            <PredecessorLink>")?;
+                writeln!(
+                    xml,
+                    "                <PredecessorUID>{}</PredecessorUID>",
+                    predecessor + 1
+                )?;
+                writeln!(xml, "                <Type>{kind}</Type>")?;
+                writeln!(
+                    xml,
+                    "                <LinkLag>{}</LinkLag>",
+                    format_lag(self.lag(predecessor, i).unwrap_or_default())
+                )?;
+                writeln!(xml, "                <LagFormat>7</LagFormat>")?;
+                writeln!(xml, "            </PredecessorLink>")?;
+            }
+            writeln!(xml, "        </Task>")?;
+        }
+        writeln!(xml, "    </Tasks>")?;
+        writeln!(xml, "</Project>")?;
+        Ok(xml)
+    }
+
+    /// Orders the tasks so that every task is followed by its subtasks, as MSPDI expects.
+    /// Tasks without a parent come in index order, each subtask after the first of its parents.
+    /// Subtask relationships pointing to tasks that don't exist are skipped.
+    ///
+    /// # Returns
+    ///
+    /// The index of every task, with its outline level: 1 for tasks without a parent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the subtask relationships form a cycle.
+    pub(crate) fn outline(&self) -> anyhow::Result<Vec<(usize, usize)>> {
+        let count = self.task_count();
+        let subtasks = |i: usize| {
+            let mut subtasks = self.subtasks(i);
+            subtasks.retain(|&s| self.contains_task(s));
+            subtasks
+        };
+        let mut has_parent = vec![false; count];
+        for i in 0..count {
+            for subtask in subtasks(i) {
+                has_parent[subtask] = true;
+            }
+        }
+
+        let mut outline = Vec::with_capacity(count);
+        let mut visited = vec![false; count];
+        // The parents of the task being visited, from the outermost one.
+        let mut ancestors: Vec<usize> = Vec::new();
+        let mut pending: Vec<(usize, usize)> = (0..count)
+            .rev()
+            .filter(|&i| !has_parent[i])
+            .map(|i| (i, 1))
+            .collect();
+        while let Some((i, level)) = pending.pop() {
+            ancestors.truncate(level - 1);
+            let Some(visited) = visited.get_mut(i) else {
+                continue;
+            };
+            if *visited {
+                continue;
+            }
+            *visited = true;
+            outline.push((i, level));
+            ancestors.push(i);
+
+            let subtasks = subtasks(i);
+            if subtasks.iter().any(|s| ancestors.contains(s)) {
+                bail!("The subtask relationships of task {i} form a cycle");
+            }
+            pending.extend(subtasks.into_iter().rev().map(|s| (s, level + 1)));
+        }
+
+        if let Some(i) = visited.iter().position(|visited| !visited) {
+            bail!("The subtask relationships of task {i} form a cycle");
+        }
+        Ok(outline)
+    }
+}
+
+/// Builds a [`Task`] out of a `<Task>` element.
+fn parse_task(node: Node) -> Result<Task, MsProjectError> {
+    let mut task = Task::new(child_text(node, "Name").unwrap_or_default());
+    if let Some(notes) = child_text(node, "Notes") {
+        task.edit_description(notes);
+    }
+    if let Some(duration) = child_text(node, "Duration") {
+        let duration = parse_duration(duration)?
+            .try_into()
+            .map_err(|_| invalid_value("Duration", duration))?;
+        task.edit_duration(duration);
+    }
+    if let Some(start) = child_text(node, "Start") {
+        task.edit_start(parse_date("Start", start)?)
+            .map_err(|_| invalid_value("Start", start))?;
+    }
+    if let Some(finish) = child_text(node, "Finish") {
+        let finish_date = parse_date("Finish", finish)?;
+        if task.finish() != Some(finish_date) {
+            task.edit_finish(finish_date)
+                .map_err(|_| invalid_value("Finish", finish))?;
+        }
+    }
+    Ok(task)
+}
+
+/// Returns the trimmed text of the first child element called `name`, if any.
+fn child_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.children()
+        .find(|n| n.has_tag_name(name))
+        .and_then(|n| n.text())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+/// Parses MSPDI dates, which don't carry a timezone and are read as UTC.
+fn parse_date(element: &str, value: &str) -> Result<DateTime<Utc>, MsProjectError> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S")
+        .map(|date| date.and_utc())
+        .map_err(|_| invalid_value(element, value))
+}
+
+/// Parses MSPDI durations in the `PTxHxMxS` format, up to [`MAX_DURATION`] milliseconds.
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_in_result)]
+fn parse_duration(value: &str) -> Result<Duration, MsProjectError> {
+    static RE: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"^PT([0-9]+)H([0-9]+)M([0-9]+)(?:\.[0-9]+)?S$")
+            .expect("It wasn't possible to compile a hardcoded regex. This is a bug.")
+    });
+    let captures = RE
+        .captures(value)
+        .ok_or_else(|| invalid_value("Duration", value))?;
+    let part = |i: usize| -> Result<i64, MsProjectError> {
+        captures[i]
+            .parse::<i64>()
+            .map_err(|_| invalid_value("Duration", value))
+    };
+    let duration = Duration::try_hours(part(1)?)
+        .zip(Duration::try_minutes(part(2)?))
+        .zip(Duration::try_seconds(part(3)?))
+        .and_then(|((hours, minutes), seconds)| hours.checked_add(&minutes)?.checked_add(&seconds))
+        .filter(|duration| *duration <= Duration::milliseconds(MAX_DURATION))
+        .ok_or_else(|| invalid_value("Duration", value))?;
+    Ok(duration)
+}
+
+/// Formats a lag as an MSPDI link lag, rounded to the nearest tenth of a minute. Halves are
+/// rounded up.
+fn format_lag(lag: Duration) -> i64 {
+    lag.num_milliseconds()
+        .saturating_add(LAG_UNIT_MS / 2)
+        .div_euclid(LAG_UNIT_MS)
+}
+
+/// Parses MSPDI link lags, which are counted in tenths of a minute, up to [`MAX_DURATION`]
+/// milliseconds either way.
+fn parse_lag(value: &str) -> Result<Duration, MsProjectError> {
+    value
+        .parse::<i64>()
+        .ok()
+        .and_then(|tenths| tenths.checked_mul(LAG_UNIT_MS))
+        .filter(|lag| lag.unsigned_abs() <= MAX_DURATION.unsigned_abs())
+        .map(Duration::milliseconds)
+        .ok_or_else(|| invalid_value("LinkLag", value))
+}
+
+/// Formats dates as MSPDI expects them, without a timezone.
+fn format_date(date: DateTime<Utc>) -> String {
+    date.format("%Y-%m-%dT%H:%M:%S").to_string()
+}
+
+/// Formats durations in the MSPDI `PTxHxMxS` format.
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.num_seconds();
+    format!(
+        "PT{}H{}M{}S",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+/// Escapes the characters that have a meaning in XML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Parses a non negative integer.
+fn parse_number(element: &str, value: &str) -> Result<usize, MsProjectError> {
+    value.parse().map_err(|_| invalid_value(element, value))
+}
+
+/// Shorthand to build an [`MsProjectError::InvalidValue`].
+fn invalid_value(element: &str, value: &str) -> MsProjectError {
+    MsProjectError::InvalidValue {
+        element: element.to_owned(),
+        value: value.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/world_domination.xml");
+
+    #[test]
+    fn mpp_is_reported_as_unsupported() {
+        let result = Project::from_ms_project(FIXTURE.as_bytes(), MsProjectFormat::Mpp);
+
+        assert!(matches!(
+            result,
+            Err(MsProjectError::UnsupportedFormat(MsProjectFormat::Mpp))
+        ));
+    }
+
+    #[test]
+    fn xml_fixture_is_imported() {
+        let project = Project::from_ms_project(FIXTURE.as_bytes(), MsProjectFormat::Xml).unwrap();
+
+        assert_eq!(project.name(), "World domination");
+        assert_eq!(
+            project.start_date(),
+            Some(Utc.with_ymd_and_hms(2025, 1, 6, 8, 0, 0).unwrap())
+        );
+        assert_eq!(project.tasks().count(), 4);
+
+        let crowbar = project.task(1).unwrap();
+        assert_eq!(crowbar.name(), "Find a crowbar");
+        assert_eq!(
+            crowbar.start(),
+            Some(Utc.with_ymd_and_hms(2025, 1, 6, 8, 0, 0).unwrap())
+        );
+        assert_eq!(*crowbar.duration().unwrap(), Duration::hours(8));
+
+        // Outline levels become subtasks.
+        assert_eq!(project.subtasks(0), vec![1, 2]);
+
+        // Predecessor links become time relationships.
+        assert_eq!(project.predecessors_indices(2).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(project.predecessors_indices(3).collect::<Vec<_>>(), vec![0]);
+    }
+
+    #[test]
+    fn invalid_durations_are_rejected() {
+        let xml = r#"<Project><Tasks><Task><UID>1</UID><Duration>8 hours</Duration></Task></Tasks></Project>"#;
+
+        assert!(matches!(
+            Project::from_ms_project(xml.as_bytes(), MsProjectFormat::Xml),
+            Err(MsProjectError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn overlong_durations_are_rejected() {
+        let xml = r#"<Project><Tasks><Task><UID>1</UID><Duration>PT9999999999999H0M0S</Duration></Task></Tasks></Project>"#;
+
+        assert!(matches!(
+            Project::from_ms_project(xml.as_bytes(), MsProjectFormat::Xml),
+            Err(MsProjectError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn exported_projects_are_imported_back() {
+        let project = Project::from_ms_project(FIXTURE.as_bytes(), MsProjectFormat::Xml).unwrap();
+        let xml = project.to_mspdi().unwrap();
+        let imported = Project::from_ms_project(xml.as_bytes(), MsProjectFormat::Xml).unwrap();
+
+        assert_eq!(imported.name(), project.name());
+        assert_eq!(imported.start_date(), project.start_date());
+        assert_eq!(
+            imported.tasks().collect::<Vec<_>>(),
+            project.tasks().collect::<Vec<_>>()
+        );
+        assert_eq!(imported.subtasks(0), vec![1, 2]);
+        assert_eq!(
+            imported.predecessors_indices(2).collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert_eq!(
+            imported.predecessors_indices(3).collect::<Vec<_>>(),
+            vec![0]
+        );
+    }
+
+    #[test]
+    fn lags_are_exported_and_imported_back() {
+        let mut project = Project::new("World domination");
+        for name in ["Pour the concrete", "Build the walls", "Paint the walls"] {
+            project.add_task(Task::new(name));
+        }
+        project
+            .add_time_relationship_with_lag(
+                0,
+                1,
+                TimeRelationship::FinishToStart,
+                Duration::days(2),
+            )
+            .unwrap();
+        project
+            .add_time_relationship_with_lag(
+                1,
+                2,
+                TimeRelationship::StartToStart,
+                Duration::minutes(-90),
+            )
+            .unwrap();
+
+        let xml = project.to_mspdi().unwrap();
+        assert!(xml.contains("<LinkLag>28800</LinkLag>"));
+        let imported = Project::from_ms_project(xml.as_bytes(), MsProjectFormat::Xml).unwrap();
+
+        assert_eq!(imported.lag(0, 1), Some(Duration::days(2)));
+        assert_eq!(imported.lag(1, 2), Some(Duration::minutes(-90)));
+        assert_eq!(
+            imported.time_relationship(1, 2),
+            Some(TimeRelationship::StartToStart)
+        );
+
+        let overlong = xml.replace(
+            "<LinkLag>28800</LinkLag>",
+            "<LinkLag>9999999999999</LinkLag>",
+        );
+        assert!(matches!(
+            Project::from_ms_project(overlong.as_bytes(), MsProjectFormat::Xml),
+            Err(MsProjectError::InvalidValue { .. })
+        ));
+
+        // Lags are rounded to the nearest tenth of a minute, halves up.
+        for (lag, tenths) in [(2_999, 0), (3_000, 1), (-3_000, 0), (-3_001, -1)] {
+            let mut project = Project::new("World domination");
+            project.add_task(Task::new("Pour the concrete"));
+            project.add_task(Task::new("Build the walls"));
+            project
+                .add_time_relationship_with_lag(
+                    0,
+                    1,
+                    TimeRelationship::FinishToStart,
+                    Duration::milliseconds(lag),
+                )
+                .unwrap();
+            let xml = project.to_mspdi().unwrap();
+            assert!(
+                xml.contains(&format!("<LinkLag>{tenths}</LinkLag>")),
+                "{lag}"
+            );
+        }
+    }
+
+    #[test]
+    fn exported_tasks_follow_their_parents() {
+        let mut project = Project::new("Fish & <chips>");
+        for name in ["Profit", "Get equipped", "Find a crowbar"] {
+            project.add_task(Task::new(name));
+        }
+        project.add_subtask(1, 2);
+        project.add_subtask(2, 0);
+        project
+            .add_time_relationship(0, 2, TimeRelationship::StartToStart)
+            .unwrap();
+
+        let xml = project.to_mspdi().unwrap();
+        assert!(xml.contains("<Title>Fish &amp; &lt;chips&gt;</Title>"));
+        assert!(xml.contains("<PredecessorUID>1</PredecessorUID>\n                <Type>3</Type>"));
+        let names: Vec<&str> = xml
+            .lines()
+            .filter_map(|l| l.trim().strip_prefix("<Name>")?.strip_suffix("</Name>"))
+            .collect();
+        assert_eq!(names, vec!["Get equipped", "Find a crowbar", "Profit"]);
+
+        project.add_subtask(0, 1);
+        assert!(project.to_mspdi().is_err());
+    }
+
+    #[test]
+    fn dangling_subtasks_are_skipped_on_export() {
+        let mut project = Project::new("World domination");
+        project.add_task(Task::new("Find a crowbar"));
+        project.add_subtask(0, 5);
+        project.add_subtask(7, 0);
+
+        assert_eq!(project.outline().unwrap(), vec![(0, 1)]);
+        let xml = project.to_mspdi().unwrap();
+        assert!(xml.contains("<Name>Find a crowbar</Name>"));
+        assert!(xml.contains("<OutlineLevel>1</OutlineLevel>"));
+    }
+}
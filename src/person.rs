@@ -1,25 +1,32 @@
-use anyhow::Context;
+use anyhow::{Context, bail};
 pub use email_address::EmailAddress;
 use nutype::nutype;
 pub use phonenumber::PhoneNumber;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "PersonRepr", into = "PersonRepr")
+)]
 /// Represents a person with a name and contact information.
 pub struct Person {
     /// The first name of the person.
     first_name: NameString,
+    /// The middle name of the person, if any.
+    middle_name: Option<NameString>,
     /// The last name of the person.
     last_name: NameString,
-    /// The email address of the person.
-    email: Option<EmailAddress>,
-    /// The phone number of the person.
-    phone: Option<PhoneNumber>,
+    /// The email addresses of the person, the primary one first.
+    emails: Vec<EmailAddress>,
+    /// The phone numbers of the person, the primary one first.
+    phones: Vec<PhoneNumber>,
 }
 
 #[nutype(
     sanitize(trim),
     validate(not_empty, len_char_max = NAME_LEN),
-    derive(Debug, Eq, PartialEq, Clone, Display, Deref)
+    derive(Debug, Eq, PartialEq, Clone, Hash, Display, Deref)
 )]
 pub struct NameString(String);
 
@@ -46,16 +53,46 @@ impl Person {
 
         Some(Person {
             first_name: name,
+            middle_name: None,
             last_name: surname,
-            email: None,
-            phone: None,
+            emails: Vec::new(),
+            phones: Vec::new(),
         })
     }
 
-    /// Add or edit the email address of the person.
+    /// Create a new `Person` with a middle name.
     ///
     /// # Arguments
-    /// * `email` - The new email address of the person.
+    /// * `name` - The first name of the person.
+    /// * `middle_name` - The middle name of the person.
+    /// * `surname` - The last name of the person.
+    ///
+    /// # Returns
+    /// A new `Person` instance, or `None` if any of the names isn't valid.
+    ///
+    /// # Examples
+    /// ```
+    /// use planter_core::person::Person;
+    ///
+    /// let person = Person::with_middle_name("Margherita", "Teresa", "Hack").unwrap();
+    /// assert_eq!(person.full_name(), "Margherita Teresa Hack");
+    /// assert!(Person::with_middle_name("Margherita", " ", "Hack").is_none());
+    /// ```
+    pub fn with_middle_name(
+        name: impl Into<String>,
+        middle_name: impl Into<String>,
+        surname: impl Into<String>,
+    ) -> Option<Self> {
+        let mut person = Person::new(name, surname)?;
+        person.middle_name = Some(NameString::try_new(middle_name).ok()?);
+        Some(person)
+    }
+
+    /// Set the primary email address of the person, the first one. It replaces the current
+    /// primary email address, if there is one.
+    ///
+    /// # Arguments
+    /// * `email` - The new primary email address of the person.
     ///
     /// # Examples
     /// ```
@@ -66,13 +103,78 @@ impl Person {
     /// let mut person = Person::new("Margherita", "Hack").unwrap();
     /// let email = EmailAddress::from_str("margherita.hack@example.com").unwrap();
     /// person.update_email(email.clone());
-    /// assert_eq!(person.email(), &Some(email));
+    /// assert_eq!(person.email(), Some(&email));
     /// ```
     pub fn update_email(&mut self, email: EmailAddress) {
-        self.email = Some(email);
+        self.emails.retain(|e| *e != email);
+        match self.emails.first_mut() {
+            Some(primary) => *primary = email,
+            None => self.emails.push(email),
+        }
+    }
+
+    /// Add an email address to the person, after the ones they already have.
+    ///
+    /// # Arguments
+    /// * `email` - The email address to add.
+    ///
+    /// # Errors
+    /// Returns an error if the person already has the email address.
+    ///
+    /// # Examples
+    /// ```
+    /// use planter_core::person::Person;
+    /// use email_address::EmailAddress;
+    /// use std::str::FromStr;
+    ///
+    /// let mut person = Person::new("Margherita", "Hack").unwrap();
+    /// let work = EmailAddress::from_str("m.hack@example.com").unwrap();
+    /// let home = EmailAddress::from_str("margherita@example.org").unwrap();
+    /// person.add_email(work.clone()).unwrap();
+    /// person.add_email(home.clone()).unwrap();
+    ///
+    /// assert_eq!(person.email(), Some(&work));
+    /// assert_eq!(person.emails(), &[work.clone(), home]);
+    /// assert!(person.add_email(work).is_err());
+    /// ```
+    pub fn add_email(&mut self, email: EmailAddress) -> anyhow::Result<()> {
+        if self.emails.contains(&email) {
+            bail!("The person already has the email address {email}");
+        }
+        self.emails.push(email);
+        Ok(())
+    }
+
+    /// Remove the email address at the given position.
+    ///
+    /// # Arguments
+    /// * `index` - The position of the email address, 0 being the primary one.
+    ///
+    /// # Returns
+    /// * `Some(EmailAddress)` - The removed email address.
+    /// * `None` - If there's no email address at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// use planter_core::person::Person;
+    /// use email_address::EmailAddress;
+    /// use std::str::FromStr;
+    ///
+    /// let mut person = Person::new("Margherita", "Hack").unwrap();
+    /// let work = EmailAddress::from_str("m.hack@example.com").unwrap();
+    /// let home = EmailAddress::from_str("margherita@example.org").unwrap();
+    /// person.add_email(work.clone()).unwrap();
+    /// person.add_email(home.clone()).unwrap();
+    ///
+    /// assert_eq!(person.rm_email_at(0), Some(work));
+    /// assert_eq!(person.email(), Some(&home));
+    /// assert!(person.rm_email_at(1).is_none());
+    /// ```
+    pub fn rm_email_at(&mut self, index: usize) -> Option<EmailAddress> {
+        (index < self.emails.len()).then(|| self.emails.remove(index))
     }
 
-    /// Remove the email address of the person.
+    /// Remove all the email addresses of the person.
     ///
     /// # Examples
     /// ```
@@ -83,18 +185,19 @@ impl Person {
     /// let mut person = Person::new("Margherita", "Hack").unwrap();
     /// let email = EmailAddress::from_str("margherita.hack@example.com").unwrap();
     /// person.update_email(email.clone());
-    /// assert_eq!(person.email(), &Some(email));
+    /// assert_eq!(person.email(), Some(&email));
     /// person.rm_email();
     /// assert!(person.email().is_none());
     /// ```
     pub fn rm_email(&mut self) {
-        self.email = None;
+        self.emails.clear();
     }
 
-    /// Add or edit the phone number of the person.
+    /// Set the primary phone number of the person, the first one. It replaces the current
+    /// primary phone number, if there is one.
     ///
     /// # Arguments
-    /// * `phone` - The new phone number of the person.
+    /// * `phone` - The new primary phone number of the person.
     ///
     /// # Examples
     /// ```
@@ -105,13 +208,113 @@ impl Person {
     /// let mut person = Person::new("Margherita", "Hack").unwrap();
     /// let phone = PhoneNumber::from_str("+1234567890").unwrap();
     /// person.update_phone(phone.clone());
-    /// assert_eq!(person.phone(), &Some(phone));
+    /// assert_eq!(person.phone(), Some(&phone));
     /// ```
     pub fn update_phone(&mut self, phone: PhoneNumber) {
-        self.phone = Some(phone);
+        self.phones.retain(|p| *p != phone);
+        match self.phones.first_mut() {
+            Some(primary) => *primary = phone,
+            None => self.phones.push(phone),
+        }
+    }
+
+    /// Parse a phone number written as in the given country, and set it as the primary phone
+    /// number of the person, like [`Person::update_phone`] does. Numbers without a country
+    /// code are read as numbers of `country`.
+    ///
+    /// # Arguments
+    /// * `raw` - The phone number, as written by the user.
+    /// * `country` - The country the number is from, unless it has a country code.
+    ///
+    /// # Errors
+    /// Returns an error if the number can't be parsed, or if it isn't a valid number in its
+    /// country, like a number with too few digits. The person is left untouched in both cases.
+    ///
+    /// # Examples
+    /// ```
+    /// use planter_core::person::Person;
+    /// use phonenumber::country;
+    ///
+    /// let mut person = Person::new("Margherita", "Hack").unwrap();
+    /// person.update_phone_for_region("040 123 4567", country::Id::IT).unwrap();
+    /// assert_eq!(person.phone().unwrap().to_string(), "+390401234567");
+    ///
+    /// assert!(person.update_phone_for_region("040 12", country::Id::IT).is_err());
+    /// assert!(person.update_phone_for_region("not a phone", country::Id::IT).is_err());
+    /// ```
+    pub fn update_phone_for_region(
+        &mut self,
+        raw: &str,
+        country: phonenumber::country::Id,
+    ) -> anyhow::Result<()> {
+        let phone = phonenumber::parse(Some(country), raw)
+            .with_context(|| format!("{raw} isn't a phone number"))?;
+        if !phonenumber::is_valid(&phone) {
+            bail!("{raw} isn't a valid phone number in {country:?}");
+        }
+        self.update_phone(phone);
+        Ok(())
+    }
+
+    /// Add a phone number to the person, after the ones they already have.
+    ///
+    /// # Arguments
+    /// * `phone` - The phone number to add.
+    ///
+    /// # Errors
+    /// Returns an error if the person already has the phone number.
+    ///
+    /// # Examples
+    /// ```
+    /// use planter_core::person::Person;
+    /// use std::str::FromStr;
+    /// use phonenumber::PhoneNumber;
+    ///
+    /// let mut person = Person::new("Margherita", "Hack").unwrap();
+    /// let work = PhoneNumber::from_str("+390401234567").unwrap();
+    /// let home = PhoneNumber::from_str("+390407654321").unwrap();
+    /// person.add_phone(work.clone()).unwrap();
+    /// person.add_phone(home.clone()).unwrap();
+    ///
+    /// assert_eq!(person.phone(), Some(&work));
+    /// assert_eq!(person.phones(), &[work.clone(), home]);
+    /// assert!(person.add_phone(work).is_err());
+    /// ```
+    pub fn add_phone(&mut self, phone: PhoneNumber) -> anyhow::Result<()> {
+        if self.phones.contains(&phone) {
+            bail!("The person already has the phone number {phone}");
+        }
+        self.phones.push(phone);
+        Ok(())
+    }
+
+    /// Remove the phone number at the given position.
+    ///
+    /// # Arguments
+    /// * `index` - The position of the phone number, 0 being the primary one.
+    ///
+    /// # Returns
+    /// * `Some(PhoneNumber)` - The removed phone number.
+    /// * `None` - If there's no phone number at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// use planter_core::person::Person;
+    /// use std::str::FromStr;
+    /// use phonenumber::PhoneNumber;
+    ///
+    /// let mut person = Person::new("Margherita", "Hack").unwrap();
+    /// let phone = PhoneNumber::from_str("+390401234567").unwrap();
+    /// person.add_phone(phone.clone()).unwrap();
+    ///
+    /// assert_eq!(person.rm_phone_at(0), Some(phone));
+    /// assert!(person.phones().is_empty());
+    /// ```
+    pub fn rm_phone_at(&mut self, index: usize) -> Option<PhoneNumber> {
+        (index < self.phones.len()).then(|| self.phones.remove(index))
     }
 
-    /// Remove the phone number of the person.
+    /// Remove all the phone numbers of the person.
     ///
     /// # Examples
     /// ```
@@ -122,15 +325,15 @@ impl Person {
     /// let mut person = Person::new("Margherita", "Hack").unwrap();
     /// let phone = PhoneNumber::from_str("+1234567890").unwrap();
     /// person.update_phone(phone.clone());
-    /// assert_eq!(person.phone(), &Some(phone));
+    /// assert_eq!(person.phone(), Some(&phone));
     /// person.rm_phone();
     /// assert!(person.phone().is_none());
     /// ```
     pub fn rm_phone(&mut self) {
-        self.phone = None;
+        self.phones.clear();
     }
 
-    /// Get the phone number of the person.
+    /// Get the primary phone number of the person, the first one.
     ///
     /// # Examples
     /// ```
@@ -141,13 +344,26 @@ impl Person {
     /// let mut person = Person::new("Margherita", "Hack").unwrap();
     /// let phone = PhoneNumber::from_str("+1234567890").unwrap();
     /// person.update_phone(phone.clone());
-    /// assert_eq!(person.phone(), &Some(phone));
+    /// assert_eq!(person.phone(), Some(&phone));
+    /// ```
+    pub fn phone(&self) -> Option<&PhoneNumber> {
+        self.phones.first()
+    }
+
+    /// Get all the phone numbers of the person, the primary one first.
+    ///
+    /// # Examples
     /// ```
-    pub fn phone(&self) -> &Option<PhoneNumber> {
-        &self.phone
+    /// use planter_core::person::Person;
+    ///
+    /// let person = Person::new("Margherita", "Hack").unwrap();
+    /// assert!(person.phones().is_empty());
+    /// ```
+    pub fn phones(&self) -> &[PhoneNumber] {
+        &self.phones
     }
 
-    /// Get the email of the person.
+    /// Get the primary email address of the person, the first one.
     ///
     /// # Examples
     /// ```
@@ -158,13 +374,26 @@ impl Person {
     /// let mut person = Person::new("Margherita", "Hack").unwrap();
     /// let email = EmailAddress::from_str("margherita.hack@example.com").unwrap();
     /// person.update_email(email.clone());
-    /// assert_eq!(person.email(), &Some(email));
+    /// assert_eq!(person.email(), Some(&email));
     /// ```
-    pub fn email(&self) -> &Option<EmailAddress> {
-        &self.email
+    pub fn email(&self) -> Option<&EmailAddress> {
+        self.emails.first()
     }
 
-    /// Get the name of the person.
+    /// Get all the email addresses of the person, the primary one first.
+    ///
+    /// # Examples
+    /// ```
+    /// use planter_core::person::Person;
+    ///
+    /// let person = Person::new("Margherita", "Hack").unwrap();
+    /// assert!(person.emails().is_empty());
+    /// ```
+    pub fn emails(&self) -> &[EmailAddress] {
+        &self.emails
+    }
+
+    /// Get the name of the person, including the middle name if there is one.
     ///
     /// # Examples
     /// ```
@@ -172,9 +401,15 @@ impl Person {
     ///
     /// let mut person = Person::new("Margherita", "Hack").unwrap();
     /// assert_eq!(person.full_name(), "Margherita Hack");
+    ///
+    /// person.update_middle_name("Teresa").unwrap();
+    /// assert_eq!(person.full_name(), "Margherita Teresa Hack");
     /// ```
     pub fn full_name(&self) -> String {
-        format!("{} {}", self.first_name, self.last_name)
+        match &self.middle_name {
+            Some(middle_name) => format!("{} {middle_name} {}", self.first_name, self.last_name),
+            None => format!("{} {}", self.first_name, self.last_name),
+        }
     }
 
     /// Get the first name of the person.
@@ -212,6 +447,57 @@ impl Person {
         Ok(())
     }
 
+    /// Get the middle name of the person, if any.
+    ///
+    /// # Examples
+    /// ```
+    /// use planter_core::person::Person;
+    ///
+    /// let person = Person::new("Margherita", "Hack").unwrap();
+    /// assert_eq!(person.middle_name(), None);
+    /// ```
+    pub fn middle_name(&self) -> Option<&str> {
+        self.middle_name.as_deref().map(String::as_str)
+    }
+
+    /// Add or edit the middle name of the person.
+    ///
+    /// # Errors
+    ///
+    /// It can return an error, if the input `name` can't be converted to
+    /// `NameString`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::person::Person;
+    ///
+    /// let mut person = Person::new("Margherita", "Hack").unwrap();
+    /// person.update_middle_name("Teresa").unwrap();
+    /// assert_eq!(person.middle_name(), Some("Teresa"));
+    /// assert!(person.update_middle_name("").is_err());
+    /// ```
+    pub fn update_middle_name(&mut self, name: impl Into<String>) -> anyhow::Result<()> {
+        self.middle_name =
+            Some(NameString::try_new(name).context("Input can't be converted into NameString.")?);
+        Ok(())
+    }
+
+    /// Remove the middle name of the person.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use planter_core::person::Person;
+    ///
+    /// let mut person = Person::with_middle_name("Margherita", "Teresa", "Hack").unwrap();
+    /// person.rm_middle_name();
+    /// assert_eq!(person.middle_name(), None);
+    /// ```
+    pub fn rm_middle_name(&mut self) {
+        self.middle_name = None;
+    }
+
     /// Get the last name of the person.
     ///
     /// # Examples
@@ -248,8 +534,101 @@ impl Person {
     }
 }
 
+/// Formats the person as their full name, followed by the primary email address in angle
+/// brackets if there is one.
+///
+/// # Examples
+/// ```
+/// use std::str::FromStr;
+///
+/// use email_address::EmailAddress;
+/// use planter_core::person::Person;
+///
+/// let mut person = Person::new("Margherita", "Hack").unwrap();
+/// assert_eq!(person.to_string(), "Margherita Hack");
+///
+/// person.update_email(EmailAddress::from_str("m.hack@example.com").unwrap());
+/// assert_eq!(format!("{person}"), "Margherita Hack <m.hack@example.com>");
+/// ```
+impl std::fmt::Display for Person {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.full_name())?;
+        if let Some(email) = self.email() {
+            write!(f, " <{email}>")?;
+        }
+        Ok(())
+    }
+}
+
 const NAME_LEN: usize = 50;
 
+/// The serialized form of a [`Person`], validated when it's turned back into one.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PersonRepr {
+    first_name: String,
+    #[serde(default)]
+    middle_name: Option<String>,
+    last_name: String,
+    #[serde(default)]
+    emails: Vec<String>,
+    #[serde(default)]
+    phones: Vec<String>,
+    /// The single email address stored before people could have more than one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+    /// The single phone number stored before people could have more than one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    phone: Option<String>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Person> for PersonRepr {
+    fn from(person: Person) -> Self {
+        PersonRepr {
+            first_name: person.first_name.to_string(),
+            middle_name: person.middle_name.map(|name| name.to_string()),
+            last_name: person.last_name.to_string(),
+            emails: person.emails.iter().map(ToString::to_string).collect(),
+            phones: person
+                .phones
+                .iter()
+                .map(|phone| phone.format().mode(phonenumber::Mode::E164).to_string())
+                .collect(),
+            email: None,
+            phone: None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<PersonRepr> for Person {
+    type Error = anyhow::Error;
+
+    fn try_from(repr: PersonRepr) -> Result<Self, Self::Error> {
+        use std::str::FromStr;
+
+        let mut person = Person::new(repr.first_name, repr.last_name)
+            .context("The first or the last name of the person isn't valid")?;
+        if let Some(middle_name) = repr.middle_name {
+            person
+                .update_middle_name(middle_name)
+                .context("The middle name of the person isn't valid")?;
+        }
+        for email in repr.email.into_iter().chain(repr.emails) {
+            let email = EmailAddress::from_str(&email)
+                .context("The email address of the person isn't valid")?;
+            person.add_email(email)?;
+        }
+        for phone in repr.phone.into_iter().chain(repr.phones) {
+            let phone = PhoneNumber::from_str(&phone)
+                .context("The phone number of the person isn't valid")?;
+            person.add_phone(phone)?;
+        }
+        Ok(person)
+    }
+}
+
 #[cfg(test)]
 /// Test utilities for the `person` module.
 pub mod test_utils {
@@ -260,12 +639,89 @@ pub mod test_utils {
     use proptest::prelude::Strategy;
 
     /// Generate a random email address.
+    ///
+    /// # Panics
+    /// Panics if the generated string isn't a valid email address.
     pub fn email() -> impl Strategy<Value = EmailAddress> {
         r"^\+?[1-9][0-9]{7,14}$".prop_map(|s: String| EmailAddress::from_str(&s).unwrap())
     }
 
     /// Generate a random phone number.
+    ///
+    /// # Panics
+    /// Panics if the generated string isn't a valid phone number.
     pub fn phone_number() -> impl Strategy<Value = PhoneNumber> {
         r"^\d{3}-\d{3}-\d{4}$".prop_map(|s: String| PhoneNumber::from_str(&s).unwrap())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "serde")]
+    use std::str::FromStr;
+
+    #[cfg(feature = "serde")]
+    use email_address::EmailAddress;
+    #[cfg(feature = "serde")]
+    use phonenumber::PhoneNumber;
+
+    use super::Person;
+
+    #[test]
+    fn phones_are_validated_for_their_region() {
+        let mut person = Person::new("Margherita", "Hack").unwrap();
+        person
+            .update_phone_for_region("+1 650 253 0000", phonenumber::country::Id::IT)
+            .unwrap();
+        assert_eq!(person.phone().unwrap().code().value(), 1);
+
+        // Too short to be an italian number, but it would parse.
+        assert!(
+            person
+                .update_phone_for_region("040", phonenumber::country::Id::IT)
+                .is_err()
+        );
+        assert_eq!(person.phones().len(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn people_round_trip_through_json() {
+        let mut person = Person::with_middle_name("Margherita", "Teresa", "Hack").unwrap();
+        person.update_email(EmailAddress::from_str("margherita@hack.it").unwrap());
+        person
+            .add_email(EmailAddress::from_str("m.hack@example.com").unwrap())
+            .unwrap();
+        person.update_phone(PhoneNumber::from_str("+390401234567").unwrap());
+
+        let json = serde_json::to_string(&person).unwrap();
+        assert_eq!(serde_json::from_str::<Person>(&json).unwrap(), person);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn single_contacts_are_still_read() {
+        let json = r#"{"first_name":"Margherita","last_name":"Hack","email":"margherita@hack.it","phone":"+390401234567"}"#;
+        let person = serde_json::from_str::<Person>(json).unwrap();
+
+        assert_eq!(person.emails().len(), 1);
+        assert_eq!(person.email().unwrap().as_str(), "margherita@hack.it");
+        assert_eq!(person.phones().len(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn invalid_people_are_rejected() {
+        let json = serde_json::to_string(&Person::new("Margherita", "Hack").unwrap()).unwrap();
+
+        for invalid in [
+            json.replace(r#""Margherita""#, r#""""#),
+            json.replace(r#""emails":[]"#, r#""emails":["not an email"]"#),
+            json.replace(r#""phones":[]"#, r#""phones":["not a phone"]"#),
+            json.replace(r#""emails":[]"#, r#""emails":["a@b.it","a@b.it"]"#),
+        ] {
+            assert_ne!(invalid, json);
+            assert!(serde_json::from_str::<Person>(&invalid).is_err());
+        }
+    }
+}
@@ -1,25 +1,57 @@
-use std::collections::HashSet;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    ops::{Add, Sub},
+};
 
 use anyhow::{Context, bail};
-use bon::{Builder, builder};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use daggy::{
     Dag,
     petgraph::{
         Direction,
-        visit::{IntoNeighborsDirected, IntoNodeIdentifiers},
+        visit::{EdgeRef, IntoNeighborsDirected, IntoNodeIdentifiers},
     },
 };
 use thiserror::Error;
 
+mod baseline;
+mod builder;
+#[cfg(feature = "csv")]
+mod csv;
+mod dot;
+mod earned_value;
+#[cfg(feature = "serde")]
+mod frontend;
+mod gantt;
+mod history;
+mod normalize;
+#[cfg(feature = "serde")]
+mod persistence;
+mod raci;
+mod scheduling;
+mod stats;
+
+pub use baseline::{Baseline, BaselineComparison, BaselineTask, TaskDelta};
+pub use builder::ProjectBuilder;
+pub use dot::NEAR_CRITICAL_FLOAT;
+pub use earned_value::EarnedValue;
+pub use gantt::GanttRow;
+pub use history::{Change, ChangeLog};
+pub use normalize::NormalizationReport;
+pub use stats::ProjectStats;
+
 use crate::{
+    calendar::Calendar,
+    duration::{MAX_DURATION, PositiveDuration},
+    money::{Currency, Money, MoneyError},
     resources::{Material, Resource},
-    stakeholders::Stakeholder,
-    task::Task,
+    risk::Risk,
+    stakeholders::{Engagement, Stakeholder},
+    task::{Assignment, Task, TaskConstraint, TaskStatus},
 };
 
-#[derive(Debug, Default, Builder)]
-#[builder(on(String, into))]
+#[derive(Debug, Default, Clone)]
 /// Represents a project with a name and a list of resources.
 pub struct Project {
     /// The name of the project.
@@ -29,30 +61,37 @@ pub struct Project {
     /// The start date of the project.
     start_date: Option<DateTime<Utc>>,
     /// The tasks associated with the project.
-    #[builder(default, with = |tasks: impl IntoIterator<Item = Task>| {
-        let mut dag = Dag::new();
-        tasks.into_iter().for_each(|t| _  = dag.add_node(t));
-        dag
-    })]
-    tasks: Dag<Task, TimeRelationship, usize>,
-    #[builder(default)]
+    tasks: Dag<Task, Dependency, usize>,
     subtask_relationships: Vec<SubtaskRelationship>,
     /// The list of resources associated with the project.
-    #[builder(default)]
     resources: Vec<Resource>,
     /// The list of stakeholders associated with the project.
-    #[builder(default)]
     stakeholders: Vec<Stakeholder>,
+    /// Arbitrary key-value metadata, like the client name or a contract number.
+    metadata: BTreeMap<String, String>,
+    /// Snapshots of the plan, from the oldest to the newest.
+    baselines: Vec<Baseline>,
+    /// When work can happen. Without one, work happens around the clock.
+    calendar: Option<Calendar>,
+    /// Whether scheduling adds the buffer of each task to its duration.
+    include_buffers: bool,
+    /// The risk register of the project.
+    risks: Vec<Risk>,
+    /// The changes made to the project, once recording them is enabled.
+    /// It's left out of comparisons and serialization.
+    history: Option<ChangeLog>,
 }
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A given task, might be composed of different subtasks.
 pub struct SubtaskRelationship {
     task: usize,
     subtask: usize,
 }
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The predecessor - successor relationship between tasks.
 pub enum TimeRelationship {
     /// The predecessor has to start for the successor to finish.
@@ -66,6 +105,77 @@ pub enum TimeRelationship {
     StartToStart,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The standard phases of a project, which tasks can be grouped into.
+pub enum Phase {
+    /// The project is evaluated, to understand if it's valid and if it can be delivered.
+    Feasibility,
+    /// Planning and analysis lead to the design of the deliverable.
+    Design,
+    /// The deliverable is built, with integrated quality assurance.
+    Build,
+    /// The deliverables are reviewed and inspected before transition.
+    Test,
+    /// The deliverables are put into use.
+    Deploy,
+    /// The project is closed and its artifacts archived.
+    Close,
+}
+
+impl Phase {
+    /// All the phases, in the order they usually happen.
+    pub const ALL: [Phase; 6] = [
+        Phase::Feasibility,
+        Phase::Design,
+        Phase::Build,
+        Phase::Test,
+        Phase::Deploy,
+        Phase::Close,
+    ];
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// The link between a predecessor and a successor: the kind of relationship and the delay it imposes.
+struct Dependency {
+    /// The kind of relationship between the tasks.
+    kind: TimeRelationship,
+    /// How long the successor has to wait, after the relationship would allow it to go on.
+    /// It's negative for a lead, when the successor can overlap the predecessor.
+    lag: Duration,
+}
+
+/// Projects are equal when all their data is: the tasks are compared by index, together with
+/// the relationships between them, regardless of the order the relationships were added in.
+impl PartialEq for Project {
+    fn eq(&self, other: &Self) -> bool {
+        let relationships = |project: &Project| {
+            let mut relationships: Vec<(usize, usize, Dependency)> = project
+                .tasks
+                .graph()
+                .edge_references()
+                .map(|edge| (edge.source().index(), edge.target().index(), *edge.weight()))
+                .collect();
+            relationships.sort_unstable_by_key(|(source, target, _)| (*source, *target));
+            relationships
+        };
+
+        self.name == other.name
+            && self.description == other.description
+            && self.start_date == other.start_date
+            && self.tasks().eq(other.tasks())
+            && relationships(self) == relationships(other)
+            && self.subtask_relationships == other.subtask_relationships
+            && self.resources == other.resources
+            && self.stakeholders == other.stakeholders
+            && self.metadata == other.metadata
+            && self.baselines == other.baselines
+            && self.calendar == other.calendar
+            && self.include_buffers == other.include_buffers
+            && self.risks == other.risks
+    }
+}
+
 impl Project {
     /// Creates a new project with the given name.
     ///
@@ -94,6 +204,12 @@ impl Project {
             stakeholders: Vec::new(),
             tasks: Dag::new(),
             subtask_relationships: Vec::new(),
+            metadata: BTreeMap::new(),
+            baselines: Vec::new(),
+            calendar: None,
+            include_buffers: false,
+            risks: Vec::new(),
+            history: None,
         }
     }
 
@@ -125,12 +241,85 @@ impl Project {
         self.description.as_deref()
     }
 
-    /// Adds a task to the project.
+    /// Sets a metadata entry on the project, replacing any previous value for the same key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The name of the entry.
+    /// * `value` - The value of the entry.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::project::Project;
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.set_metadata("Client", "Acme");
+    /// assert_eq!(project.metadata("Client"), Some("Acme"));
+    /// ```
+    pub fn set_metadata(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.metadata.insert(key.into(), value.into());
+    }
+
+    /// Returns the value of a metadata entry, if it's set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::project::Project;
+    ///
+    /// let mut project = Project::new("World domination");
+    /// assert!(project.metadata("Client").is_none());
+    /// project.set_metadata("Client", "Acme");
+    /// assert_eq!(project.metadata("Client"), Some("Acme"));
+    /// ```
+    pub fn metadata(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(String::as_str)
+    }
+
+    /// Removes a metadata entry from the project, returning its value if it was set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::project::Project;
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.set_metadata("Client", "Acme");
+    /// assert_eq!(project.remove_metadata("Client").as_deref(), Some("Acme"));
+    /// assert!(project.metadata("Client").is_none());
+    /// ```
+    pub fn remove_metadata(&mut self, key: &str) -> Option<String> {
+        self.metadata.remove(key)
+    }
+
+    /// Returns all the metadata entries of the project, sorted by key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::project::Project;
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.set_metadata("Client", "Acme");
+    /// project.set_metadata("Contract", "C-1138");
+    /// assert_eq!(project.metadata_entries().len(), 2);
+    /// ```
+    pub fn metadata_entries(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+
+    /// Adds a task to the project. If another task of the project has the same identifier,
+    /// like a clone of the task, the new task gets a fresh one.
     ///
     /// # Arguments
     ///
     /// * `task` - The task to add to the project.
     ///
+    /// # Returns
+    ///
+    /// The index of the new task.
+    ///
     /// # Example
     ///
     /// ```
@@ -138,14 +327,57 @@ impl Project {
     ///
     /// let mut project = Project::new("World domination");
     /// assert_eq!(project.tasks().count(), 0);
-    /// project.add_task(Task::new("Become world leader"));
+    /// let index = project.add_task(Task::new("Become world leader"));
     /// assert_eq!(project.tasks().count(), 1);
+    /// assert_eq!(project.task(index).unwrap().name(), "Become world leader");
+    ///
+    /// let copy = project.task(index).unwrap().clone();
+    /// let copy_index = project.add_task(copy);
+    /// assert_ne!(
+    ///     project.task(copy_index).unwrap().id(),
+    ///     project.task(index).unwrap().id()
+    /// );
+    /// ```
+    pub fn add_task(&mut self, mut task: Task) -> usize {
+        if self.tasks().any(|other| other.id() == task.id()) {
+            task.renew_id();
+        }
+        let index = self.tasks.add_node(task).index();
+        self.record(Change::TaskAdded(index));
+        index
+    }
+
+    /// Adds some tasks to the project, in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `tasks` - The tasks to add to the project.
+    ///
+    /// # Returns
+    ///
+    /// The indices of the new tasks, in the same order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Get rich"));
+    /// let indices = project.add_tasks([Task::new("Buy an army"), Task::new("Become world leader")]);
+    /// assert_eq!(indices, vec![1, 2]);
+    ///
+    /// project
+    ///     .add_time_relationship(indices[0], indices[1], TimeRelationship::default())
+    ///     .unwrap();
     /// ```
-    pub fn add_task(&mut self, task: Task) {
-        self.tasks.add_node(task);
+    pub fn add_tasks(&mut self, tasks: impl IntoIterator<Item = Task>) -> Vec<usize> {
+        tasks.into_iter().map(|task| self.add_task(task)).collect()
     }
 
     /// Deletes a task and all references to it from the project.
+    /// The last task takes the index of the deleted one, and its subtask relationships
+    /// follow it.
     ///
     /// # Arguments
     ///
@@ -166,9 +398,52 @@ impl Project {
     /// assert_eq!(project.tasks().count(), 0);
     /// ```
     pub fn rm_task(&mut self, i: usize) -> anyhow::Result<()> {
-        self.tasks
+        let last = self.tasks.node_count().saturating_sub(1);
+        // The relationships are only gathered for the history, when it's enabled.
+        let relationships = match self.history {
+            Some(_) if i <= last => {
+                let graph = self.tasks.graph();
+                (graph.edges_directed(i.into(), Direction::Incoming))
+                    .chain(graph.edges_directed(i.into(), Direction::Outgoing))
+                    .map(|edge| {
+                        let dependency = edge.weight();
+                        let (predecessor, successor) = (edge.source(), edge.target());
+                        (
+                            predecessor.index(),
+                            successor.index(),
+                            dependency.kind,
+                            dependency.lag,
+                        )
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        let task = self
+            .tasks
             .remove_node(i.into())
             .context("Tried removing a non existing node from Dag")?;
+
+        // Removing a node moves the last one into its place.
+        let moved = |index: usize| if index == last { i } else { index };
+        let mut subtask_relationships = Vec::new();
+        self.subtask_relationships.retain(|relationship| {
+            let kept = relationship.task != i && relationship.subtask != i;
+            if !kept {
+                subtask_relationships.push((relationship.task, relationship.subtask));
+            }
+            kept
+        });
+        for relationship in &mut self.subtask_relationships {
+            relationship.task = moved(relationship.task);
+            relationship.subtask = moved(relationship.subtask);
+        }
+        self.record(Change::TaskRemoved {
+            index: i,
+            task: Box::new(task),
+            relationships,
+            subtask_relationships,
+        });
         Ok(())
     }
 
@@ -214,6 +489,41 @@ impl Project {
         self.tasks.node_weight_mut(index.into())
     }
 
+    /// Renames the task with the given index. Unlike renaming it through
+    /// [`Project::task_mut`], the change is recorded in the history of the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the task.
+    /// * `name` - The new name of the task.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the task doesn't exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Become world leader"));
+    ///
+    /// project.rename_task(0, "Become world's biggest loser").unwrap();
+    /// assert_eq!(project.task(0).unwrap().name(), "Become world's biggest loser");
+    /// assert!(project.rename_task(1, "Gloat").is_err());
+    /// ```
+    pub fn rename_task(&mut self, index: usize, name: impl Into<String>) -> anyhow::Result<()> {
+        let task = self
+            .task_mut(index)
+            .with_context(|| format!("Tried renaming the non existing task {index}"))?;
+        let name = name.into();
+        let old = task.name().to_owned();
+        task.edit_name(name.clone());
+        self.record(Change::TaskRenamed(index, old, name));
+        Ok(())
+    }
+
     /// Returns the tasks of the project.
     ///
     /// # Example
@@ -244,626 +554,3132 @@ impl Project {
         self.tasks.node_weights_mut()
     }
 
-    /// Adds a relationship betwen tasks, where one is the predecessor and the other one a successor.
-    ///
-    /// # Errors
-    /// TODO!
+    /// Returns the number of tasks in the project.
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    /// use planter_core::{project::Project, task::Task};
     ///
     /// let mut project = Project::new("World domination");
-    /// project.add_task(Task::new("Get rich"));
+    /// assert_eq!(project.task_count(), 0);
     /// project.add_task(Task::new("Become world leader"));
-    /// project.add_time_relationship(0, 1, TimeRelationship::default());
+    /// assert_eq!(project.task_count(), 1);
+    /// ```
+    pub fn task_count(&self) -> usize {
+        self.tasks.node_count()
+    }
+
+    /// Checks whether the project has no tasks.
+    ///
+    /// # Example
     ///
-    /// assert_eq!(project.successors(0).next().unwrap().name(), "Become world leader")
     /// ```
-    pub fn add_time_relationship(
-        &mut self,
-        predecessor_index: usize,
-        successor_index: usize,
-        kind: TimeRelationship,
-    ) -> anyhow::Result<()> {
-        self.tasks
-            .update_edge(predecessor_index.into(), successor_index.into(), kind)
-            .context("Tried to add a relationship between non existing nodes")?;
-        anyhow::Ok(())
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// assert!(project.is_empty());
+    /// project.add_task(Task::new("Become world leader"));
+    /// assert!(!project.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.task_count() == 0
     }
 
-    /// Removes a relationship betwen tasks, where one is the predecessor and the other one a successor.
+    /// Checks whether the project has a task with the given index.
     ///
-    /// # Errors
-    /// TODO!
+    /// # Arguments
+    ///
+    /// * `index` - The index of the task.
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    /// use planter_core::{project::Project, task::Task};
     ///
     /// let mut project = Project::new("World domination");
-    /// project.add_task(Task::new("Get rich"));
     /// project.add_task(Task::new("Become world leader"));
-    /// project.add_time_relationship(0, 1, TimeRelationship::default());
-    /// project.remove_time_relationship(0, 1);
-    ///
-    /// assert_eq!(project.successors(0).count(), 0);
+    /// assert!(project.contains_task(0));
+    /// assert!(!project.contains_task(1));
     /// ```
-    pub fn remove_time_relationship(
-        &mut self,
-        predecessor_index: usize,
-        successor_index: usize,
-    ) -> anyhow::Result<()> {
-        let edge_index = self
-            .tasks
-            .find_edge(predecessor_index.into(), successor_index.into())
-            .context(
-                "Tried to remove a relationship that doesn't exist or between non existing nodes",
-            )?;
+    pub fn contains_task(&self, index: usize) -> bool {
+        index < self.task_count()
+    }
 
-        self.tasks.remove_edge(edge_index);
-        anyhow::Ok(())
+    /// Returns the indices of the tasks whose custom field `key` is set to `value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// let mut task = Task::new("Become world leader");
+    /// task.set_custom_field("Jira", "WD-42");
+    /// project.add_task(Task::new("Get rich"));
+    /// project.add_task(task);
+    ///
+    /// assert_eq!(project.tasks_where_field("Jira", "WD-42"), vec![1]);
+    /// ```
+    pub fn tasks_where_field(&self, key: &str, value: &str) -> Vec<usize> {
+        self.find_tasks_matching(|task| task.custom_field(key) == Some(value))
     }
 
-    /// Gets the list of successors for a given node.
+    /// Returns the index of the first task with the given name. The match is exact and
+    /// case sensitive.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the task.
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    /// use planter_core::{project::Project, task::Task};
     ///
     /// let mut project = Project::new("World domination");
     /// project.add_task(Task::new("Get rich"));
     /// project.add_task(Task::new("Become world leader"));
-    /// project.add_time_relationship(0, 1, TimeRelationship::default());
     ///
-    /// assert_eq!(project.successors(0).next().unwrap().name(), "Become world leader")
+    /// assert_eq!(project.find_task_by_name("Become world leader"), Some(1));
+    /// assert_eq!(project.find_task_by_name("become world leader"), None);
     /// ```
-    pub fn successors(&self, node_index: usize) -> impl Iterator<Item = &Task> {
-        self.tasks
-            .neighbors_directed(node_index.into(), Direction::Outgoing)
-            .map(|index| &self.tasks[index])
+    pub fn find_task_by_name(&self, name: &str) -> Option<usize> {
+        self.tasks().position(|task| task.name() == name)
     }
 
-    /// Gets the indices of all successors for a given node.
+    /// Returns the indices of all the tasks with the given name. The match is exact and
+    /// case sensitive.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the tasks.
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    /// use planter_core::{project::Project, task::Task};
     ///
     /// let mut project = Project::new("World domination");
     /// project.add_task(Task::new("Get rich"));
     /// project.add_task(Task::new("Become world leader"));
-    /// project.add_time_relationship(0, 1, TimeRelationship::default());
+    /// project.add_task(Task::new("Get rich"));
     ///
-    /// assert_eq!(project.successors_indices(0).next().unwrap(), 1)
+    /// assert_eq!(project.find_tasks_by_name("Get rich"), vec![0, 2]);
     /// ```
-    pub fn successors_indices(&self, node_index: usize) -> impl Iterator<Item = usize> {
-        self.tasks
-            .neighbors_directed(node_index.into(), Direction::Outgoing)
-            .map(|index| index.index())
+    pub fn find_tasks_by_name(&self, name: &str) -> Vec<usize> {
+        self.find_tasks_matching(|task| task.name() == name)
     }
 
-    /// Gets the list of predecessors for a given node.
+    /// Returns the indices of all the tasks for which `predicate` is true.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - The condition the tasks have to satisfy.
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    /// use planter_core::{project::Project, task::Task};
     ///
     /// let mut project = Project::new("World domination");
     /// project.add_task(Task::new("Get rich"));
     /// project.add_task(Task::new("Become world leader"));
-    /// project.add_time_relationship(1, 0, TimeRelationship::default());
     ///
-    /// assert_eq!(project.predecessors(0).next().unwrap().name(), "Become world leader")
+    /// let found = project.find_tasks_matching(|task| task.name().to_lowercase().contains("rich"));
+    /// assert_eq!(found, vec![0]);
     /// ```
-    pub fn predecessors(&self, node_index: usize) -> impl Iterator<Item = &Task> {
-        self.tasks
-            .neighbors_directed(node_index.into(), Direction::Incoming)
-            .map(|index| &self.tasks[index])
+    pub fn find_tasks_matching(&self, predicate: impl Fn(&Task) -> bool) -> Vec<usize> {
+        self.tasks()
+            .enumerate()
+            .filter(|(_, task)| predicate(task))
+            .map(|(i, _)| i)
+            .collect()
     }
 
-    /// Gets the indices of all predecessors for a given node.
+    /// Returns the milestones of the project, with their indices.
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    /// use planter_core::{project::Project, task::Task};
     ///
     /// let mut project = Project::new("World domination");
+    /// let mut milestone = Task::new("World domination achieved");
+    /// milestone.set_milestone(true);
     /// project.add_task(Task::new("Get rich"));
-    /// project.add_task(Task::new("Become world leader"));
-    /// project.add_time_relationship(1, 0, TimeRelationship::default());
+    /// project.add_task(milestone);
     ///
-    /// assert_eq!(project.predecessors_indices(0).next().unwrap(), 1)
+    /// let milestones: Vec<_> = project.milestones().map(|(i, _)| i).collect();
+    /// assert_eq!(milestones, vec![1]);
     /// ```
-    pub fn predecessors_indices(&self, node_index: usize) -> impl Iterator<Item = usize> {
-        self.tasks
-            .neighbors_directed(node_index.into(), Direction::Incoming)
-            .map(|index| index.index())
+    pub fn milestones(&self) -> impl Iterator<Item = (usize, &Task)> {
+        self.tasks()
+            .enumerate()
+            .filter(|(_, task)| task.is_milestone())
     }
 
-    /// Updates the project by making sure the predecessors for the task with
-    /// index `node_index` are exactly the ones listed in `predecessors_indices`
+    /// Returns the task with the given identifier, see [`Task::id`].
     ///
     /// # Arguments
     ///
-    /// * `task_index` - The index whose predecessors need to be updated.
-    /// * `predecessors_indices` - The indices of the predecessors.
+    /// * `id` - The identifier of the task.
     ///
-    /// # Errors
-    /// TODO!
+    /// # Returns
+    ///
+    /// * `Some((usize, &Task))` - The current index of the task, and the task itself.
+    /// * `None` - If no task has that identifier.
     ///
     /// # Example
     ///
     /// ```
     /// use planter_core::{project::Project, task::Task};
     ///
-    /// let tasks = vec![
-    ///      Task::new("Become world leader"),
-    ///      Task::new("Get rich"),
-    ///      Task::new("Be evil")
-    /// ];
-    /// let mut project = Project::builder().name("World domination").tasks(tasks).build();
+    /// let mut project = Project::new("World domination");
+    /// let profit = Task::new("Profit");
+    /// let id = profit.id();
+    /// project.add_task(Task::new("Find a crowbar"));
+    /// project.add_task(profit);
+    /// project.rm_task(0).unwrap();
+    ///
+    /// let (index, task) = project.task_by_id(id).unwrap();
+    /// assert_eq!((index, task.name()), (0, "Profit"));
+    /// ```
+    pub fn task_by_id(&self, id: u64) -> Option<(usize, &Task)> {
+        self.tasks().enumerate().find(|(_, task)| task.id() == id)
+    }
+
+    /// Returns the tasks of the project with the given tag, with their indices.
     ///
-    /// project.update_predecessors(2, &[0, 1]);
-    /// assert_eq!(project.predecessors(2).count(), 2);
-    pub fn update_predecessors(
-        &mut self,
-        task_index: usize,
-        predecessors_indices: &[usize],
-    ) -> anyhow::Result<()> {
-        self.validate_indices(task_index, predecessors_indices)?;
+    /// # Arguments
+    ///
+    /// * `tag` - The tag to look for.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// let mut task = Task::new("Build the doomsday device");
+    /// task.add_tag("risk");
+    /// project.add_task(Task::new("Get rich"));
+    /// project.add_task(task);
+    ///
+    /// let risky: Vec<_> = project.tasks_with_tag("risk").map(|(i, _)| i).collect();
+    /// assert_eq!(risky, vec![1]);
+    /// ```
+    pub fn tasks_with_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = (usize, &'a Task)> {
+        self.tasks()
+            .enumerate()
+            .filter(move |(_, task)| task.has_tag(tag))
+    }
 
-        // Update predecessors in a cloned data structure for tasks.
-        // If this gives an error, the actual data structure won't be polluted.
-        // TODO: benchmark and see if there is a better way to do this without cloning.
-        let mut tasks_clone = self.tasks.clone();
-        for &i in predecessors_indices {
-            tasks_clone
-                .add_edge(i.into(), task_index.into(), TimeRelationship::FinishToStart)
-                .context(format!(
-                    "A cycle was detected between tasks {i} and {task_index}"
-                ))?;
-        }
-
-        // Remove all predecessors.
-        for i in self
-            .predecessors_indices(task_index)
-            .collect::<Vec<usize>>()
-        {
-            self.remove_time_relationship(i, task_index)
-                .context("It should have been possible to remove a predecessor. This is a bug.")?;
-        }
-        // Update predecessors.
-        for &i in predecessors_indices {
-            self.tasks
-                .add_edge(i.into(), task_index.into(), TimeRelationship::FinishToStart)
-                .context("This shouldn't have happened because the data structure was just checked for cycles.")?;
-        }
-        Ok(())
-    }
-
-    /// Checks that all the tasks with indices passed as parameters actually exist in the project.
-    fn validate_indices(&self, task_index: usize, related_indices: &[usize]) -> anyhow::Result<()> {
-        let graph_edges: HashSet<usize> =
-            self.tasks.node_identifiers().map(|i| i.index()).collect();
-        // Make sure all the listed predecessors exist within the graph.
-        if !related_indices.iter().all(|i| graph_edges.contains(i)) {
-            bail!("Some index in the predecessors list doesn't exist in the graph");
-        }
-
-        // Make sure the task index exists withing the graph.
-        if !graph_edges.contains(&task_index) {
-            bail!(format!(
-                "Task index {task_index} doesn't exist in the graph"
-            ));
-        }
-
-        Ok(())
-    }
-
-    /// Updates the project by making sure the successors for the task with
-    /// index `node_index` are exactly the ones listed in `successors_indices`
+    /// Returns the tasks of the project that belong to the given phase.
     ///
     /// # Arguments
     ///
-    /// * `task_index` - The index whose successors need to be updated.
-    /// * `successors_indices` - The indices of the successors.
-    ///
-    /// # Errors
-    /// TODO!
+    /// * `phase` - The phase to look for.
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{project::Project, task::Task};
-    ///
-    /// let tasks = vec![
-    ///      Task::new("Become world leader"),
-    ///      Task::new("Get rich"),
-    ///      Task::new("Be evil")
-    /// ];
-    /// let mut project = Project::builder().name("World domination").tasks(tasks).build();
-    ///
-    /// project.update_successors(0, &[1, 2]);
-    /// assert_eq!(project.successors(0).count(), 2);
-    pub fn update_successors(
-        &mut self,
-        task_index: usize,
-        successors_indices: &[usize],
-    ) -> anyhow::Result<()> {
-        self.validate_indices(task_index, successors_indices)?;
-
-        // Update successors in a cloned data structure for tasks.
-        // If this gives an error, the actual data structure won't be polluted.
-        // TODO: benchmark and see if there is a better way to do this without cloning.
-        let mut tasks_clone = self.tasks.clone();
-        for &i in successors_indices {
-            tasks_clone
-                .add_edge(task_index.into(), i.into(), TimeRelationship::FinishToStart)
-                .context(format!(
-                    "A cycle was detected between tasks {i} and {task_index}"
-                ))?;
-        }
-
-        // Remove all successors.
-        for i in self.successors_indices(task_index).collect::<Vec<usize>>() {
-            self.remove_time_relationship(task_index, i)
-                .context("It should have been possible to remove a predecessor. This is a bug.")?;
-        }
-        // Update successors.
-        for &i in successors_indices {
-            self.tasks
-                .add_edge( task_index.into(), i.into(), TimeRelationship::FinishToStart)
-                .context("This shouldn't have happened because the data structure was just checked for cycles.")?;
-        }
-        Ok(())
-    }
-
-    /// Adds a subtask to a given task. This relationship means that the parent
-    /// task is completed when all the children are completed. Also, the parent's cost
-    /// and duration is the cumulative cost and duration of the children.
-    ///
-    /// Example
-    ///
-    /// ```
-    /// use planter_core::{project::Project, task::Task};
+    /// use planter_core::{project::{Phase, Project}, task::Task};
     ///
-    /// let tasks = vec![
-    ///      Task::new("Become world leader"),
-    ///      Task::new("Get rich"),
-    ///      Task::new("Be evil")
-    /// ];
-    /// let mut project = Project::builder().name("World domination").tasks(tasks).build();
+    /// let mut project = Project::new("World domination");
+    /// let mut task = Task::new("Draw the master plan");
+    /// task.set_phase(Phase::Design);
+    /// project.add_task(task);
+    /// project.add_task(Task::new("Get rich"));
     ///
-    /// project.add_subtask(0, 1);
-    /// project.add_subtask(0, 2);
-    /// assert_eq!(project.subtasks(0).len(), 2);
+    /// let names: Vec<_> = project.tasks_in_phase(Phase::Design).map(|t| t.name()).collect();
+    /// assert_eq!(names, vec!["Draw the master plan"]);
+    /// assert_eq!(project.tasks_in_phase(Phase::Build).count(), 0);
     /// ```
-    pub fn add_subtask(&mut self, parent_index: usize, child_index: usize) {
-        self.subtask_relationships.push(SubtaskRelationship {
-            task: parent_index,
-            subtask: child_index,
-        });
+    pub fn tasks_in_phase(&self, phase: Phase) -> impl Iterator<Item = &Task> {
+        self.tasks().filter(move |task| task.phase() == Some(phase))
     }
 
-    /// Gets a list of all the subtasks of the given task.
+    /// Counts the tasks of the project in each status. Every status is present in the result,
+    /// even when no task has it.
     ///
-    /// Example
+    /// # Example
     ///
     /// ```
-    /// use planter_core::{project::Project, task::Task};
+    /// use planter_core::{project::Project, task::{Task, TaskStatus}};
     ///
     /// let mut project = Project::new("World domination");
+    /// let mut task = Task::new("Get rich");
+    /// task.set_status(TaskStatus::InProgress);
+    /// project.add_task(task);
     /// project.add_task(Task::new("Become world leader"));
-    /// project.add_task(Task::new("Get rich"));
-    /// assert_eq!(project.subtasks(0).len(), 0);
     ///
-    /// project.add_subtask(0, 1);
-    /// assert_eq!(project.subtasks(0).len(), 1);
+    /// let counts = project.status_counts();
+    /// assert_eq!(counts[&TaskStatus::NotStarted], 1);
+    /// assert_eq!(counts[&TaskStatus::InProgress], 1);
+    /// assert_eq!(counts[&TaskStatus::Completed], 0);
     /// ```
-    pub fn subtasks(&self, task_index: usize) -> Vec<usize> {
-        self.subtask_relationships
-            .iter()
-            .filter(|r| r.task == task_index)
-            .map(|r| r.subtask)
-            .collect()
+    pub fn status_counts(&self) -> HashMap<TaskStatus, usize> {
+        let mut counts: HashMap<TaskStatus, usize> =
+            TaskStatus::ALL.iter().map(|&status| (status, 0)).collect();
+        for task in self.tasks() {
+            *counts.entry(task.status()).or_default() += 1;
+        }
+        counts
     }
 
-    /// Returns the start date of the project.
+    /// Adds a relationship betwen tasks, where one is the predecessor and the other one a successor.
+    ///
+    /// # Errors
+    /// TODO!
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{project::Project};
-    /// use chrono::Utc;
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
     ///
-    /// let start_date = Utc::now();
-    /// let mut project = Project::builder().name("World domination").start_date(start_date).build();
-    /// assert_eq!(project.start_date(), Some(start_date));
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Get rich"));
+    /// project.add_task(Task::new("Become world leader"));
+    /// project.add_time_relationship(0, 1, TimeRelationship::default());
+    ///
+    /// assert_eq!(project.successors(0).next().unwrap().name(), "Become world leader")
     /// ```
-    pub fn start_date(&self) -> Option<DateTime<Utc>> {
-        self.start_date
+    pub fn add_time_relationship(
+        &mut self,
+        predecessor_index: usize,
+        successor_index: usize,
+        kind: TimeRelationship,
+    ) -> anyhow::Result<()> {
+        self.add_time_relationship_with_lag(
+            predecessor_index,
+            successor_index,
+            kind,
+            Duration::zero(),
+        )
     }
 
-    /// Adds a resource to the project.
+    /// Adds a relationship between tasks, where the successor has to wait `lag` more than the
+    /// relationship alone would require. For example, a finish to start relationship with a
+    /// lag of 2 days means that the successor starts 2 days after the predecessor finishes.
+    /// If the tasks are already related, the relationship is replaced.
     ///
     /// # Arguments
     ///
-    /// * `resource` - The resource to add to the project.
+    /// * `predecessor_index` - The index of the predecessor.
+    /// * `successor_index` - The index of the successor.
+    /// * `kind` - The kind of relationship between the tasks.
+    /// * `lag` - The delay between the tasks. A negative lag is a lead: with a finish to start
+    ///   relationship, the successor can start before the predecessor finishes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either task doesn't exist, if the relationship would create a cycle,
+    /// or if the lag is longer than [`MAX_DURATION`] milliseconds, either way.
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{resources::Resource, project::Project, person::Person};
+    /// use chrono::Duration;
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
     ///
     /// let mut project = Project::new("World domination");
-    /// project.add_resource(Resource::Personnel {
-    ///     person: Person::new("Sebastiano", "Giordano").unwrap(),
-    ///     hourly_rate: None,
-    /// });
-    /// assert_eq!(project.resources().len(), 1);
+    /// project.add_task(Task::new("Pour the concrete"));
+    /// project.add_task(Task::new("Build the walls"));
+    /// project
+    ///     .add_time_relationship_with_lag(
+    ///         0,
+    ///         1,
+    ///         TimeRelationship::FinishToStart,
+    ///         Duration::days(-2),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(project.predecessors_indices(1).collect::<Vec<_>>(), vec![0]);
     /// ```
-    pub fn add_resource(&mut self, resource: Resource) {
-        self.resources.push(resource);
+    pub fn add_time_relationship_with_lag(
+        &mut self,
+        predecessor_index: usize,
+        successor_index: usize,
+        kind: TimeRelationship,
+        lag: Duration,
+    ) -> anyhow::Result<()> {
+        self.validate_indices(successor_index, &[predecessor_index])?;
+        if lag.abs() > Duration::milliseconds(MAX_DURATION) {
+            bail!("The lag {lag} exceeds the maximum duration");
+        }
+        if self
+            .tasks
+            .update_edge(
+                predecessor_index.into(),
+                successor_index.into(),
+                Dependency { kind, lag },
+            )
+            .is_err()
+        {
+            bail!(cycle_error(&self.tasks, predecessor_index, successor_index));
+        }
+        self.record_relationship_added(
+            predecessor_index,
+            successor_index,
+            Dependency { kind, lag },
+        );
+        anyhow::Ok(())
     }
 
-    /// Get a reference to a resource used in the project.
+    /// Removes a relationship betwen tasks, where one is the predecessor and the other one a successor.
+    ///
+    /// # Errors
+    /// TODO!
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{resources::Resource, project::Project, person::Person};
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
     ///
     /// let mut project = Project::new("World domination");
-    /// project.add_resource(Resource::Personnel {
-    ///     person: Person::new("Sebastiano", "Giordano").unwrap(),
-    ///     hourly_rate: None,
-    /// });
+    /// project.add_task(Task::new("Get rich"));
+    /// project.add_task(Task::new("Become world leader"));
+    /// project.add_time_relationship(0, 1, TimeRelationship::default());
+    /// project.remove_time_relationship(0, 1);
     ///
-    /// assert!(project.resource(0).is_some());
+    /// assert_eq!(project.successors(0).count(), 0);
     /// ```
-    pub fn resource(&self, index: usize) -> Option<&Resource> {
-        self.resources.get(index)
+    pub fn remove_time_relationship(
+        &mut self,
+        predecessor_index: usize,
+        successor_index: usize,
+    ) -> anyhow::Result<()> {
+        let edge_index = self
+            .tasks
+            .find_edge(predecessor_index.into(), successor_index.into())
+            .context(
+                "Tried to remove a relationship that doesn't exist or between non existing nodes",
+            )?;
+
+        let dependency = self
+            .tasks
+            .remove_edge(edge_index)
+            .context("Tried to remove a relationship that doesn't exist")?;
+        self.record(Change::RelationshipRemoved {
+            predecessor: predecessor_index,
+            successor: successor_index,
+            kind: dependency.kind,
+            lag: dependency.lag,
+        });
+        anyhow::Ok(())
     }
 
-    /// Remove a resource from the project.
+    /// Inserts a task between two related tasks: the predecessor becomes related to the new
+    /// task, and the new task to the successor, both with the kind of the original
+    /// relationship, which is removed. The lag stays between the predecessor and the new task.
     ///
-    /// # Panics
+    /// # Arguments
+    ///
+    /// * `predecessor_index` - The index of the predecessor.
+    /// * `successor_index` - The index of the successor.
+    /// * `task` - The task to insert.
+    ///
+    /// # Returns
     ///
-    /// Panics if the resource index is out of bounds.
+    /// The index of the new task.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tasks aren't related. The project is left untouched in that case.
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{resources::Resource, project::Project, person::Person};
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
     ///
     /// let mut project = Project::new("World domination");
-    /// project.add_resource(Resource::Personnel {
-    ///     person: Person::new("Sebastiano", "Giordano").unwrap(),
-    ///     hourly_rate: None,
-    /// });
-    ///
-    /// assert!(project.resource(0).is_some());
-    /// project.rm_resource(0);
-    /// assert!(project.resource(0).is_none());
+    /// project.add_task(Task::new("Get rich"));
+    /// project.add_task(Task::new("Become world leader"));
+    /// project.add_time_relationship(0, 1, TimeRelationship::StartToStart).unwrap();
     ///
-    /// let result = std::panic::catch_unwind(move || {
-    ///     project.rm_resource(0);
-    /// });
-    /// assert!(result.is_err());
+    /// let army = project.insert_between(0, 1, Task::new("Buy an army")).unwrap();
+    /// assert_eq!(project.time_relationship(0, army), Some(TimeRelationship::StartToStart));
+    /// assert_eq!(project.time_relationship(army, 1), Some(TimeRelationship::StartToStart));
+    /// assert_eq!(project.time_relationship(0, 1), None);
     /// ```
-    pub fn rm_resource(&mut self, index: usize) -> Resource {
-        self.resources.remove(index)
+    pub fn insert_between(
+        &mut self,
+        predecessor_index: usize,
+        successor_index: usize,
+        task: Task,
+    ) -> anyhow::Result<usize> {
+        let dependency = self
+            .dependency(predecessor_index, successor_index)
+            .with_context(|| {
+                format!("Task {predecessor_index} isn't a predecessor of task {successor_index}")
+            })?;
+        self.remove_time_relationship(predecessor_index, successor_index)?;
+
+        let index = self.add_task(task);
+        // The new task has no other relationships, so these can't form a cycle.
+        self.tasks
+            .add_edge(predecessor_index.into(), index.into(), dependency)
+            .context("Couldn't relate the predecessor to the new task. This is a bug.")?;
+        self.record_relationship_added(predecessor_index, index, dependency);
+        let dependency = Dependency {
+            lag: Duration::zero(),
+            ..dependency
+        };
+        self.tasks
+            .add_edge(index.into(), successor_index.into(), dependency)
+            .context("Couldn't relate the new task to the successor. This is a bug.")?;
+        self.record_relationship_added(index, successor_index, dependency);
+        Ok(index)
     }
 
-    /// Get a mutable reference to a resource used in the project.
+    /// Returns the kind of the relationship between two tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `predecessor_index` - The index of the predecessor.
+    /// * `successor_index` - The index of the successor.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(TimeRelationship)` - The kind of the relationship.
+    /// * `None` - If the tasks aren't related, or if either doesn't exist.
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{resources::Resource, project::Project, person::Person};
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
     ///
     /// let mut project = Project::new("World domination");
-    /// project.add_resource(Resource::Personnel {
-    ///     person: Person::new("Sebastiano", "Giordano").unwrap(),
-    ///     hourly_rate: None,
-    /// });
+    /// project.add_task(Task::new("Get rich"));
+    /// project.add_task(Task::new("Become world leader"));
+    /// project.add_time_relationship(0, 1, TimeRelationship::StartToStart).unwrap();
     ///
-    /// let resource = project.resource_mut(0).unwrap();
-    /// match resource {
-    ///   Resource::Material(_) => panic!(),
-    ///   Resource::Personnel {
-    ///     person,
-    ///     ..
-    ///   } => {
-    ///     person.update_first_name("Alessandro");
-    ///     assert_eq!(person.first_name(), "Alessandro");
-    ///   }
-    /// }
+    /// assert_eq!(project.time_relationship(0, 1), Some(TimeRelationship::StartToStart));
+    /// assert_eq!(project.time_relationship(1, 0), None);
     /// ```
-    pub fn resource_mut(&mut self, index: usize) -> Option<&mut Resource> {
-        self.resources.get_mut(index)
+    pub fn time_relationship(
+        &self,
+        predecessor_index: usize,
+        successor_index: usize,
+    ) -> Option<TimeRelationship> {
+        self.dependency(predecessor_index, successor_index)
+            .map(|dependency| dependency.kind)
     }
 
-    /// Returns a reference to the list of resources associated with the project.
+    /// Returns every time relationship of the project, as the index of the predecessor, the
+    /// index of the successor and the kind of the relationship, in no particular order.
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{resources::{Resource, Material, NonConsumable}, project::Project};
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
     ///
     /// let mut project = Project::new("World domination");
-    /// project.add_resource(Resource::Material(Material::NonConsumable(
-    ///    NonConsumable::new("Crowbar"),
-    /// )));
-    /// assert_eq!(project.resources().len(), 1);
+    /// for name in ["Get rich", "Buy an army", "Become world leader"] {
+    ///     project.add_task(Task::new(name));
+    /// }
+    /// project.add_time_relationship(0, 1, TimeRelationship::StartToStart).unwrap();
+    /// project.add_time_relationship(1, 2, TimeRelationship::FinishToStart).unwrap();
+    ///
+    /// let mut relationships: Vec<_> = project.relationships().collect();
+    /// relationships.sort_unstable_by_key(|&(predecessor, successor, _)| (predecessor, successor));
+    /// assert_eq!(
+    ///     relationships,
+    ///     vec![
+    ///         (0, 1, TimeRelationship::StartToStart),
+    ///         (1, 2, TimeRelationship::FinishToStart)
+    ///     ]
+    /// );
     /// ```
-    pub fn resources(&self) -> &[Resource] {
-        &self.resources
+    pub fn relationships(&self) -> impl Iterator<Item = (usize, usize, TimeRelationship)> {
+        self.tasks.graph().edge_references().map(|edge| {
+            (
+                edge.source().index(),
+                edge.target().index(),
+                edge.weight().kind,
+            )
+        })
     }
 
-    /// Converts a resource into a `Consumable`, if that's possible.
+    /// Finds the time relationships that the dates of the tasks break, lag included, like a
+    /// successor that starts before its finish to start predecessor finishes. It's the read-only
+    /// counterpart of [`Project::schedule`], to spot dates entered by hand that don't add up.
+    /// Relationships are only checked when both the dates they relate are set. With a calendar,
+    /// the lag counts working time only.
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * resource_index - The index of a `Material`, that's not a `Consumable`
+    /// The index of the predecessor, the index of the successor and the kind of each broken
+    /// relationship, sorted by predecessor and then by successor.
     ///
-    /// # Errors
+    /// # Example
     ///
-    /// * `ResourceConversionError::ResourceNotFound` - If a resource with the specified index does not exist.
-    /// * `ResourceConversionError::ConversionNotPossible` - When trying to convert a resource that's not a `Material`.
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// let start = Utc::now();
+    /// let mut crowbar = Task::new("Find a crowbar");
+    /// crowbar.edit_start(start).unwrap();
+    /// crowbar.edit_finish(start + Duration::hours(2)).unwrap();
+    /// let mut profit = Task::new("Profit");
+    /// profit.edit_start(start + Duration::hours(1)).unwrap();
+    /// project.add_task(crowbar);
+    /// project.add_task(profit);
+    /// project.add_time_relationship(0, 1, TimeRelationship::FinishToStart).unwrap();
+    ///
+    /// assert_eq!(project.date_conflicts(), vec![(0, 1, TimeRelationship::FinishToStart)]);
+    ///
+    /// project.task_mut(1).unwrap().edit_start(start + Duration::hours(2)).unwrap();
+    /// assert!(project.date_conflicts().is_empty());
+    /// ```
+    pub fn date_conflicts(&self) -> Vec<(usize, usize, TimeRelationship)> {
+        let calendar = self.calendar.as_ref();
+        let mut conflicts: Vec<_> = self
+            .tasks
+            .graph()
+            .edge_references()
+            .filter(|edge| {
+                let (Some(predecessor), Some(successor)) = (
+                    self.task(edge.source().index()),
+                    self.task(edge.target().index()),
+                ) else {
+                    return false;
+                };
+                let dates = match edge.weight().kind {
+                    TimeRelationship::FinishToStart => (predecessor.finish(), successor.start()),
+                    TimeRelationship::StartToStart => (predecessor.start(), successor.start()),
+                    TimeRelationship::FinishToFinish => (predecessor.finish(), successor.finish()),
+                    TimeRelationship::StartToFinish => (predecessor.start(), successor.finish()),
+                };
+                match dates {
+                    (Some(required), Some(actual)) => {
+                        actual
+                            < (WorkingDate {
+                                date: required,
+                                calendar,
+                            } + edge.weight().lag)
+                                .date
+                    }
+                    _ => false,
+                }
+            })
+            .map(|edge| {
+                (
+                    edge.source().index(),
+                    edge.target().index(),
+                    edge.weight().kind,
+                )
+            })
+            .collect();
+        conflicts.sort_unstable_by_key(|&(predecessor, successor, _)| (predecessor, successor));
+        conflicts
+    }
+
+    /// Returns the lag of the time relationship between two tasks, if they are related.
+    pub(crate) fn lag(&self, predecessor_index: usize, successor_index: usize) -> Option<Duration> {
+        self.dependency(predecessor_index, successor_index)
+            .map(|dependency| dependency.lag)
+    }
+
+    /// Returns the relationship between two tasks, if they are related.
+    fn dependency(&self, predecessor_index: usize, successor_index: usize) -> Option<Dependency> {
+        let edge_index = self
+            .tasks
+            .find_edge(predecessor_index.into(), successor_index.into())?;
+        self.tasks.edge_weight(edge_index).copied()
+    }
+
+    /// Gets the list of successors for a given node.
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{resources::{Resource, Material, NonConsumable}, project::Project};
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
     ///
     /// let mut project = Project::new("World domination");
-    /// project.add_resource(Resource::Material(Material::NonConsumable(
-    ///    NonConsumable::new("Crowbar"),
-    /// )));
-    /// assert!(project.res_into_consumable(0).is_ok());
-    /// ```
+    /// project.add_task(Task::new("Get rich"));
+    /// project.add_task(Task::new("Become world leader"));
+    /// project.add_time_relationship(0, 1, TimeRelationship::default());
     ///
-    pub fn res_into_consumable(
-        &mut self,
-        resource_index: usize,
-    ) -> Result<(), ResourceConversionError> {
-        let res = self
-            .resources
-            .get_mut(resource_index)
-            .ok_or(ResourceConversionError::ResourceNotFound)?;
-        match res {
-            Resource::Material(Material::NonConsumable(non_consumable)) => {
-                *res = Resource::Material(Material::Consumable(non_consumable.clone().into()))
-            }
-            Resource::Material(Material::Consumable(_)) => {}
-            _ => return Err(ResourceConversionError::ConversionNotPossible),
-        }
-        Ok(())
+    /// assert_eq!(project.successors(0).next().unwrap().name(), "Become world leader")
+    /// ```
+    pub fn successors(&self, node_index: usize) -> impl Iterator<Item = &Task> {
+        self.tasks
+            .neighbors_directed(node_index.into(), Direction::Outgoing)
+            .map(|index| &self.tasks[index])
     }
 
-    /// Converts a resource into a `NonConsumable`, if that's possible.
+    /// Gets the indices of all successors for a given node.
     ///
-    /// # Arguments
+    /// # Example
     ///
-    /// * resource_index - The index of a `Material`, that's not a `NonConsumable`
+    /// ```
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
     ///
-    /// # Errors
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Get rich"));
+    /// project.add_task(Task::new("Become world leader"));
+    /// project.add_time_relationship(0, 1, TimeRelationship::default());
     ///
-    /// * `ResourceConversionError::ResourceNotFound` - If a resource with the specified index does not exist.
-    /// * `ResourceConversionError::ConversionNotPossible` - When trying to convert a resource that's not a `Material`.
+    /// assert_eq!(project.successors_indices(0).next().unwrap(), 1)
+    /// ```
+    pub fn successors_indices(&self, node_index: usize) -> impl Iterator<Item = usize> {
+        self.tasks
+            .neighbors_directed(node_index.into(), Direction::Outgoing)
+            .map(|index| index.index())
+    }
+
+    /// Gets the list of predecessors for a given node.
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{resources::{Resource, Material, Consumable}, project::Project};
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
     ///
     /// let mut project = Project::new("World domination");
-    /// project.add_resource(Resource::Material(Material::Consumable(
-    ///    Consumable::new("Stimpack"),
-    /// )));
-    /// assert!(project.res_into_nonconsumable(0).is_ok());
+    /// project.add_task(Task::new("Get rich"));
+    /// project.add_task(Task::new("Become world leader"));
+    /// project.add_time_relationship(1, 0, TimeRelationship::default());
+    ///
+    /// assert_eq!(project.predecessors(0).next().unwrap().name(), "Become world leader")
     /// ```
-    pub fn res_into_nonconsumable(
-        &mut self,
-        resource_index: usize,
-    ) -> Result<(), ResourceConversionError> {
-        let res = self
-            .resources
-            .get_mut(resource_index)
-            .ok_or(ResourceConversionError::ResourceNotFound)?;
+    pub fn predecessors(&self, node_index: usize) -> impl Iterator<Item = &Task> {
+        self.tasks
+            .neighbors_directed(node_index.into(), Direction::Incoming)
+            .map(|index| &self.tasks[index])
+    }
 
-        match res {
-            Resource::Material(Material::Consumable(consumable)) => {
-                *res = Resource::Material(Material::NonConsumable(consumable.clone().into()));
-            }
-            Resource::Material(Material::NonConsumable(_)) => {}
-            _ => return Err(ResourceConversionError::ConversionNotPossible),
-        }
-        Ok(())
+    /// Gets the indices of all predecessors for a given node.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Get rich"));
+    /// project.add_task(Task::new("Become world leader"));
+    /// project.add_time_relationship(1, 0, TimeRelationship::default());
+    ///
+    /// assert_eq!(project.predecessors_indices(0).next().unwrap(), 1)
+    /// ```
+    pub fn predecessors_indices(&self, node_index: usize) -> impl Iterator<Item = usize> {
+        self.tasks
+            .neighbors_directed(node_index.into(), Direction::Incoming)
+            .map(|index| index.index())
     }
 
-    /// Adds a stakeholder to the project.
+    /// Gets the indices of all the tasks that come after a given task, following its
+    /// successors, their successors and so on. These are the tasks affected by delaying it.
     ///
     /// # Arguments
     ///
-    /// * `stakeholder` - The stakeholder to add to the project.
+    /// * `index` - The index of the task.
+    ///
+    /// # Returns
+    ///
+    /// The sorted indices of the descendants, without the task itself. Empty if the task
+    /// doesn't exist.
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{stakeholders::Stakeholder, project::Project, person::Person};
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
     ///
     /// let mut project = Project::new("World domination");
-    /// let person = Person::new("Margherita", "Hack").unwrap();
-    /// project.add_stakeholder(Stakeholder::Individual {
-    ///   person,
-    ///   description: None,
-    /// });
-    /// assert_eq!(project.stakeholders().len(), 1);
+    /// for name in ["Get rich", "Buy an army", "Hire a general", "Become world leader"] {
+    ///     project.add_task(Task::new(name));
+    /// }
+    /// for (predecessor, successor) in [(0, 1), (0, 2), (1, 3), (2, 3)] {
+    ///     project
+    ///         .add_time_relationship(predecessor, successor, TimeRelationship::default())
+    ///         .unwrap();
+    /// }
+    ///
+    /// assert_eq!(project.descendants(0), vec![1, 2, 3]);
+    /// assert!(project.descendants(3).is_empty());
     /// ```
-    pub fn add_stakeholder(&mut self, stakeholder: Stakeholder) {
-        self.stakeholders.push(stakeholder);
+    pub fn descendants(&self, index: usize) -> Vec<usize> {
+        let mut descendants: Vec<usize> = self
+            .reachable(index, Direction::Outgoing)
+            .into_iter()
+            .collect();
+        descendants.sort_unstable();
+        descendants
     }
 
-    /// Returns a reference to the list of stakeholders associated with the project.
+    /// Gets the indices of all the tasks that come before a given task, following its
+    /// predecessors, their predecessors and so on.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the task.
+    ///
+    /// # Returns
+    ///
+    /// The sorted indices of the ancestors, without the task itself. Empty if the task
+    /// doesn't exist.
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{stakeholders::Stakeholder, project::Project, person::Person};
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
     ///
     /// let mut project = Project::new("World domination");
-    /// let person = Person::new("Margherita", "Hack").unwrap();
-    /// project.add_stakeholder(Stakeholder::Individual {
-    ///   person,
-    ///   description: None,
-    /// });
-    /// assert_eq!(project.stakeholders().len(), 1);
+    /// for name in ["Get rich", "Buy an army", "Hire a general", "Become world leader"] {
+    ///     project.add_task(Task::new(name));
+    /// }
+    /// for (predecessor, successor) in [(0, 1), (0, 2), (1, 3), (2, 3)] {
+    ///     project
+    ///         .add_time_relationship(predecessor, successor, TimeRelationship::default())
+    ///         .unwrap();
+    /// }
+    ///
+    /// assert_eq!(project.ancestors(3), vec![0, 1, 2]);
+    /// assert_eq!(project.ancestors(1), vec![0]);
     /// ```
-    pub fn stakeholders(&self) -> &[Stakeholder] {
-        &self.stakeholders
+    pub fn ancestors(&self, index: usize) -> Vec<usize> {
+        let mut ancestors: Vec<usize> = self
+            .reachable(index, Direction::Incoming)
+            .into_iter()
+            .collect();
+        ancestors.sort_unstable();
+        ancestors
     }
-}
-
-/// Represents an error that can occur when trying to convert `Material` resources variants to another variant.
-#[derive(Error, Debug, PartialEq, Eq)]
-pub enum ResourceConversionError {
-    /// Used when trying to convert a resource with an index out of bounds.
-    #[error("The resource with the specified index wasn't found")]
-    ResourceNotFound,
-    /// Used when trying to convert a resource that's not a material, for example personnel.
-    #[error("Tried to convert a resource that's not a material")]
-    ConversionNotPossible,
-}
 
-#[cfg(test)]
-/// Utilities to test `[Project]`
-pub mod test_utils {
-    use proptest::{collection, prelude::*};
+    /// Gets the indices of the tasks without predecessors, the ones work can start from.
+    ///
+    /// # Returns
+    ///
+    /// The indices of the root tasks, in increasing order. Isolated tasks are included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// for name in ["Get rich", "Buy an army", "Become world leader", "Gloat"] {
+    ///     project.add_task(Task::new(name));
+    /// }
+    /// project.add_time_relationship(0, 1, TimeRelationship::default()).unwrap();
+    /// project.add_time_relationship(1, 2, TimeRelationship::default()).unwrap();
+    ///
+    /// assert_eq!(project.roots(), vec![0, 3]);
+    /// ```
+    pub fn roots(&self) -> Vec<usize> {
+        (0..self.tasks.node_count())
+            .filter(|&i| self.predecessors_indices(i).next().is_none())
+            .collect()
+    }
 
-    use crate::task::{Task, test_utils::task_strategy};
+    /// Gets the indices of the tasks without successors, the ones work ends with.
+    ///
+    /// # Returns
+    ///
+    /// The indices of the leaf tasks, in increasing order. Isolated tasks are included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// for name in ["Get rich", "Buy an army", "Become world leader", "Gloat"] {
+    ///     project.add_task(Task::new(name));
+    /// }
+    /// project.add_time_relationship(0, 1, TimeRelationship::default()).unwrap();
+    /// project.add_time_relationship(1, 2, TimeRelationship::default()).unwrap();
+    ///
+    /// assert_eq!(project.leaves(), vec![2, 3]);
+    /// ```
+    pub fn leaves(&self) -> Vec<usize> {
+        (0..self.tasks.node_count())
+            .filter(|&i| self.successors_indices(i).next().is_none())
+            .collect()
+    }
 
-    use super::{Project, TimeRelationship};
+    /// Gets the indices of the tasks with neither predecessors nor successors, which are
+    /// often forgotten or entered by mistake.
+    ///
+    /// # Returns
+    ///
+    /// The indices of the isolated tasks, in increasing order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// for name in ["Get rich", "Buy an army", "Become world leader", "Gloat"] {
+    ///     project.add_task(Task::new(name));
+    /// }
+    /// project.add_time_relationship(0, 1, TimeRelationship::default()).unwrap();
+    /// project.add_time_relationship(1, 2, TimeRelationship::default()).unwrap();
+    ///
+    /// assert_eq!(project.isolated_tasks(), vec![3]);
+    /// ```
+    pub fn isolated_tasks(&self) -> Vec<usize> {
+        (0..self.tasks.node_count())
+            .filter(|&i| {
+                self.predecessors_indices(i).next().is_none()
+                    && self.successors_indices(i).next().is_none()
+            })
+            .collect()
+    }
 
-    const MAX_TASKS: usize = 100;
-    const MIN_TASKS: usize = 5;
+    /// Finds pairs of tasks that could run in parallel to shorten the schedule: there's no chain
+    /// of relationships between them in either direction, and they aren't already scheduled to
+    /// overlap. Tasks without dates are never considered to overlap.
+    ///
+    /// Pairs are returned as `(lower index, higher index)`, and at most
+    /// [`MAX_INDEPENDENT_PAIRS`] of them are returned, to keep large graphs manageable. The
+    /// relationships are only followed from the tasks visited before the limit is reached.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Get rich"));
+    /// project.add_task(Task::new("Find a crowbar"));
+    /// project.add_task(Task::new("Become world leader"));
+    /// project.add_time_relationship(0, 2, TimeRelationship::default()).unwrap();
+    /// project.add_time_relationship(1, 2, TimeRelationship::default()).unwrap();
+    ///
+    /// assert_eq!(project.independent_task_pairs(), vec![(0, 1)]);
+    /// ```
+    pub fn independent_task_pairs(&self) -> Vec<(usize, usize)> {
+        let count = self.tasks.node_count();
+        let overlapping = |a: &Task, b: &Task| match (a.start(), a.finish(), b.start(), b.finish())
+        {
+            (Some(a_start), Some(a_finish), Some(b_start), Some(b_finish)) => {
+                a_start < b_finish && b_start < a_finish
+            }
+            _ => false,
+        };
 
-    /// Generates a random task relationship kind.
-    pub fn task_relationship_strategy() -> impl Strategy<Value = TimeRelationship> {
-        prop_oneof![
-            Just(TimeRelationship::StartToFinish),
-            Just(TimeRelationship::StartToStart),
-            Just(TimeRelationship::FinishToFinish),
-            Just(TimeRelationship::FinishToStart),
-        ]
+        let mut pairs = Vec::new();
+        for i in 0..count {
+            // Tasks linked to `i` by a chain of relationships, in either direction.
+            let mut linked = self.reachable(i, Direction::Outgoing);
+            linked.extend(self.reachable(i, Direction::Incoming));
+            for j in (i + 1)..count {
+                if linked.contains(&j) {
+                    continue;
+                }
+                if let (Some(a), Some(b)) = (self.task(i), self.task(j))
+                    && !overlapping(a, b)
+                {
+                    pairs.push((i, j));
+                    if pairs.len() == MAX_INDEPENDENT_PAIRS {
+                        return pairs;
+                    }
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Returns the indices of all the tasks that can be reached from the task with index
+    /// `index`, following relationships in the given direction. The task itself isn't included.
+    fn reachable(&self, index: usize, direction: Direction) -> HashSet<usize> {
+        let mut visited = HashSet::new();
+        let mut pending = vec![index];
+        while let Some(current) = pending.pop() {
+            for next in self.tasks.neighbors_directed(current.into(), direction) {
+                if visited.insert(next.index()) {
+                    pending.push(next.index());
+                }
+            }
+        }
+        visited
+    }
+
+    /// Updates the project by making sure the predecessors for the task with
+    /// index `node_index` are exactly the ones listed in `predecessors_indices`.
+    /// Relationships that are kept don't change, new ones are finish to start: use
+    /// [`Project::add_time_relationship`] for the other kinds.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_index` - The index whose predecessors need to be updated.
+    /// * `predecessors_indices` - The indices of the predecessors.
+    ///
+    /// # Errors
+    /// TODO!
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let tasks = vec![
+    ///      Task::new("Become world leader"),
+    ///      Task::new("Get rich"),
+    ///      Task::new("Be evil")
+    /// ];
+    /// let mut project = Project::builder().name("World domination").tasks(tasks).build();
+    ///
+    /// project.update_predecessors(2, &[0, 1]);
+    /// assert_eq!(project.predecessors(2).count(), 2);
+    pub fn update_predecessors(
+        &mut self,
+        task_index: usize,
+        predecessors_indices: &[usize],
+    ) -> anyhow::Result<()> {
+        self.validate_indices(task_index, predecessors_indices)?;
+
+        // Update predecessors in a cloned data structure for tasks.
+        // If this gives an error, the actual data structure won't be polluted.
+        // TODO: benchmark and see if there is a better way to do this without cloning.
+        let mut tasks_clone = self.tasks.clone();
+        for &i in predecessors_indices {
+            if tasks_clone
+                .add_edge(i.into(), task_index.into(), Dependency::default())
+                .is_err()
+            {
+                bail!(cycle_error(&tasks_clone, i, task_index));
+            }
+        }
+
+        // Remove the predecessors that aren't listed anymore.
+        let current: Vec<usize> = self.predecessors_indices(task_index).collect();
+        for &i in current.iter().filter(|i| !predecessors_indices.contains(i)) {
+            self.remove_time_relationship(i, task_index)
+                .context("It should have been possible to remove a predecessor. This is a bug.")?;
+        }
+        // Add the new ones.
+        for &i in predecessors_indices {
+            if self.dependency(i, task_index).is_some() {
+                continue;
+            }
+            self.tasks
+                .add_edge(i.into(), task_index.into(), Dependency::default())
+                .context("This shouldn't have happened because the data structure was just checked for cycles.")?;
+            self.record_relationship_added(i, task_index, Dependency::default());
+        }
+        Ok(())
+    }
+
+    /// Checks that all the tasks with indices passed as parameters actually exist in the project.
+    fn validate_indices(&self, task_index: usize, related_indices: &[usize]) -> anyhow::Result<()> {
+        let graph_edges: HashSet<usize> =
+            self.tasks.node_identifiers().map(|i| i.index()).collect();
+        // Make sure all the listed predecessors exist within the graph.
+        if !related_indices.iter().all(|i| graph_edges.contains(i)) {
+            bail!("Some index in the predecessors list doesn't exist in the graph");
+        }
+
+        // Make sure the task index exists withing the graph.
+        if !graph_edges.contains(&task_index) {
+            bail!(format!(
+                "Task index {task_index} doesn't exist in the graph"
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Updates the project by making sure the successors for the task with
+    /// index `node_index` are exactly the ones listed in `successors_indices`.
+    /// Relationships that are kept don't change, new ones are finish to start: use
+    /// [`Project::add_time_relationship`] for the other kinds.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_index` - The index whose successors need to be updated.
+    /// * `successors_indices` - The indices of the successors.
+    ///
+    /// # Errors
+    /// TODO!
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let tasks = vec![
+    ///      Task::new("Become world leader"),
+    ///      Task::new("Get rich"),
+    ///      Task::new("Be evil")
+    /// ];
+    /// let mut project = Project::builder().name("World domination").tasks(tasks).build();
+    ///
+    /// project.update_successors(0, &[1, 2]);
+    /// assert_eq!(project.successors(0).count(), 2);
+    pub fn update_successors(
+        &mut self,
+        task_index: usize,
+        successors_indices: &[usize],
+    ) -> anyhow::Result<()> {
+        self.validate_indices(task_index, successors_indices)?;
+
+        // Update successors in a cloned data structure for tasks.
+        // If this gives an error, the actual data structure won't be polluted.
+        // TODO: benchmark and see if there is a better way to do this without cloning.
+        let mut tasks_clone = self.tasks.clone();
+        for &i in successors_indices {
+            if tasks_clone
+                .add_edge(task_index.into(), i.into(), Dependency::default())
+                .is_err()
+            {
+                bail!(cycle_error(&tasks_clone, task_index, i));
+            }
+        }
+
+        // Remove the successors that aren't listed anymore.
+        let current: Vec<usize> = self.successors_indices(task_index).collect();
+        for &i in current.iter().filter(|i| !successors_indices.contains(i)) {
+            self.remove_time_relationship(task_index, i)
+                .context("It should have been possible to remove a successor. This is a bug.")?;
+        }
+        // Add the new ones.
+        for &i in successors_indices {
+            if self.dependency(task_index, i).is_some() {
+                continue;
+            }
+            self.tasks
+                .add_edge(task_index.into(), i.into(), Dependency::default())
+                .context("This shouldn't have happened because the data structure was just checked for cycles.")?;
+            self.record_relationship_added(task_index, i, Dependency::default());
+        }
+        Ok(())
+    }
+
+    /// Adds a subtask to a given task. This relationship means that the parent
+    /// task is completed when all the children are completed. Also, the parent's cost
+    /// and duration is the cumulative cost and duration of the children.
+    ///
+    /// Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let tasks = vec![
+    ///      Task::new("Become world leader"),
+    ///      Task::new("Get rich"),
+    ///      Task::new("Be evil")
+    /// ];
+    /// let mut project = Project::builder().name("World domination").tasks(tasks).build();
+    ///
+    /// project.add_subtask(0, 1);
+    /// project.add_subtask(0, 2);
+    /// assert_eq!(project.subtasks(0).len(), 2);
+    /// ```
+    pub fn add_subtask(&mut self, parent_index: usize, child_index: usize) {
+        self.subtask_relationships.push(SubtaskRelationship {
+            task: parent_index,
+            subtask: child_index,
+        });
+    }
+
+    /// Returns every subtask relationship, as the index of the parent and of the subtask.
+    pub(crate) fn subtask_pairs(&self) -> impl Iterator<Item = (usize, usize)> {
+        self.subtask_relationships
+            .iter()
+            .map(|relationship| (relationship.task, relationship.subtask))
+    }
+
+    /// Gets a list of all the subtasks of the given task.
+    ///
+    /// Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Become world leader"));
+    /// project.add_task(Task::new("Get rich"));
+    /// assert_eq!(project.subtasks(0).len(), 0);
+    ///
+    /// project.add_subtask(0, 1);
+    /// assert_eq!(project.subtasks(0).len(), 1);
+    /// ```
+    pub fn subtasks(&self, task_index: usize) -> Vec<usize> {
+        self.subtask_relationships
+            .iter()
+            .filter(|r| r.task == task_index)
+            .map(|r| r.subtask)
+            .collect()
+    }
+
+    /// Computes the duration of a task as the sum of the durations of its subtasks, recursively.
+    /// Tasks without subtasks contribute their own duration.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_index` - The index of the task.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PositiveDuration)` - The rolled up duration.
+    /// * `None` - If the task doesn't exist, if one of the tasks without subtasks has no
+    ///   duration, if the subtask relationships form a cycle or if the sum is too long.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Become world leader"));
+    /// for hours in [2, 3] {
+    ///     let mut task = Task::new(format!("{hours}h"));
+    ///     task.edit_duration(Duration::hours(hours).try_into().unwrap());
+    ///     project.add_task(task);
+    /// }
+    /// project.add_subtask(0, 1);
+    /// project.add_subtask(0, 2);
+    ///
+    /// assert_eq!(*project.rolled_up_duration(0).unwrap(), Duration::hours(5));
+    /// ```
+    pub fn rolled_up_duration(&self, task_index: usize) -> Option<PositiveDuration> {
+        self.roll_up(task_index, &mut Vec::new(), &|task: &Task| {
+            task.duration().map(|duration| *duration)
+        })?
+        .try_into()
+        .ok()
+    }
+
+    /// Computes the cost of a task as the sum of the costs of its subtasks, recursively.
+    /// Tasks without subtasks cost the hourly rates of the personnel and the non consumable
+    /// materials assigned to them, over their duration, rounded up to the hundredth.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_index` - The index of the task.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Money)` - The rolled up cost.
+    /// * `None` - If the task doesn't exist, if one of the tasks without subtasks has no
+    ///   duration, if the costs are in different currencies or too large, or if the subtask
+    ///   relationships form a cycle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::{
+    ///     money::{Currency, Money},
+    ///     person::Person,
+    ///     project::Project,
+    ///     resources::Resource,
+    ///     task::Task,
+    /// };
+    ///
+    /// let mut project = Project::new("World domination");
+    /// let person = project.add_resource(Resource::Personnel {
+    ///     person: Person::new("Margherita", "Hack").unwrap(),
+    ///     hourly_rate: Some(Money::new(1550, Currency::USD)),
+    ///     availability: Vec::new(),
+    /// });
+    /// project.add_task(Task::new("Become world leader"));
+    /// let mut task = Task::new("Get rich");
+    /// task.edit_duration(Duration::minutes(90).try_into().unwrap());
+    /// task.add_resource(person);
+    /// project.add_task(task);
+    /// project.add_subtask(0, 1);
+    ///
+    /// assert_eq!(project.rolled_up_cost(0), Some(Money::new(2325, Currency::USD)));
+    /// ```
+    pub fn rolled_up_cost(&self, task_index: usize) -> Option<Money> {
+        self.try_roll_up(
+            task_index,
+            &mut Vec::new(),
+            &|task: &Task| self.task_cost(task),
+            &|a, b| a.checked_add(b).ok(),
+        )
+    }
+
+    /// Computes how much of a task is done, from 0 to 100, as the average of its subtasks
+    /// weighted by their duration, recursively. Tasks without subtasks report their own
+    /// progress. If none of the tasks without subtasks has a duration, they weigh the same.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent_index` - The index of the task.
+    ///
+    /// # Returns
+    ///
+    /// The completion percentage, or 0 if the task doesn't exist or if the subtask
+    /// relationships form a cycle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Become world leader"));
+    /// for (hours, percent_complete) in [(1, 100), (3, 20)] {
+    ///     let mut task = Task::new("Get rich");
+    ///     task.edit_duration(Duration::hours(hours).try_into().unwrap());
+    ///     task.set_percent_complete(percent_complete);
+    ///     project.add_task(task);
+    /// }
+    /// project.add_subtask(0, 1);
+    /// project.add_subtask(0, 2);
+    ///
+    /// assert_eq!(project.percent_complete(0), 40.0);
+    /// ```
+    pub fn percent_complete(&self, parent_index: usize) -> f64 {
+        let minutes = |task: &Task| {
+            // Precision loss is irrelevant at the scale of task durations.
+            #[allow(clippy::cast_precision_loss)]
+            task.duration()
+                .map_or(0.0, |duration| duration.num_minutes() as f64)
+        };
+        let roll_up = |leaf: &dyn Fn(&Task) -> f64| {
+            self.roll_up(parent_index, &mut Vec::new(), &|task: &Task| {
+                Some(leaf(task))
+            })
+            .unwrap_or_default()
+        };
+        let progress = |task: &Task| f64::from(task.percent_complete());
+
+        let total_minutes = roll_up(&minutes);
+        if total_minutes > 0.0 {
+            roll_up(&|task: &Task| minutes(task) * progress(task)) / total_minutes
+        } else {
+            let count = roll_up(&|_: &Task| 1.0);
+            if count > 0.0 {
+                roll_up(&progress) / count
+            } else {
+                0.0
+            }
+        }
+    }
+
+    /// Returns the start date of the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project};
+    /// use chrono::Utc;
+    ///
+    /// let start_date = Utc::now();
+    /// let mut project = Project::builder().name("World domination").start_date(start_date).build();
+    /// assert_eq!(project.start_date(), Some(start_date));
+    /// ```
+    pub fn start_date(&self) -> Option<DateTime<Utc>> {
+        self.start_date
+    }
+
+    /// Sets the calendar the project is scheduled on. With a calendar, task durations and
+    /// relationship lags are working time, and scheduling only moves dates over working
+    /// time: a day long task starting on Friday afternoon finishes on Monday. The finish
+    /// of a task scheduled this way doesn't match its start plus its duration.
+    ///
+    /// # Arguments
+    ///
+    /// * `calendar` - The working calendar.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, NaiveDate};
+    /// use planter_core::{calendar::Calendar, project::Project, task::Task};
+    ///
+    /// let friday = NaiveDate::from_ymd_opt(2025, 6, 6).unwrap();
+    /// let monday = NaiveDate::from_ymd_opt(2025, 6, 9).unwrap();
+    /// let mut task = Task::new("Find a crowbar");
+    /// task.edit_duration(Duration::hours(8).try_into().unwrap());
+    /// task.edit_start(friday.and_hms_opt(9, 0, 0).unwrap().and_utc()).unwrap();
+    /// let mut project = Project::new("World domination").with_calendar(Calendar::default());
+    /// project.add_task(task);
+    ///
+    /// project.roll_forward(friday.and_hms_opt(13, 0, 0).unwrap().and_utc()).unwrap();
+    /// assert_eq!(
+    ///     project.task(0).unwrap().finish(),
+    ///     Some(monday.and_hms_opt(13, 0, 0).unwrap().and_utc())
+    /// );
+    /// ```
+    pub fn with_calendar(mut self, calendar: Calendar) -> Self {
+        self.calendar = Some(calendar);
+        self
+    }
+
+    /// Sets whether scheduling accounts for the buffers of the tasks. When it does, tasks
+    /// take their [effective duration](Task::effective_duration) in the schedule: their
+    /// successors wait for the buffer, while the dates of the tasks themselves don't include
+    /// it. It's false by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `include_buffers` - Whether to include the buffers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let start = Utc::now();
+    /// let mut project = Project::builder().start_date(start).build();
+    /// for name in ["Find a crowbar", "Profit"] {
+    ///     let mut task = Task::new(name);
+    ///     task.edit_duration(Duration::hours(8).try_into().unwrap());
+    ///     task.edit_buffer(Duration::hours(2).try_into().unwrap());
+    ///     project.add_task(task);
+    /// }
+    /// project.add_time_relationship(0, 1, Default::default()).unwrap();
+    ///
+    /// project.schedule().unwrap();
+    /// assert_eq!(project.task(1).unwrap().start(), Some(start + Duration::hours(8)));
+    ///
+    /// project.set_include_buffers(true);
+    /// project.schedule().unwrap();
+    /// assert_eq!(project.task(0).unwrap().finish(), Some(start + Duration::hours(8)));
+    /// assert_eq!(project.task(1).unwrap().start(), Some(start + Duration::hours(10)));
+    /// ```
+    pub fn set_include_buffers(&mut self, include_buffers: bool) {
+        self.include_buffers = include_buffers;
+    }
+
+    /// Returns whether scheduling accounts for the buffers of the tasks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::project::Project;
+    ///
+    /// let mut project = Project::new("World domination");
+    /// assert!(!project.include_buffers());
+    /// project.set_include_buffers(true);
+    /// assert!(project.include_buffers());
+    /// ```
+    pub fn include_buffers(&self) -> bool {
+        self.include_buffers
+    }
+
+    /// Returns the calendar the project is scheduled on, if any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{calendar::Calendar, project::Project};
+    ///
+    /// let project = Project::new("World domination");
+    /// assert!(project.calendar().is_none());
+    ///
+    /// let project = project.with_calendar(Calendar::default());
+    /// assert_eq!(project.calendar(), Some(&Calendar::default()));
+    /// ```
+    pub fn calendar(&self) -> Option<&Calendar> {
+        self.calendar.as_ref()
+    }
+
+    /// Schedules every task as soon as possible from the project start date. Each task starts
+    /// when the relationships with its predecessors allow, and finishes after its duration.
+    ///
+    /// Constraints move the task on top of that: tasks that must start or finish on a date are
+    /// placed there, tasks that can't start or finish before a date are delayed to it.
+    /// Constraints that set a latest date can't pull a task before its predecessors, so they
+    /// are left for [`Project::validate`] to report. With a calendar, tasks only move
+    /// forward during working time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the project has no start date, if any task has no duration, or if
+    /// the computed dates are out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::{
+    ///     project::{Project, TimeRelationship},
+    ///     task::{Task, TaskConstraint},
+    /// };
+    ///
+    /// let start = Utc::now();
+    /// let mut project = Project::builder().name("World domination").start_date(start).build();
+    /// for hours in [2, 3, 1] {
+    ///     let mut task = Task::new("Task");
+    ///     task.edit_duration(Duration::hours(hours).try_into().unwrap());
+    ///     project.add_task(task);
+    /// }
+    /// project.add_time_relationship(0, 1, TimeRelationship::FinishToStart).unwrap();
+    /// let tomorrow = start + Duration::days(1);
+    /// project.task_mut(2).unwrap().set_constraint(TaskConstraint::StartNoEarlierThan(tomorrow));
+    ///
+    /// project.schedule().unwrap();
+    /// assert_eq!(project.task(1).unwrap().start(), Some(start + Duration::hours(2)));
+    /// assert_eq!(project.task(1).unwrap().finish(), Some(start + Duration::hours(5)));
+    /// assert_eq!(project.task(2).unwrap().start(), Some(tomorrow));
+    /// ```
+    pub fn schedule(&mut self) -> anyhow::Result<()> {
+        let project_start = self
+            .start_date
+            .context("The project has no start date to schedule from")?;
+        let order = self.tasks_topological()?;
+        let durations = self.task_durations()?;
+        let calendar = self.calendar.as_ref();
+        let at = |date| WorkingDate { date, calendar };
+        let mut starts = vec![project_start; order.len()];
+        let mut finishes = vec![project_start; order.len()];
+
+        for &i in &order {
+            let duration = durations[i];
+            let earliest = self
+                .tasks
+                .graph()
+                .edges_directed(i.into(), Direction::Incoming)
+                .map(|edge| {
+                    let predecessor = edge.source().index();
+                    scheduling::earliest_successor_start(
+                        *edge.weight(),
+                        at(starts[predecessor]),
+                        at(finishes[predecessor]),
+                        duration,
+                    )
+                    .date
+                })
+                .fold(project_start, DateTime::max);
+
+            let constraint = self.task(i).and_then(Task::constraint);
+            let start = match constraint {
+                Some(TaskConstraint::MustStartOn(date)) => date,
+                Some(TaskConstraint::MustFinishOn(date)) => (at(date) - duration).date,
+                Some(TaskConstraint::StartNoEarlierThan(date)) => earliest.max(date),
+                Some(TaskConstraint::FinishNoEarlierThan(date)) => {
+                    earliest.max((at(date) - duration).date)
+                }
+                Some(
+                    TaskConstraint::StartNoLaterThan(_) | TaskConstraint::FinishNoLaterThan(_),
+                )
+                | None => earliest,
+            };
+            starts[i] = shift(calendar, start, Duration::zero())?;
+            finishes[i] = shift(calendar, starts[i], duration)?;
+        }
+
+        for i in order {
+            let Some(task) = self.tasks.node_weight_mut(i.into()) else {
+                continue;
+            };
+            // Successors wait for the buffer, but it isn't part of the dates of the task.
+            let span = task.span().map_or(durations[i], |span| *span);
+            match calendar {
+                Some(_) => task.set_working_dates(starts[i], shift(calendar, starts[i], span)?),
+                None => task
+                    .edit_start(starts[i])
+                    .with_context(|| format!("Couldn't schedule task {i}"))?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Schedules every task as late as possible, so that the project ends exactly at `target_finish`.
+    /// Each task gets its latest start and finish according to the relationships with its successors,
+    /// and the project start date is moved to the earliest of the computed starts.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_finish` - The date the project has to be completed by.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any task has no duration, or if the computed dates are too far apart.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// let mut task = Task::new("Find a crowbar");
+    /// task.edit_duration(Duration::hours(2).try_into().unwrap());
+    /// project.add_task(task);
+    ///
+    /// let deadline = Utc::now();
+    /// project.schedule_backward(deadline).unwrap();
+    /// assert_eq!(project.task(0).unwrap().finish(), Some(deadline));
+    /// assert_eq!(project.start_date(), Some(deadline - Duration::hours(2)));
+    /// ```
+    pub fn schedule_backward(&mut self, target_finish: DateTime<Utc>) -> anyhow::Result<()> {
+        let order = self.tasks_topological()?;
+        let durations = self.task_durations()?;
+        let ends = vec![Duration::zero(); order.len()];
+        let (late_start, _) = self.backward_pass(&order, &durations, &ends);
+        let calendar = self.calendar.as_ref();
+        let starts = late_start
+            .iter()
+            .map(|offset| shift(calendar, target_finish, *offset))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        for (i, &start) in starts.iter().enumerate() {
+            let Some(task) = self.tasks.node_weight_mut(i.into()) else {
+                continue;
+            };
+            let span = task.span().map_or(durations[i], |span| *span);
+            match calendar {
+                Some(calendar) => {
+                    task.set_working_dates(start, shift(Some(calendar), start, span)?);
+                }
+                None => task
+                    .edit_start(start)
+                    .with_context(|| format!("Couldn't schedule task {i}"))?,
+            }
+        }
+        if let Some(earliest) = starts.into_iter().min() {
+            self.start_date = Some(earliest);
+        }
+        Ok(())
+    }
+
+    /// Moves the whole plan to start at `new_start`: every task date moves by the time
+    /// between the current start date of the project and `new_start`, pauses of split tasks
+    /// included. Constraints and deadlines don't move, since they are commitments, so the
+    /// tasks that break them after the move are reported by [`Project::validate`].
+    ///
+    /// # Arguments
+    ///
+    /// * `new_start` - The new start date of the project.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the project has no start date, or if some date would be out of
+    /// range. The project is left untouched in both cases.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let start = Utc::now();
+    /// let mut project = Project::builder().name("World domination").start_date(start).build();
+    /// let mut task = Task::new("Find a crowbar");
+    /// task.edit_start(start + Duration::days(1)).unwrap();
+    /// project.add_task(task);
+    ///
+    /// project.reschedule_from(start + Duration::days(7)).unwrap();
+    /// assert_eq!(project.start_date(), Some(start + Duration::days(7)));
+    /// assert_eq!(project.task(0).unwrap().start(), Some(start + Duration::days(8)));
+    /// ```
+    pub fn reschedule_from(&mut self, new_start: DateTime<Utc>) -> anyhow::Result<()> {
+        let start = self
+            .start_date
+            .context("The project has no start date to move from")?;
+        let offset = new_start - start;
+        let shifted = self
+            .tasks()
+            .enumerate()
+            .map(|(i, task)| {
+                let mut task = task.clone();
+                task.shift(offset)
+                    .with_context(|| format!("Couldn't move task {i}"))?;
+                Ok(task)
+            })
+            .collect::<anyhow::Result<Vec<Task>>>()?;
+
+        for (task, shifted) in self.tasks_mut().zip(shifted) {
+            *task = shifted;
+        }
+        self.start_date = Some(new_start);
+        Ok(())
+    }
+
+    /// Moves the work that should have started by `as_of`, but didn't, to begin at `as_of`.
+    ///
+    /// Tasks that haven't started yet and are planned to start before `as_of` are
+    /// rescheduled to start at `as_of`, keeping their duration. The dependents of a moved
+    /// task are pushed forward as well, as much as needed to keep their relationships.
+    /// Completed and in progress tasks, as well as tasks without a start, are never moved.
+    ///
+    /// # Arguments
+    ///
+    /// * `as_of` - The status date: nothing that hasn't started can begin before it.
+    ///
+    /// # Returns
+    ///
+    /// The indices of the tasks that were moved, in dependency order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the task graph has a cycle, or if a task can't be moved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let as_of = Utc::now();
+    /// let mut task = Task::new("Find a crowbar");
+    /// task.edit_duration(Duration::hours(2).try_into().unwrap());
+    /// task.edit_start(as_of - Duration::days(1)).unwrap();
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(task);
+    ///
+    /// assert_eq!(project.roll_forward(as_of).unwrap(), vec![0]);
+    /// assert_eq!(project.task(0).unwrap().start(), Some(as_of));
+    /// assert_eq!(project.task(0).unwrap().finish(), Some(as_of + Duration::hours(2)));
+    /// ```
+    pub fn roll_forward(&mut self, as_of: DateTime<Utc>) -> anyhow::Result<Vec<usize>> {
+        let mut moved = Vec::new();
+        let calendar = self.calendar.as_ref();
+        let at = |date| WorkingDate { date, calendar };
+
+        for i in self.tasks_topological()? {
+            let Some(task) = self.task(i) else {
+                continue;
+            };
+            if matches!(
+                task.status(),
+                TaskStatus::InProgress | TaskStatus::Completed
+            ) {
+                continue;
+            }
+            let Some(start) = task.start() else {
+                continue;
+            };
+            let duration = task.duration().map_or_else(Duration::zero, |d| *d);
+
+            let earliest = self
+                .tasks
+                .graph()
+                .edges_directed(i.into(), Direction::Incoming)
+                .filter_map(|edge| {
+                    let predecessor = self.task(edge.source().index())?;
+                    Some(
+                        scheduling::earliest_successor_start(
+                            *edge.weight(),
+                            at(predecessor.start()?),
+                            at(predecessor.finish()?),
+                            duration,
+                        )
+                        .date,
+                    )
+                })
+                .fold(as_of, DateTime::max);
+            let earliest = shift(calendar, earliest, Duration::zero())?;
+
+            if earliest > start {
+                if let Some(task) = self.tasks.node_weight_mut(i.into()) {
+                    match calendar {
+                        Some(calendar) => task.set_working_dates(
+                            earliest,
+                            shift(Some(calendar), earliest, duration)?,
+                        ),
+                        None => task
+                            .edit_start(earliest)
+                            .with_context(|| format!("Couldn't move task {i}"))?,
+                    }
+                }
+                moved.push(i);
+            }
+        }
+
+        Ok(moved)
+    }
+
+    /// Adds a resource to the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `resource` - The resource to add to the project.
+    ///
+    /// # Returns
+    ///
+    /// The index of the new resource, to assign it to tasks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{resources::Resource, project::Project, person::Person};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// let index = project.add_resource(Resource::Personnel {
+    ///     person: Person::new("Sebastiano", "Giordano").unwrap(),
+    ///     hourly_rate: None,
+    ///     availability: Vec::new(),
+    /// });
+    /// assert_eq!(project.resources().len(), 1);
+    /// assert_eq!(index, 0);
+    /// ```
+    pub fn add_resource(&mut self, resource: Resource) -> usize {
+        self.resources.push(resource);
+        self.resources.len() - 1
+    }
+
+    /// Adds a resource to the project, while building it.
+    ///
+    /// # Arguments
+    ///
+    /// * `resource` - The resource to add to the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, resources::{Material, Resource}};
+    ///
+    /// let project = Project::new("World domination")
+    ///     .with_resource(Resource::Material(Material::new("Crowbar")));
+    /// assert_eq!(project.resources().len(), 1);
+    /// ```
+    pub fn with_resource(mut self, resource: Resource) -> Self {
+        self.add_resource(resource);
+        self
+    }
+
+    /// Adds resources to the project, after the existing ones, while building it.
+    ///
+    /// # Arguments
+    ///
+    /// * `resources` - The resources to add to the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, resources::{Material, Resource}};
+    ///
+    /// let project = Project::new("World domination")
+    ///     .with_resource(Resource::Material(Material::new("Crowbar")))
+    ///     .with_resources(["Stimpack", "Nuka-Cola"].map(|name| Resource::Material(Material::new(name))));
+    /// assert_eq!(project.resources().len(), 3);
+    /// assert_eq!(
+    ///     project.resource(2),
+    ///     Some(&Resource::Material(Material::new("Nuka-Cola")))
+    /// );
+    /// ```
+    pub fn with_resources(mut self, resources: impl IntoIterator<Item = Resource>) -> Self {
+        self.resources.extend(resources);
+        self
+    }
+
+    /// Get a reference to a resource used in the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{resources::Resource, project::Project, person::Person};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_resource(Resource::Personnel {
+    ///     person: Person::new("Sebastiano", "Giordano").unwrap(),
+    ///     hourly_rate: None,
+    ///     availability: Vec::new(),
+    /// });
+    ///
+    /// assert!(project.resource(0).is_some());
+    /// ```
+    pub fn resource(&self, index: usize) -> Option<&Resource> {
+        self.resources.get(index)
+    }
+
+    /// Remove a resource from the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the resource.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resource index is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{resources::Resource, project::Project, person::Person};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_resource(Resource::Personnel {
+    ///     person: Person::new("Sebastiano", "Giordano").unwrap(),
+    ///     hourly_rate: None,
+    ///     availability: Vec::new(),
+    /// });
+    ///
+    /// assert!(project.resource(0).is_some());
+    /// assert!(project.rm_resource(0).is_ok());
+    /// assert!(project.resource(0).is_none());
+    /// assert!(project.rm_resource(0).is_err());
+    /// ```
+    pub fn rm_resource(&mut self, index: usize) -> anyhow::Result<()> {
+        if index >= self.resources.len() {
+            bail!("Tried removing the non existing resource {index}");
+        }
+        self.resources.remove(index);
+        for task in self.tasks_mut() {
+            task.reindex_resources(|i| match i.cmp(&index) {
+                Ordering::Less => Some(i),
+                Ordering::Equal => None,
+                Ordering::Greater => Some(i - 1),
+            });
+        }
+        Ok(())
+    }
+
+    /// Get a mutable reference to a resource used in the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{resources::Resource, project::Project, person::Person};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_resource(Resource::Personnel {
+    ///     person: Person::new("Sebastiano", "Giordano").unwrap(),
+    ///     hourly_rate: None,
+    ///     availability: Vec::new(),
+    /// });
+    ///
+    /// let resource = project.resource_mut(0).unwrap();
+    /// match resource {
+    ///   Resource::Material(_) => panic!(),
+    ///   Resource::Personnel {
+    ///     person,
+    ///     ..
+    ///   } => {
+    ///     person.update_first_name("Alessandro");
+    ///     assert_eq!(person.first_name(), "Alessandro");
+    ///   }
+    /// }
+    /// ```
+    pub fn resource_mut(&mut self, index: usize) -> Option<&mut Resource> {
+        self.resources.get_mut(index)
+    }
+
+    /// Returns a reference to the list of resources associated with the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{resources::{Resource, Material, NonConsumable}, project::Project};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_resource(Resource::Material(Material::NonConsumable(
+    ///    NonConsumable::new("Crowbar"),
+    /// )));
+    /// assert_eq!(project.resources().len(), 1);
+    /// ```
+    pub fn resources(&self) -> &[Resource] {
+        &self.resources
+    }
+
+    /// Returns mutable references to the resources of the project, to edit them in place.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{resources::{Resource, Material}, project::Project};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_resource(Resource::Material(Material::new("Stimpack")));
+    /// project.add_resource(Resource::Material(Material::new("Crowbar")));
+    ///
+    /// for resource in project.resources_mut() {
+    ///     if let Resource::Material(material) = resource {
+    ///         material.update_quantity(10);
+    ///     }
+    /// }
+    /// assert!(project.resources().iter().all(|resource| match resource {
+    ///     Resource::Material(material) => material.quantity() == Some(10),
+    ///     Resource::Personnel { .. } => false,
+    /// }));
+    /// ```
+    pub fn resources_mut(&mut self) -> impl Iterator<Item = &mut Resource> {
+        self.resources.iter_mut()
+    }
+
+    /// Computes how much of a consumable resource is left once the completed tasks used their
+    /// share of it, as recorded with [`Task::set_consumption`].
+    ///
+    /// # Arguments
+    ///
+    /// * `resource_index` - The index of the consumable resource.
+    ///
+    /// # Returns
+    ///
+    /// The quantity of the resource minus what the completed tasks used. It's negative if
+    /// they used more than there is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resource doesn't exist, isn't consumable, or has no quantity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, resources::{Material, Resource}, task::{Task, TaskStatus}};
+    ///
+    /// let mut stimpacks = Material::new("Stimpack");
+    /// stimpacks.update_quantity(10);
+    /// let mut project = Project::new("World domination");
+    /// project.add_resource(Resource::Material(stimpacks));
+    /// for (name, amount, status) in [
+    ///     ("Patch up the troops", 4, TaskStatus::Completed),
+    ///     ("Patch up the generals", 5, TaskStatus::InProgress),
+    /// ] {
+    ///     let mut task = Task::new(name);
+    ///     task.set_consumption(0, amount);
+    ///     task.set_status(status);
+    ///     project.add_task(task);
+    /// }
+    ///
+    /// assert_eq!(project.remaining_quantity(0).unwrap(), 6);
+    /// ```
+    pub fn remaining_quantity(&self, resource_index: usize) -> anyhow::Result<i64> {
+        let Some(Resource::Material(material @ Material::Consumable(_))) =
+            self.resources.get(resource_index)
+        else {
+            bail!("Resource {resource_index} isn't a consumable material");
+        };
+        let quantity = material
+            .quantity()
+            .with_context(|| format!("The quantity of resource {resource_index} isn't known"))?;
+        let used: i64 = self
+            .tasks()
+            .filter(|task| task.completed())
+            .map(|task| i64::from(task.consumption(resource_index)))
+            .sum();
+        Ok(i64::from(quantity) - used)
+    }
+
+    /// Converts a resource into a `Consumable`, if that's possible.
+    ///
+    /// # Arguments
+    ///
+    /// * resource_index - The index of a `Material`, that's not a `Consumable`
+    ///
+    /// # Errors
+    ///
+    /// * `ResourceConversionError::ResourceNotFound` - If a resource with the specified index does not exist.
+    /// * `ResourceConversionError::ConversionNotPossible` - When trying to convert a resource that's not a `Material`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{resources::{Resource, Material, NonConsumable}, project::Project};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_resource(Resource::Material(Material::NonConsumable(
+    ///    NonConsumable::new("Crowbar"),
+    /// )));
+    /// assert!(project.res_into_consumable(0).is_ok());
+    /// ```
+    ///
+    pub fn res_into_consumable(
+        &mut self,
+        resource_index: usize,
+    ) -> Result<(), ResourceConversionError> {
+        let res = self
+            .resources
+            .get_mut(resource_index)
+            .ok_or(ResourceConversionError::ResourceNotFound)?;
+        let Resource::Material(material) = res else {
+            return Err(ResourceConversionError::ConversionNotPossible);
+        };
+        *material = std::mem::take(material).into_consumable();
+        Ok(())
+    }
+
+    /// Converts a resource into a `NonConsumable`, if that's possible.
+    ///
+    /// # Arguments
+    ///
+    /// * resource_index - The index of a `Material`, that's not a `NonConsumable`
+    ///
+    /// # Errors
+    ///
+    /// * `ResourceConversionError::ResourceNotFound` - If a resource with the specified index does not exist.
+    /// * `ResourceConversionError::ConversionNotPossible` - When trying to convert a resource that's not a `Material`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{resources::{Resource, Material, Consumable}, project::Project};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_resource(Resource::Material(Material::Consumable(
+    ///    Consumable::new("Stimpack"),
+    /// )));
+    /// assert!(project.res_into_nonconsumable(0).is_ok());
+    /// ```
+    pub fn res_into_nonconsumable(
+        &mut self,
+        resource_index: usize,
+    ) -> Result<(), ResourceConversionError> {
+        let res = self
+            .resources
+            .get_mut(resource_index)
+            .ok_or(ResourceConversionError::ResourceNotFound)?;
+        let Resource::Material(material) = res else {
+            return Err(ResourceConversionError::ConversionNotPossible);
+        };
+        *material = std::mem::take(material).into_non_consumable();
+        Ok(())
+    }
+
+    /// Computes how long a personnel resource sits idle within the project span.
+    /// The span goes from the project start date to the latest finish among its tasks,
+    /// and the resource is considered busy while any of the dated tasks it's assigned to is running.
+    /// If the project has a calendar, only working time counts.
+    ///
+    /// # Arguments
+    ///
+    /// * `resource_index` - The index of a `Resource::Personnel` in the project.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PositiveDuration)` - The time in which the resource has no assigned task running.
+    /// * `None` - If the resource isn't personnel, is never assigned to a task, or the project isn't dated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::{person::Person, project::Project, resources::Resource, task::Task};
+    ///
+    /// let start = Utc::now();
+    /// let mut project = Project::builder().name("World domination").start_date(start).build();
+    /// let person = project.add_resource(Resource::Personnel {
+    ///     person: Person::new("Sebastiano", "Giordano").unwrap(),
+    ///     hourly_rate: None,
+    ///     availability: Vec::new(),
+    /// });
+    /// let mut task = Task::new("Find a crowbar");
+    /// task.add_resource(person);
+    /// task.edit_start(start).unwrap();
+    /// task.edit_finish(start + Duration::hours(2)).unwrap();
+    /// project.add_task(task);
+    ///
+    /// assert!(project.idle_time(0).unwrap().is_zero());
+    /// ```
+    pub fn idle_time(&self, resource_index: usize) -> Option<PositiveDuration> {
+        let resource = self.resources.get(resource_index)?;
+        if !matches!(resource, Resource::Personnel { .. }) {
+            return None;
+        }
+        let assigned = self.tasks_assigned_to(resource_index);
+        if assigned.is_empty() {
+            return None;
+        }
+
+        let span_start = self.start_date?;
+        let span_finish = self.tasks().filter_map(Task::finish).max()?;
+        if span_finish <= span_start {
+            return Some(PositiveDuration::ZERO);
+        }
+
+        let elapsed = |start: DateTime<Utc>, finish: DateTime<Utc>| match &self.calendar {
+            Some(calendar) => calendar.working_duration_between(start, finish),
+            None => finish - start,
+        };
+
+        // Clip every busy interval to the project span, then merge the overlapping ones.
+        let mut busy: Vec<(DateTime<Utc>, DateTime<Utc>)> = assigned
+            .iter()
+            .filter_map(|&i| {
+                let task = self.task(i)?;
+                let start = task.start()?.max(span_start);
+                let finish = task.finish()?.min(span_finish);
+                (start < finish).then_some((start, finish))
+            })
+            .collect();
+        busy.sort();
+
+        let mut busy_time = Duration::zero();
+        let mut current: Option<(DateTime<Utc>, DateTime<Utc>)> = None;
+        for (start, finish) in busy {
+            current = match current {
+                Some((s, f)) if start <= f => Some((s, f.max(finish))),
+                Some((s, f)) => {
+                    busy_time += elapsed(s, f);
+                    Some((start, finish))
+                }
+                None => Some((start, finish)),
+            };
+        }
+        if let Some((s, f)) = current {
+            busy_time += elapsed(s, f);
+        }
+
+        (elapsed(span_start, span_finish) - busy_time)
+            .try_into()
+            .ok()
+    }
+
+    /// Finds the personnel assigned to more work than they can do at the same time.
+    /// A person is over-allocated when the tasks they work on at some point in time add up
+    /// to more than their full capacity, summing the units of the assignments.
+    /// A task finishing exactly when another one starts doesn't overlap with it, and tasks
+    /// without a start and a finish are ignored.
+    /// Personnel and non consumable materials are over-allocated as well when they are used
+    /// outside of their [availability windows](Resource::availability).
+    ///
+    /// # Returns
+    ///
+    /// The index of every over-allocated resource, with the sorted indices of the tasks that
+    /// overlap when the resource is over-allocated, or that use it when it's unavailable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::{person::Person, project::Project, resources::Resource, task::Task};
+    ///
+    /// let start = Utc::now();
+    /// let mut project = Project::new("World domination");
+    /// project.add_resource(Resource::Personnel {
+    ///     person: Person::new("Sebastiano", "Giordano").unwrap(),
+    ///     hourly_rate: None,
+    ///     availability: Vec::new(),
+    /// });
+    /// for (name, units) in [("Find a crowbar", 0.5), ("Find a stimpack", 0.5), ("Find a map", 1.0)] {
+    ///     let mut task = Task::new(name);
+    ///     task.edit_start(start).unwrap();
+    ///     task.edit_duration(Duration::hours(2).try_into().unwrap());
+    ///     task.assign_resource(0, units).unwrap();
+    ///     project.add_task(task);
+    /// }
+    /// assert_eq!(project.overallocations(), vec![(0, vec![0, 1, 2])]);
+    ///
+    /// project.task_mut(2).unwrap().edit_start(start + Duration::hours(2)).unwrap();
+    /// assert!(project.overallocations().is_empty());
+    /// ```
+    pub fn overallocations(&self) -> Vec<(usize, Vec<usize>)> {
+        let unavailable = self.unavailable_uses();
+        self.resources
+            .iter()
+            .enumerate()
+            .filter(|(_, resource)| {
+                matches!(
+                    resource,
+                    Resource::Personnel { .. } | Resource::Material(Material::NonConsumable(_))
+                )
+            })
+            .filter_map(|(i, resource)| {
+                // Only the capacity of personnel is limited.
+                let limited = matches!(resource, Resource::Personnel { .. });
+                let busy: Vec<(usize, DateTime<Utc>, DateTime<Utc>, u64)> = self
+                    .tasks_assigned_to(i)
+                    .into_iter()
+                    .filter(|_| limited)
+                    .filter_map(|t| {
+                        let task = self.task(t)?;
+                        let (start, finish) = (task.start()?, task.finish()?);
+                        let units = thousandths(units_of(task, i)?);
+                        (start < finish).then_some((t, start, finish, units))
+                    })
+                    .collect();
+
+                let mut conflicting: HashSet<usize> = unavailable
+                    .iter()
+                    .filter(|(r, _)| *r == i)
+                    .map(|(_, t)| *t)
+                    .collect();
+                // The load only grows when a task starts, so it's enough to check every start.
+                for &(_, start, _, _) in &busy {
+                    let running: Vec<_> = busy
+                        .iter()
+                        .filter(|(_, s, f, _)| *s <= start && start < *f)
+                        .collect();
+                    if running.iter().map(|(_, _, _, units)| units).sum::<u64>() > 1000 {
+                        conflicting.extend(running.iter().map(|(t, _, _, _)| *t));
+                    }
+                }
+
+                let mut conflicting: Vec<usize> = conflicting.into_iter().collect();
+                conflicting.sort_unstable();
+                (!conflicting.is_empty()).then_some((i, conflicting))
+            })
+            .collect()
+    }
+
+    /// Computes how long the project would take if every person could only work on one
+    /// task at a time, without changing the project.
+    ///
+    /// Tasks are scheduled greedily in dependency order: each one starts as soon as both the
+    /// relationships with its predecessors and the personnel it uses allow. Like in
+    /// [`Project::overallocations`], only the capacity of personnel is limited: materials
+    /// never delay a task. The result is never shorter than the critical path length.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any task has no duration, or if the result exceeds the maximum duration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::{
+    ///     person::Person,
+    ///     project::Project,
+    ///     resources::{Material, NonConsumable, Resource},
+    ///     task::Task,
+    /// };
+    ///
+    /// let mut project = Project::new("World domination");
+    /// let person = project.add_resource(Resource::Personnel {
+    ///     person: Person::new("Sebastiano", "Giordano").unwrap(),
+    ///     hourly_rate: None,
+    ///     availability: Vec::new(),
+    /// });
+    /// let map = project.add_resource(Resource::Material(Material::NonConsumable(
+    ///     NonConsumable::new("Map"),
+    /// )));
+    /// for name in ["Find a crowbar", "Find a stimpack"] {
+    ///     let mut task = Task::new(name);
+    ///     task.edit_duration(Duration::hours(2).try_into().unwrap());
+    ///     task.add_resource(map);
+    ///     project.add_task(task);
+    /// }
+    ///
+    /// // Sharing the map doesn't delay anything.
+    /// assert_eq!(project.levelled_makespan().unwrap().num_hours(), 2);
+    ///
+    /// // The tasks are independent, but the same person has to do both.
+    /// for task in 0..2 {
+    ///     project.task_mut(task).unwrap().add_resource(person);
+    /// }
+    /// assert_eq!(project.levelled_makespan().unwrap().num_hours(), 4);
+    /// ```
+    pub fn levelled_makespan(&self) -> anyhow::Result<PositiveDuration> {
+        let order = self.tasks_topological()?;
+        let durations = self.task_durations()?;
+
+        let mut starts = vec![Duration::zero(); durations.len()];
+        let mut finishes = vec![Duration::zero(); durations.len()];
+        let mut available_from: HashMap<usize, Duration> = HashMap::new();
+
+        for &i in &order {
+            let resources: Vec<usize> = self
+                .resources_of_task(i)
+                .into_iter()
+                .filter(|&r| matches!(self.resources[r], Resource::Personnel { .. }))
+                .collect();
+
+            let start = resources.iter().filter_map(|r| available_from.get(r)).fold(
+                self.earliest_start(i, &starts, &finishes, &durations),
+                |start, &available| start.max(available),
+            );
+            starts[i] = start;
+            finishes[i] = start + durations[i];
+            for r in resources {
+                available_from.insert(r, finishes[i]);
+            }
+        }
+
+        finishes
+            .into_iter()
+            .max()
+            .unwrap_or_else(Duration::zero)
+            .try_into()
+            .context("The levelled schedule exceeds the maximum duration")
+    }
+
+    /// Returns the indices of the tasks that use the resource with index `resource_index`.
+    ///
+    /// A resource assigned to a parent task is often meant to be shared by the work
+    /// breaking it down. When `include_subtasks` is set, the subtasks of every task the
+    /// resource is directly assigned to are included too, recursively, even if the resource
+    /// isn't assigned to them. Otherwise, only the direct assignments are returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `resource_index` - The index of the resource.
+    /// * `include_subtasks` - Whether subtasks inherit the resources of their parents.
+    ///
+    /// # Returns
+    ///
+    /// The sorted indices of the tasks, empty if the resource doesn't exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{person::Person, project::Project, resources::Resource, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// let person = project.add_resource(Resource::Personnel {
+    ///     person: Person::new("Sebastiano", "Giordano").unwrap(),
+    ///     hourly_rate: None,
+    ///     availability: Vec::new(),
+    /// });
+    /// let mut parent = Task::new("Get equipped");
+    /// parent.add_resource(person);
+    /// project.add_task(parent);
+    /// project.add_task(Task::new("Find a crowbar"));
+    /// project.add_subtask(0, 1);
+    ///
+    /// assert_eq!(project.effective_resource_tasks(0, false), vec![0]);
+    /// assert_eq!(project.effective_resource_tasks(0, true), vec![0, 1]);
+    /// ```
+    pub fn effective_resource_tasks(
+        &self,
+        resource_index: usize,
+        include_subtasks: bool,
+    ) -> Vec<usize> {
+        let mut tasks: HashSet<usize> = HashSet::new();
+        let mut pending = self.tasks_assigned_to(resource_index);
+
+        while let Some(i) = pending.pop() {
+            if tasks.insert(i) && include_subtasks {
+                pending.extend(self.subtasks(i));
+            }
+        }
+
+        let mut tasks: Vec<usize> = tasks.into_iter().collect();
+        tasks.sort_unstable();
+        tasks
+    }
+
+    /// Computes what the project costs. Materials cost their cost per unit times their quantity.
+    /// Personnel, and non consumable materials with an hourly rate, also cost their hourly rate
+    /// for every hour spent on the tasks they are assigned to, as given by the task durations.
+    /// The time based cost of each resource is rounded up to the hundredth. Tasks without a
+    /// duration don't add to the cost.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the costs are in different currencies, or if the total is too large.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::{
+    ///     money::Money,
+    ///     person::Person,
+    ///     project::Project,
+    ///     resources::{Material, Resource},
+    ///     task::Task,
+    /// };
+    ///
+    /// let mut project = Project::new("World domination");
+    /// let mut crowbars = Material::new("Crowbar");
+    /// crowbars.update_quantity(2);
+    /// crowbars.update_cost_per_unit(20);
+    /// project.add_resource(Resource::Material(crowbars));
+    /// let personnel = project.add_resource(Resource::Personnel {
+    ///     person: Person::new("Margherita", "Hack").unwrap(),
+    ///     hourly_rate: Some(Money::from(15)),
+    ///     availability: Vec::new(),
+    /// });
+    ///
+    /// let mut task = Task::new("Find a crowbar");
+    /// task.edit_duration(Duration::hours(3).try_into().unwrap());
+    /// task.add_resource(personnel);
+    /// project.add_task(task);
+    ///
+    /// assert_eq!(project.total_cost().unwrap(), Money::from(2 * 20 + 3 * 15));
+    /// ```
+    pub fn total_cost(&self) -> anyhow::Result<Money> {
+        let mut total = Money::zero(Currency::default());
+        for (i, resource) in self.resources.iter().enumerate() {
+            if let Resource::Material(material) = resource
+                && let Some(cost_per_unit) = material.cost_per_unit()
+            {
+                let quantity = material.quantity().unwrap_or_default();
+                total = total.checked_add(cost_per_unit.checked_mul(quantity.into())?)?;
+            }
+            let Some(hourly_rate) = hourly_rate(resource) else {
+                continue;
+            };
+            // Minutes spent on the tasks, in thousandths of the resource capacity.
+            let minutes: u64 = self
+                .tasks_assigned_to(i)
+                .into_iter()
+                .filter_map(|task| {
+                    let task = self.task(task)?;
+                    let minutes = u64::try_from(task.duration()?.num_minutes()).ok()?;
+                    Some(minutes * thousandths(units_of(task, i)?))
+                })
+                .sum();
+            total = total.checked_add(time_cost(hourly_rate, minutes)?)?;
+        }
+        Ok(total)
+    }
+
+    /// Sets the duration of a task from its effort and the number of people assigned to it,
+    /// assuming they share the work evenly. The duration is rounded up to the whole minute.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_index` - The index of the task.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the task doesn't exist, has no effort, or has no personnel assigned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::{person::Person, project::Project, resources::Resource, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// let person = project.add_resource(Resource::Personnel {
+    ///     person: Person::new("Sebastiano", "Giordano").unwrap(),
+    ///     hourly_rate: None,
+    ///     availability: Vec::new(),
+    /// });
+    /// let mut task = Task::new("Find a crowbar");
+    /// task.edit_effort(Duration::hours(3).try_into().unwrap());
+    /// task.add_resource(person);
+    /// project.add_task(task);
+    /// project.infer_duration_from_effort(0).unwrap();
+    /// assert_eq!(project.task(0).unwrap().duration().unwrap().num_hours(), 3);
+    /// ```
+    pub fn infer_duration_from_effort(&mut self, task_index: usize) -> anyhow::Result<()> {
+        let task = self
+            .task(task_index)
+            .with_context(|| format!("Task {task_index} doesn't exist"))?;
+        let effort = task
+            .effort()
+            .with_context(|| format!("Task {task_index} has no effort"))?;
+        // The people working on the task, in thousandths of a full time person.
+        let headcount: u64 = self
+            .staffing(task)
+            .into_iter()
+            .filter(|(r, _)| matches!(r, Resource::Personnel { .. }))
+            .map(|(_, units)| thousandths(units))
+            .sum();
+        if headcount == 0 {
+            bail!("Task {task_index} has no personnel assigned");
+        }
+
+        let seconds = u64::try_from(effort.num_seconds()).context("The effort is negative")?;
+        let minutes = (seconds * 1000).div_ceil(headcount * 60);
+        let duration = i64::try_from(minutes)
+            .ok()
+            .and_then(Duration::try_minutes)
+            .and_then(|duration| duration.try_into().ok())
+            .context("The inferred duration exceeds the maximum duration")?;
+        if let Some(task) = self.task_mut(task_index) {
+            task.edit_duration(duration);
+        }
+        Ok(())
+    }
+
+    /// Sums `leaf` over the subtasks of the task with index `task_index`, recursively.
+    /// `visiting` holds the tasks whose subtasks are being summed, to detect cycles.
+    fn roll_up<T: Add<Output = T>>(
+        &self,
+        task_index: usize,
+        visiting: &mut Vec<usize>,
+        leaf: &impl Fn(&Task) -> Option<T>,
+    ) -> Option<T> {
+        self.try_roll_up(task_index, visiting, leaf, &|a, b| Some(a + b))
+    }
+
+    /// Like [`Project::roll_up`], for values whose sum can fail, like amounts of money.
+    fn try_roll_up<T>(
+        &self,
+        task_index: usize,
+        visiting: &mut Vec<usize>,
+        leaf: &impl Fn(&Task) -> Option<T>,
+        add: &impl Fn(T, T) -> Option<T>,
+    ) -> Option<T> {
+        let task = self.task(task_index)?;
+        if visiting.contains(&task_index) {
+            return None;
+        }
+        let subtasks = self.subtasks(task_index);
+        if subtasks.is_empty() {
+            return leaf(task);
+        }
+
+        visiting.push(task_index);
+        let mut total: Option<T> = None;
+        for subtask in subtasks {
+            let value = self.try_roll_up(subtask, visiting, leaf, add)?;
+            total = Some(match total {
+                Some(total) => add(total, value)?,
+                None => value,
+            });
+        }
+        visiting.pop();
+        total
+    }
+
+    /// Finds the dated tasks that use a resource outside of its availability windows.
+    ///
+    /// # Returns
+    ///
+    /// The index of the resource and the index of the task, for every such use, sorted.
+    pub(crate) fn unavailable_uses(&self) -> Vec<(usize, usize)> {
+        self.resources
+            .iter()
+            .enumerate()
+            .filter(|(_, resource)| !resource.availability().is_empty())
+            .flat_map(|(r, resource)| {
+                self.tasks_assigned_to(r).into_iter().filter_map(move |t| {
+                    let task = self.task(t)?;
+                    let (start, finish) = (task.start()?, task.finish()?);
+                    (!resource.is_available(start, finish)).then_some((r, t))
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the indices of the tasks the resource with index `resource_index` is assigned to.
+    fn tasks_assigned_to(&self, resource_index: usize) -> Vec<usize> {
+        if resource_index >= self.resources.len() {
+            return Vec::new();
+        }
+        self.tasks()
+            .enumerate()
+            .filter(|(_, task)| units_of(task, resource_index).is_some())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Returns the indices of the project resources assigned to the task with index `task_index`.
+    fn resources_of_task(&self, task_index: usize) -> Vec<usize> {
+        let Some(task) = self.task(task_index) else {
+            return Vec::new();
+        };
+        (task.assignments().iter())
+            .map(Assignment::resource_index)
+            .filter(|&r| r < self.resources.len())
+            .collect()
+    }
+
+    /// Returns the project resources assigned to a task, with the share of their capacity
+    /// they spend on it.
+    fn staffing<'a>(&'a self, task: &'a Task) -> Vec<(&'a Resource, f32)> {
+        (task.assignments().iter())
+            .filter_map(|assignment| {
+                let resource = self.resources.get(assignment.resource_index())?;
+                Some((resource, assignment.units()))
+            })
+            .collect()
+    }
+
+    /// Computes the cost of a task from the hourly rates of the resources working on it,
+    /// over its duration, rounded up to the hundredth. It's None if the task has no duration,
+    /// or if the costs are in different currencies or too large.
+    fn task_cost(&self, task: &Task) -> Option<Money> {
+        task.duration()?;
+        baseline::planned_cost(self, task).ok()
+    }
+
+    /// Adds a stakeholder to the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `stakeholder` - The stakeholder to add to the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{stakeholders::{Engagement, Level, Stakeholder}, project::Project, person::Person};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// let person = Person::new("Margherita", "Hack").unwrap();
+    /// project.add_stakeholder(Stakeholder::Individual {
+    ///   person,
+    ///   description: None,
+    ///   influence: Level::High,
+    ///   interest: Level::Medium,
+    ///   current_engagement: Engagement::Neutral,
+    ///   desired_engagement: Engagement::Neutral,
+    /// });
+    /// assert_eq!(project.stakeholders().len(), 1);
+    /// ```
+    pub fn add_stakeholder(&mut self, stakeholder: Stakeholder) {
+        self.stakeholders.push(stakeholder);
+    }
+
+    /// Adds a stakeholder to the project, while building it.
+    ///
+    /// # Arguments
+    ///
+    /// * `stakeholder` - The stakeholder to add to the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{stakeholders::{Engagement, Level, Stakeholder}, project::Project};
+    ///
+    /// let project = Project::new("World domination").with_stakeholder(Stakeholder::Organization {
+    ///     name: "Acme".to_owned(),
+    ///     description: None,
+    ///     influence: Level::High,
+    ///     interest: Level::Low,
+    ///     current_engagement: Engagement::Neutral,
+    ///     desired_engagement: Engagement::Neutral,
+    /// });
+    /// assert_eq!(project.stakeholders().len(), 1);
+    /// ```
+    pub fn with_stakeholder(mut self, stakeholder: Stakeholder) -> Self {
+        self.add_stakeholder(stakeholder);
+        self
+    }
+
+    /// Adds stakeholders to the project, after the existing ones, while building it.
+    ///
+    /// # Arguments
+    ///
+    /// * `stakeholders` - The stakeholders to add to the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{stakeholders::{Engagement, Level, Stakeholder}, project::Project};
+    ///
+    /// let organization = |name: &str| Stakeholder::Organization {
+    ///     name: name.to_owned(),
+    ///     description: None,
+    ///     influence: Level::default(),
+    ///     interest: Level::default(),
+    ///     current_engagement: Engagement::default(),
+    ///     desired_engagement: Engagement::default(),
+    /// };
+    /// let project = Project::new("World domination")
+    ///     .with_stakeholders(["Acme", "Umbrella"].map(organization));
+    /// assert_eq!(project.stakeholders().len(), 2);
+    /// ```
+    pub fn with_stakeholders(
+        mut self,
+        stakeholders: impl IntoIterator<Item = Stakeholder>,
+    ) -> Self {
+        self.stakeholders.extend(stakeholders);
+        self
+    }
+
+    /// Returns a reference to the list of stakeholders associated with the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{stakeholders::{Engagement, Level, Stakeholder}, project::Project, person::Person};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// let person = Person::new("Margherita", "Hack").unwrap();
+    /// project.add_stakeholder(Stakeholder::Individual {
+    ///   person,
+    ///   description: None,
+    ///   influence: Level::High,
+    ///   interest: Level::Medium,
+    ///   current_engagement: Engagement::Neutral,
+    ///   desired_engagement: Engagement::Neutral,
+    /// });
+    /// assert_eq!(project.stakeholders().len(), 1);
+    /// ```
+    pub fn stakeholders(&self) -> &[Stakeholder] {
+        &self.stakeholders
+    }
+
+    /// Removes a stakeholder from the project, along with their roles in the tasks and the
+    /// ownership of their risks.
+    /// The stakeholders after it move back by one, in the tasks too.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the stakeholder.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stakeholder index is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{stakeholders::{Engagement, Level, Stakeholder}, project::Project};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_stakeholder(Stakeholder::Organization {
+    ///     name: "Acme".to_owned(),
+    ///     description: None,
+    ///     influence: Level::High,
+    ///     interest: Level::Low,
+    ///     current_engagement: Engagement::Neutral,
+    ///     desired_engagement: Engagement::Neutral,
+    /// });
+    ///
+    /// assert!(project.rm_stakeholder(0).is_ok());
+    /// assert!(project.stakeholders().is_empty());
+    /// assert!(project.rm_stakeholder(0).is_err());
+    /// ```
+    pub fn rm_stakeholder(&mut self, index: usize) -> anyhow::Result<()> {
+        if index >= self.stakeholders.len() {
+            bail!("Tried removing the non existing stakeholder {index}");
+        }
+        self.stakeholders.remove(index);
+        let reindex = |i: usize| match i.cmp(&index) {
+            Ordering::Less => Some(i),
+            Ordering::Equal => None,
+            Ordering::Greater => Some(i - 1),
+        };
+        for task in self.tasks_mut() {
+            task.reindex_responsibilities(reindex);
+        }
+        for risk in &mut self.risks {
+            risk.reindex_owner(reindex);
+        }
+        Ok(())
+    }
+
+    /// Get a mutable reference to a stakeholder of the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the stakeholder.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{stakeholders::{Engagement, Level, Stakeholder}, project::Project};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_stakeholder(Stakeholder::Organization {
+    ///     name: "Acme".to_owned(),
+    ///     description: None,
+    ///     influence: Level::High,
+    ///     interest: Level::Low,
+    ///     current_engagement: Engagement::Neutral,
+    ///     desired_engagement: Engagement::Neutral,
+    /// });
+    ///
+    /// if let Some(Stakeholder::Organization { description, .. }) = project.stakeholder_mut(0) {
+    ///     *description = Some("They sell crowbars".to_owned());
+    /// }
+    /// assert!(project.stakeholder_mut(1).is_none());
+    /// ```
+    pub fn stakeholder_mut(&mut self, index: usize) -> Option<&mut Stakeholder> {
+        self.stakeholders.get_mut(index)
+    }
+
+    /// Buckets the stakeholders by influence and interest, for a power/interest grid.
+    /// The grid is indexed first by influence and then by interest, both from
+    /// [`Level::Low`](crate::stakeholders::Level::Low) to
+    /// [`Level::High`](crate::stakeholders::Level::High).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{stakeholders::{Engagement, Level, Stakeholder}, project::Project};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_stakeholder(Stakeholder::Organization {
+    ///     name: "Acme".to_owned(),
+    ///     description: None,
+    ///     influence: Level::High,
+    ///     interest: Level::Low,
+    ///     current_engagement: Engagement::Neutral,
+    ///     desired_engagement: Engagement::Neutral,
+    /// });
+    ///
+    /// let grid = project.stakeholder_grid();
+    /// assert_eq!(grid[Level::High as usize][Level::Low as usize].len(), 1);
+    /// assert!(grid[Level::Low as usize][Level::High as usize].is_empty());
+    /// ```
+    pub fn stakeholder_grid(&self) -> [[Vec<&Stakeholder>; 3]; 3] {
+        let mut grid: [[Vec<&Stakeholder>; 3]; 3] = Default::default();
+        for stakeholder in &self.stakeholders {
+            grid[stakeholder.influence() as usize][stakeholder.interest() as usize]
+                .push(stakeholder);
+        }
+        grid
+    }
+
+    /// Lists the stakeholders whose current engagement differs from the desired one,
+    /// for a stakeholder engagement assessment matrix.
+    ///
+    /// # Returns
+    ///
+    /// The index of each of those stakeholders, with their current and desired engagement.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{stakeholders::{Engagement, Level, Stakeholder}, project::Project};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_stakeholder(Stakeholder::Organization {
+    ///     name: "Acme".to_owned(),
+    ///     description: None,
+    ///     influence: Level::High,
+    ///     interest: Level::Low,
+    ///     current_engagement: Engagement::Supportive,
+    ///     desired_engagement: Engagement::Supportive,
+    /// });
+    /// project.add_stakeholder(Stakeholder::Organization {
+    ///     name: "Umbrella".to_owned(),
+    ///     description: None,
+    ///     influence: Level::High,
+    ///     interest: Level::High,
+    ///     current_engagement: Engagement::Resistant,
+    ///     desired_engagement: Engagement::Neutral,
+    /// });
+    ///
+    /// assert_eq!(
+    ///     project.engagement_gaps(),
+    ///     vec![(1, Engagement::Resistant, Engagement::Neutral)]
+    /// );
+    /// ```
+    pub fn engagement_gaps(&self) -> Vec<(usize, Engagement, Engagement)> {
+        self.stakeholders
+            .iter()
+            .enumerate()
+            .filter(|(_, stakeholder)| {
+                stakeholder.current_engagement() != stakeholder.desired_engagement()
+            })
+            .map(|(index, stakeholder)| {
+                (
+                    index,
+                    stakeholder.current_engagement(),
+                    stakeholder.desired_engagement(),
+                )
+            })
+            .collect()
+    }
+
+    /// Adds a risk to the risk register of the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `risk` - The risk to add to the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, risk::Risk, stakeholders::Level};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_risk(Risk::new("The death ray overheats", Level::Medium, Level::High));
+    /// assert_eq!(project.risks().len(), 1);
+    /// ```
+    pub fn add_risk(&mut self, risk: Risk) {
+        self.risks.push(risk);
+    }
+
+    /// Returns the risk register of the project, in the order the risks were added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, risk::Risk, stakeholders::Level};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// assert!(project.risks().is_empty());
+    /// project.add_risk(Risk::new("The death ray overheats", Level::Medium, Level::High));
+    /// assert_eq!(project.risks()[0].description(), "The death ray overheats");
+    /// ```
+    pub fn risks(&self) -> &[Risk] {
+        &self.risks
+    }
+
+    /// Removes a risk from the risk register of the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the risk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the risk index is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, risk::Risk, stakeholders::Level};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_risk(Risk::new("The death ray overheats", Level::Medium, Level::High));
+    ///
+    /// assert!(project.rm_risk(0).is_ok());
+    /// assert!(project.risks().is_empty());
+    /// assert!(project.rm_risk(0).is_err());
+    /// ```
+    pub fn rm_risk(&mut self, index: usize) -> anyhow::Result<()> {
+        if index >= self.risks.len() {
+            bail!("Tried removing the non existing risk {index}");
+        }
+        self.risks.remove(index);
+        Ok(())
+    }
+
+    /// Returns the risks of the project, from the most to the least severe.
+    /// Risks that are equally severe keep the order they were added in.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, risk::Risk, stakeholders::Level};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_risk(Risk::new("Minions go on strike", Level::Low, Level::Medium));
+    /// project.add_risk(Risk::new("The death ray overheats", Level::Medium, Level::High));
+    ///
+    /// let risks = project.risks_by_severity();
+    /// assert_eq!(risks[0].description(), "The death ray overheats");
+    /// assert_eq!(risks[1].description(), "Minions go on strike");
+    /// ```
+    pub fn risks_by_severity(&self) -> Vec<&Risk> {
+        let mut risks: Vec<&Risk> = self.risks.iter().collect();
+        risks.sort_by_key(|risk| std::cmp::Reverse(risk.severity()));
+        risks
+    }
+}
+
+/// The maximum amount of pairs returned by [`Project::independent_task_pairs`].
+pub const MAX_INDEPENDENT_PAIRS: usize = 1000;
+
+/// Represents an error that can occur when trying to convert `Material` resources variants to another variant.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ResourceConversionError {
+    /// Used when trying to convert a resource with an index out of bounds.
+    #[error("The resource with the specified index wasn't found")]
+    ResourceNotFound,
+    /// Used when trying to convert a resource that's not a material, for example personnel.
+    #[error("Tried to convert a resource that's not a material")]
+    ConversionNotPossible,
+}
+
+/// Moves a date by the given offset, over working time only if there's a calendar.
+fn shift(
+    calendar: Option<&Calendar>,
+    date: DateTime<Utc>,
+    offset: Duration,
+) -> anyhow::Result<DateTime<Utc>> {
+    match calendar {
+        Some(calendar) => calendar.add_working_time(date, offset),
+        None => date.checked_add_signed(offset),
+    }
+    .context("The date would be out of range")
+}
+
+/// A date that moves over working time, when there's a calendar, so that relationships can
+/// be resolved on dates the same way as on offsets.
+#[derive(Clone, Copy)]
+struct WorkingDate<'a> {
+    /// The date itself.
+    date: DateTime<Utc>,
+    /// The calendar the date moves on.
+    calendar: Option<&'a Calendar>,
+}
+
+impl Add<Duration> for WorkingDate<'_> {
+    type Output = Self;
+
+    /// Saturates at the earliest or latest representable date.
+    fn add(self, offset: Duration) -> Self {
+        let date =
+            shift(self.calendar, self.date, offset).unwrap_or(if offset < Duration::zero() {
+                DateTime::<Utc>::MIN_UTC
+            } else {
+                DateTime::<Utc>::MAX_UTC
+            });
+        WorkingDate { date, ..self }
+    }
+}
+
+impl Sub<Duration> for WorkingDate<'_> {
+    type Output = Self;
+
+    fn sub(self, offset: Duration) -> Self {
+        self + -offset
+    }
+}
+
+/// Returns the hourly rate of personnel and non consumable materials.
+fn hourly_rate(resource: &Resource) -> Option<Money> {
+    match resource {
+        Resource::Personnel { hourly_rate, .. } => *hourly_rate,
+        Resource::Material(Material::NonConsumable(material)) => material.hourly_rate(),
+        Resource::Material(Material::Consumable(_)) => None,
+    }
+}
+
+/// Computes what working for the given minutes, in thousandths of the resource capacity,
+/// costs at an hourly rate, rounded up to the hundredth.
+fn time_cost(hourly_rate: Money, minutes: u64) -> Result<Money, MoneyError> {
+    let divisor = 60 * 1000;
+    let cost = i128::from(hourly_rate.amount()) * i128::from(minutes);
+    let rounded_up = cost.div_euclid(divisor) + i128::from(cost.rem_euclid(divisor) != 0);
+    let amount = i64::try_from(rounded_up).map_err(|_| MoneyError::Overflow)?;
+    Ok(Money::new(amount, hourly_rate.currency()))
+}
+
+/// Returns the share of its capacity the resource with index `resource_index` spends on
+/// `task`, if it's assigned to it.
+fn units_of(task: &Task, resource_index: usize) -> Option<f32> {
+    (task.assignments().iter())
+        .find(|assignment| assignment.resource_index() == resource_index)
+        .map(Assignment::units)
+}
+
+/// Converts the units of an assignment to thousandths, to compute costs with integers.
+fn thousandths(units: f32) -> u64 {
+    (f64::from(units) * 1000.0).round() as u64
+}
+
+/// Describes the cycle that a relationship from `predecessor` to `successor` would close,
+/// like "2 -> 0 -> 1 -> 2", finding the shortest path from `successor` back to `predecessor`.
+fn cycle_error(
+    tasks: &Dag<Task, Dependency, usize>,
+    predecessor: usize,
+    successor: usize,
+) -> String {
+    let mut parents: HashMap<usize, usize> = HashMap::new();
+    let mut pending = VecDeque::from([successor]);
+    while let Some(current) = pending.pop_front() {
+        if current == predecessor {
+            break;
+        }
+        for next in tasks.neighbors_directed(current.into(), Direction::Outgoing) {
+            let next = next.index();
+            if next != successor && !parents.contains_key(&next) {
+                parents.insert(next, current);
+                pending.push_back(next);
+            }
+        }
+    }
+
+    let mut path = vec![successor];
+    let mut current = predecessor;
+    while current != successor {
+        path.push(current);
+        let Some(&parent) = parents.get(&current) else {
+            break;
+        };
+        current = parent;
+    }
+    path[1..].reverse();
+    path.push(successor);
+    let path: Vec<String> = path.iter().map(ToString::to_string).collect();
+    format!(
+        "A cycle was detected between tasks {predecessor} and {successor}: {}",
+        path.join(" -> ")
+    )
+}
+
+#[cfg(test)]
+/// Utilities to test `[Project]`
+pub mod test_utils {
+    use proptest::{collection, prelude::*};
+
+    use crate::task::{Task, test_utils::task_strategy};
+
+    use super::{Project, TimeRelationship};
+
+    const MAX_TASKS: usize = 100;
+    const MIN_TASKS: usize = 5;
+
+    /// Generates a random task relationship kind.
+    pub fn task_relationship_strategy() -> impl Strategy<Value = TimeRelationship> {
+        prop_oneof![
+            Just(TimeRelationship::StartToFinish),
+            Just(TimeRelationship::StartToStart),
+            Just(TimeRelationship::FinishToFinish),
+            Just(TimeRelationship::FinishToStart),
+        ]
     }
 
     /// Generate a random amount of randomly generated `[Tasks]`, between `[MIN_TASKS]` and `[MAX_TASKS]`.
@@ -871,266 +3687,1353 @@ pub mod test_utils {
         collection::vec(task_strategy(), MIN_TASKS..MAX_TASKS)
     }
 
-    /// Generate a random `[Project]` where every node is connected to the previous one.
-    pub fn project_graph_strategy() -> impl Strategy<Value = Project> {
-        (".*", tasks_strategy()).prop_map(|(n, tasks)| {
-            let indices = 0..tasks.len();
-            let mut project = Project::builder().name(n).tasks(tasks).build();
+    /// Generate a random `[Project]` where every node is connected to the previous one.
+    ///
+    /// # Panics
+    /// Panics if connecting the tasks fails, which would be a bug.
+    pub fn project_graph_strategy() -> impl Strategy<Value = Project> {
+        (".*", tasks_strategy()).prop_map(|(n, tasks)| {
+            let indices = 0..tasks.len();
+            let mut project = Project::builder().name(n).tasks(tasks).build();
+
+            let mut previous = None;
+            indices.for_each(|current| {
+                if let Some(prev) = previous {
+                    project.update_successors(prev, &[current]).unwrap();
+                }
+                previous = Some(current);
+            });
+            project
+        })
+    }
+
+    /// Generate a random `[Project]` with `[Task]`s, but no predecessors/successors relationships.
+    pub fn project_strategy() -> impl Strategy<Value = Project> {
+        (".*", tasks_strategy())
+            .prop_map(|(n, tasks)| Project::builder().name(n).tasks(tasks).build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use proptest::prelude::*;
+    use rand::{Rng, rng};
+
+    use crate::{
+        calendar::Calendar,
+        money::{Currency, Money},
+        person::Person,
+        project::{
+            Phase, Project, ResourceConversionError, TimeRelationship,
+            test_utils::{project_graph_strategy, project_strategy},
+        },
+        resources::{Consumable, Material, NonConsumable, Resource},
+        task::{Task, TaskConstraint, TaskStatus},
+        validation::ValidationIssue,
+    };
+    proptest! {
+        #[test]
+        fn update_predecessors_rejects_circular_graphs(mut project in project_graph_strategy()) {
+            assert!(project.update_predecessors(0, &[project.tasks().count() - 1]).is_err());
+        }
+
+        #[test]
+        fn update_successors_rejects_circular_graphs(mut project in project_graph_strategy()) {
+            assert!(project.update_successors(project.tasks().count() - 1, &[0] ).is_err());
+        }
+
+        #[test]
+        fn tasks_topological_follows_the_chain(project in project_graph_strategy()) {
+            let count = project.tasks().count();
+            assert_eq!(project.tasks_topological().unwrap(), (0..count).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn update_predecessors_rejects_non_existent_indices(mut project in project_strategy()) {
+            let count: usize = project.tasks().count();
+
+            assert!(project.update_predecessors(0, &[count]).is_err())
+        }
+
+        #[test]
+        fn update_successors_rejects_non_existent_indices(mut project in project_strategy()) {
+            let count: usize = project.tasks().count();
+
+            assert!(project.update_successors(0, &[count]).is_err())
+        }
+
+        #[test]
+        fn update_predecessors_removes_them_if_input_is_empty(mut project in project_strategy()) {
+            let mut rng = rng();
+            let task_index1 = rng.random_range(0..project.tasks().count());
+            let mut task_index2 = task_index1;
+
+            while task_index2 == task_index1 {
+                task_index2 = rng.random_range(0..project.tasks().count());
+            }
+
+            project.update_predecessors(task_index1, &[task_index2]).unwrap();
+            project.update_predecessors(task_index1, &[]).unwrap();
+
+            assert_eq!(project.predecessors(task_index1).count(), 0);
+        }
+
+        #[test]
+        fn update_predecessors_removes_indices_not_present_in_input(mut project in project_strategy()) {
+            let mut rng = rng();
+            let task_index1 = rng.random_range(0..project.tasks().count());
+            let mut task_index2 = task_index1;
+            let mut task_index3 = task_index1;
+
+            while task_index2 == task_index1 {
+                task_index2 = rng.random_range(0..project.tasks().count());
+            }
+            while task_index3 == task_index1 || task_index3 == task_index2 {
+                task_index3 = rng.random_range(0..project.tasks().count());
+            }
+
+            project.update_predecessors(task_index1, &[task_index2, task_index3]).unwrap();
+            project.update_predecessors(task_index1, &[task_index2]).unwrap();
+
+            let mut predecessors = project.predecessors(task_index1);
+            assert_eq!(predecessors.next(), project.task(task_index2));
+            assert!(predecessors.next().is_none());
+        }
+
+        #[test]
+        fn update_predecessors_works(mut project in project_strategy()) {
+            let mut rng = rng();
+            let task_index1 = rng.random_range(0..project.tasks().count());
+            let mut task_index2 = task_index1;
+
+            while task_index2 == task_index1 {
+                task_index2 = rng.random_range(0..project.tasks().count());
+            }
+
+            project.update_predecessors(task_index1, &[task_index2]).unwrap();
+
+            let mut predecessors = project.predecessors(task_index1);
+            assert_eq!(project.predecessors(task_index1).count(), 1);
+            assert_eq!(predecessors.next(), project.task(task_index2));
+        }
+
+        #[test]
+        fn update_successors_works(mut project in project_strategy()) {
+            let mut rng = rng();
+            let task_index1 = rng.random_range(0..project.tasks().count());
+            let mut task_index2 = task_index1;
+
+            while task_index2 == task_index1 {
+                task_index2 = rng.random_range(0..project.tasks().count());
+            }
+
+            project.update_successors(task_index1, &[task_index2]).unwrap();
+
+            let mut successors = project.successors(task_index1);
+            assert_eq!(successors.next(), project.task(task_index2));
+            assert!(successors.next().is_none());
+        }
+
+        #[test]
+        fn update_successors_removes_them_if_input_is_empty(mut project in project_strategy()) {
+            let mut rng = rng();
+            let task_index1 = rng.random_range(0..project.tasks().count());
+            let mut task_index2 = task_index1;
+
+            while task_index2 == task_index1 {
+                task_index2 = rng.random_range(0..project.tasks().count());
+            }
+
+            project.update_successors(task_index1, &[task_index2]).unwrap();
+            project.update_successors(task_index1, &[]).unwrap();
+
+            assert_eq!(project.successors(task_index1).count(), 0);
+        }
+
+        #[test]
+        fn update_successors_removes_indices_not_present_in_input(mut project in project_strategy()) {
+            let mut rng = rng();
+            let task_index1 = rng.random_range(0..project.tasks().count());
+            let mut task_index2 = task_index1;
+            let mut task_index3 = task_index1;
+
+            while task_index2 == task_index1 {
+                task_index2 = rng.random_range(0..project.tasks().count());
+            }
+            while task_index3 == task_index1 || task_index3 == task_index2 {
+                task_index3 = rng.random_range(0..project.tasks().count());
+            }
+
+            project.update_successors(task_index1, &[task_index2, task_index3]).unwrap();
+            project.update_successors(task_index1, &[task_index2]).unwrap();
+
+            let mut successors = project.successors(task_index1);
+            assert_eq!(successors.next(), project.task(task_index2));
+            assert!(successors.next().is_none());
+        }
+    }
+
+    #[test]
+    fn res_into_consumable_returns_the_correct_errors() {
+        let mut project = Project::new("World domination");
 
-            let mut previous = None;
-            indices.for_each(|current| {
-                if let Some(prev) = previous {
-                    project.update_successors(prev, &[current]).unwrap();
-                }
-                previous = Some(current);
-            });
-            project
-        })
+        project.add_resource(Resource::Personnel {
+            person: Person::new("Sebastiano", "Giordano").unwrap(),
+            hourly_rate: None,
+            availability: Vec::new(),
+        });
+
+        // The correct error when trying to convert a reasource that's not a `Material`.
+        assert_eq!(
+            project.res_into_consumable(0),
+            Err(ResourceConversionError::ConversionNotPossible)
+        );
+
+        // The correct error when trying to convert a reasource that does not exist.
+        assert_eq!(
+            project.res_into_consumable(1),
+            Err(ResourceConversionError::ResourceNotFound)
+        );
+
+        // Doesn't return an error when trying to convert a `Consumable` into a `Consumable`.
+        project.add_resource(Resource::Material(Material::Consumable(Consumable::new(
+            "Stimpack",
+        ))));
+
+        assert!(project.res_into_consumable(1).is_ok());
+
+        // Doesn't change the type of the resource when not needed.
+        if let Resource::Material(Material::Consumable(_)) = project.resources()[1] {
+        } else {
+            panic!("It changed the resource type");
+        }
+
+        // It changes it when needed.
+        project.add_resource(Resource::Material(Material::NonConsumable(
+            NonConsumable::new("Crowbar"),
+        )));
+        project.res_into_consumable(2).unwrap();
+        if let Resource::Material(Material::Consumable(_)) = project.resources()[2] {
+        } else {
+            panic!("It didn't change the resource type");
+        }
     }
 
-    /// Generate a random `[Project]` with `[Task]`s, but no predecessors/successors relationships.
-    pub fn project_strategy() -> impl Strategy<Value = Project> {
-        (".*", tasks_strategy())
-            .prop_map(|(n, tasks)| Project::builder().name(n).tasks(tasks).build())
+    #[test]
+    fn res_into_nonconsumable_returns_the_correct_errors() {
+        let mut project = Project::new("World domination");
+
+        project.add_resource(Resource::Personnel {
+            person: Person::new("Sebastiano", "Giordano").unwrap(),
+            hourly_rate: None,
+            availability: Vec::new(),
+        });
+
+        // The correct error when trying to convert a resource that's not a `Material`.
+        assert_eq!(
+            project.res_into_nonconsumable(0),
+            Err(ResourceConversionError::ConversionNotPossible)
+        );
+
+        // The correct error when trying to convert a resource that does not exist.
+        assert_eq!(
+            project.res_into_nonconsumable(1),
+            Err(ResourceConversionError::ResourceNotFound)
+        );
+
+        // Doesn't return an error when trying to convert a `NonConsumable` into a `NonConsumable`.
+        project.add_resource(Resource::Material(Material::NonConsumable(
+            NonConsumable::new("Crowbar"),
+        )));
+
+        assert!(project.res_into_nonconsumable(1).is_ok());
+
+        // Doesn't change the type of the resource when not needed.
+        if let Resource::Material(Material::NonConsumable(_)) = project.resources()[1] {
+        } else {
+            panic!("It changed the resource type");
+        }
+
+        // It changes it when needed.
+        project.add_resource(Resource::Material(Material::Consumable(Consumable::new(
+            "Stimpack",
+        ))));
+        project.res_into_nonconsumable(2).unwrap();
+        if let Resource::Material(Material::NonConsumable(_)) = project.resources()[2] {
+        } else {
+            panic!("It didn't change the resource type");
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use proptest::prelude::*;
-    use rand::{Rng, rng};
+    #[test]
+    fn idle_time_measures_the_gaps_between_assigned_tasks() {
+        let start = Utc::now();
+        let person = Resource::Personnel {
+            person: Person::new("Sebastiano", "Giordano").unwrap(),
+            hourly_rate: None,
+            availability: Vec::new(),
+        };
+        let mut project = Project::builder()
+            .name("World domination")
+            .start_date(start)
+            .build();
+        let person = project.add_resource(person);
 
-    use crate::{
-        person::Person,
-        project::{
-            Project, ResourceConversionError,
-            test_utils::{project_graph_strategy, project_strategy},
-        },
-        resources::{Consumable, Material, NonConsumable, Resource},
-    };
-    proptest! {
-        #[test]
-        fn update_predecessors_rejects_circular_graphs(mut project in project_graph_strategy()) {
-            assert!(project.update_predecessors(0, &[project.tasks().count() - 1]).is_err());
+        // Not assigned to anything yet.
+        assert!(project.idle_time(0).is_none());
+
+        let mut assigned = Task::new("Find a crowbar");
+        assigned.add_resource(person);
+        assigned.edit_start(start + Duration::hours(2)).unwrap();
+        assigned.edit_finish(start + Duration::hours(4)).unwrap();
+        project.add_task(assigned);
+
+        let mut unassigned = Task::new("Find a stimpack");
+        unassigned.edit_start(start).unwrap();
+        unassigned.edit_finish(start + Duration::hours(10)).unwrap();
+        project.add_task(unassigned);
+
+        assert_eq!(*project.idle_time(0).unwrap(), Duration::hours(8));
+    }
+
+    #[test]
+    fn schedule_backward_ends_the_chain_at_the_target() {
+        let durations = [2, 3, 1];
+        let mut project = Project::new("World domination");
+        for (i, hours) in durations.into_iter().enumerate() {
+            let mut task = Task::new(format!("Step {i}"));
+            task.edit_duration(Duration::hours(hours).try_into().unwrap());
+            project.add_task(task);
         }
+        project
+            .add_time_relationship(0, 1, TimeRelationship::FinishToStart)
+            .unwrap();
+        project
+            .add_time_relationship(1, 2, TimeRelationship::FinishToStart)
+            .unwrap();
 
-        #[test]
-        fn update_successors_rejects_circular_graphs(mut project in project_graph_strategy()) {
-            assert!(project.update_successors(project.tasks().count() - 1, &[0] ).is_err());
+        let target = Utc::now();
+        project.schedule_backward(target).unwrap();
+
+        assert_eq!(
+            project.task(0).unwrap().start(),
+            Some(target - Duration::hours(6))
+        );
+        assert_eq!(
+            project.task(1).unwrap().start(),
+            Some(target - Duration::hours(4))
+        );
+        assert_eq!(project.task(2).unwrap().finish(), Some(target));
+        assert_eq!(project.start_date(), Some(target - Duration::hours(6)));
+    }
+
+    #[test]
+    fn schedule_backward_honors_relationship_kinds() {
+        let mut project = Project::new("World domination");
+        for hours in [4, 1] {
+            let mut task = Task::new("Task");
+            task.edit_duration(Duration::hours(hours).try_into().unwrap());
+            project.add_task(task);
+        }
+        project
+            .add_time_relationship(0, 1, TimeRelationship::StartToStart)
+            .unwrap();
+
+        let target = Utc::now();
+        project.schedule_backward(target).unwrap();
+
+        // Both finish at the target, the successor starts after the predecessor.
+        assert_eq!(project.task(0).unwrap().finish(), Some(target));
+        assert_eq!(
+            project.task(1).unwrap().start(),
+            Some(target - Duration::hours(1))
+        );
+    }
+
+    #[test]
+    fn schedule_backward_requires_durations() {
+        let mut project = Project::new("World domination");
+        project.add_task(Task::new("Find a crowbar"));
+
+        assert!(project.schedule_backward(Utc::now()).is_err());
+    }
+
+    #[test]
+    fn levelled_makespan_serializes_shared_resources() {
+        let person = Resource::Personnel {
+            person: Person::new("Sebastiano", "Giordano").unwrap(),
+            hourly_rate: None,
+            availability: Vec::new(),
+        };
+        let mut project = Project::new("World domination");
+        let person = project.add_resource(person);
+
+        // Two independent branches: 0 -> 1 uses the person, 2 -> 3 too.
+        for hours in [4, 2, 3, 1] {
+            let mut task = Task::new("Task");
+            task.edit_duration(Duration::hours(hours).try_into().unwrap());
+            task.add_resource(person);
+            project.add_task(task);
+        }
+        project
+            .add_time_relationship(0, 1, TimeRelationship::FinishToStart)
+            .unwrap();
+        project
+            .add_time_relationship(2, 3, TimeRelationship::FinishToStart)
+            .unwrap();
+
+        // The critical path is 6 hours long, but one person has 10 hours of work.
+        assert_eq!(*project.levelled_makespan().unwrap(), Duration::hours(10));
+    }
+
+    #[test]
+    fn tasks_where_field_matches_key_and_value() {
+        let mut project = Project::new("World domination");
+        for (name, center) in [
+            ("Find a crowbar", "Ops"),
+            ("Find a stimpack", "R&D"),
+            ("Profit", "Ops"),
+        ] {
+            let mut task = Task::new(name);
+            task.set_custom_field("Cost center", center);
+            project.add_task(task);
+        }
+        project.add_task(Task::new("Prey on free software projects"));
+
+        assert_eq!(project.tasks_where_field("Cost center", "Ops"), vec![0, 2]);
+        assert_eq!(project.tasks_where_field("Cost center", "R&D"), vec![1]);
+        assert!(project.tasks_where_field("Jira", "Ops").is_empty());
+    }
+
+    #[test]
+    fn metadata_can_be_set_replaced_and_removed() {
+        let mut project = Project::builder().name("World domination").build();
+        assert!(project.metadata_entries().is_empty());
+
+        project.set_metadata("Contract", "C-1");
+        project.set_metadata("Contract", "C-1138");
+        assert_eq!(project.metadata("Contract"), Some("C-1138"));
+        assert_eq!(project.metadata_entries().len(), 1);
+
+        assert_eq!(
+            project.remove_metadata("Contract"),
+            Some("C-1138".to_owned())
+        );
+        assert!(project.remove_metadata("Contract").is_none());
+    }
+
+    #[test]
+    fn status_counts_tallies_every_status() {
+        let mut project = Project::new("World domination");
+        let statuses = [
+            TaskStatus::Completed,
+            TaskStatus::InProgress,
+            TaskStatus::Completed,
+            TaskStatus::NotStarted,
+        ];
+        for status in statuses {
+            let mut task = Task::new("Task");
+            task.set_status(status);
+            project.add_task(task);
+        }
+
+        let counts = project.status_counts();
+        assert_eq!(counts.len(), TaskStatus::ALL.len());
+        assert_eq!(counts[&TaskStatus::NotStarted], 1);
+        assert_eq!(counts[&TaskStatus::InProgress], 1);
+        assert_eq!(counts[&TaskStatus::Blocked], 0);
+        assert_eq!(counts[&TaskStatus::Completed], 2);
+    }
+
+    #[test]
+    fn independent_task_pairs_finds_parallel_branches() {
+        let mut chain = Project::new("World domination");
+        for _ in 0..3 {
+            chain.add_task(Task::new("Task"));
+        }
+        chain
+            .add_time_relationship(0, 1, TimeRelationship::FinishToStart)
+            .unwrap();
+        chain
+            .add_time_relationship(1, 2, TimeRelationship::FinishToStart)
+            .unwrap();
+        assert!(chain.independent_task_pairs().is_empty());
+
+        // 0 -> 1 -> 3 and 0 -> 2 -> 3, where 1 and 2 are independent.
+        let mut branches = Project::new("World domination");
+        for _ in 0..4 {
+            branches.add_task(Task::new("Task"));
+        }
+        for (predecessor, successor) in [(0, 1), (0, 2), (1, 3), (2, 3)] {
+            branches
+                .add_time_relationship(predecessor, successor, TimeRelationship::FinishToStart)
+                .unwrap();
+        }
+        assert_eq!(branches.independent_task_pairs(), vec![(1, 2)]);
+
+        // Once they're scheduled to overlap, they're not suggested anymore.
+        let start = Utc::now();
+        for i in [1, 2] {
+            let task = branches.task_mut(i).unwrap();
+            task.edit_start(start).unwrap();
+            task.edit_finish(start + Duration::hours(1)).unwrap();
+        }
+        assert!(branches.independent_task_pairs().is_empty());
+
+        // Without any relationship, every pair is independent, up to the limit.
+        let mut unrelated = Project::new("World domination");
+        for _ in 0..100 {
+            unrelated.add_task(Task::new("Task"));
+        }
+        let pairs = unrelated.independent_task_pairs();
+        assert_eq!(pairs.len(), super::MAX_INDEPENDENT_PAIRS);
+        assert_eq!(pairs[..2], [(0, 1), (0, 2)]);
+    }
+
+    #[test]
+    fn roll_forward_pushes_overdue_work_to_the_cutover() {
+        let as_of = Utc::now();
+        let week_ago = as_of - Duration::days(7);
+        let mut project = Project::new("World domination");
+        for (name, status) in [
+            ("Get equipped", TaskStatus::Completed),
+            ("Find a crowbar", TaskStatus::NotStarted),
+            ("Find a stimpack", TaskStatus::InProgress),
+            ("Profit", TaskStatus::NotStarted),
+        ] {
+            let mut task = Task::new(name);
+            task.edit_duration(Duration::hours(2).try_into().unwrap());
+            task.edit_start(week_ago).unwrap();
+            task.set_status(status);
+            project.add_task(task);
+        }
+        project
+            .add_time_relationship(1, 3, TimeRelationship::FinishToStart)
+            .unwrap();
+
+        let moved = project.roll_forward(as_of).unwrap();
+
+        assert_eq!(moved, vec![1, 3]);
+        assert_eq!(project.task(0).unwrap().start(), Some(week_ago));
+        assert_eq!(project.task(1).unwrap().start(), Some(as_of));
+        assert_eq!(project.task(2).unwrap().start(), Some(week_ago));
+        // The dependent can't start until the rolled task is done.
+        assert_eq!(
+            project.task(3).unwrap().start(),
+            Some(as_of + Duration::hours(2))
+        );
+        assert_eq!(
+            *project.task(3).unwrap().duration().unwrap(),
+            Duration::hours(2)
+        );
+    }
+
+    #[test]
+    fn effective_resource_tasks_rolls_resources_down_to_subtasks() {
+        let person = Resource::Personnel {
+            person: Person::new("Sebastiano", "Giordano").unwrap(),
+            hourly_rate: None,
+            availability: Vec::new(),
+        };
+        let mut project = Project::new("World domination");
+        let person = project.add_resource(person);
+
+        let mut parent = Task::new("Get equipped");
+        parent.add_resource(person);
+        project.add_task(parent);
+        project.add_task(Task::new("Find a crowbar"));
+        project.add_task(Task::new("Sharpen the crowbar"));
+        project.add_task(Task::new("Profit"));
+        project.add_subtask(0, 1);
+        project.add_subtask(1, 2);
+
+        assert_eq!(project.effective_resource_tasks(0, false), vec![0]);
+        assert_eq!(project.effective_resource_tasks(0, true), vec![0, 1, 2]);
+        assert!(project.effective_resource_tasks(1, true).is_empty());
+    }
+
+    #[test]
+    fn infer_duration_from_effort_splits_work_among_people() {
+        let mut project = Project::new("World domination");
+        let mut task = Task::new("Find a crowbar");
+        task.edit_effort(Duration::hours(16).try_into().unwrap());
+        for name in ["Sebastiano", "Margherita"] {
+            task.add_resource(project.add_resource(Resource::Personnel {
+                person: Person::new(name, "Giordano").unwrap(),
+                hourly_rate: None,
+                availability: Vec::new(),
+            }));
+        }
+        task.add_resource(
+            project.add_resource(Resource::Material(Material::NonConsumable(
+                NonConsumable::new("Crowbar"),
+            ))),
+        );
+        project.add_task(task);
+        project.add_task(Task::new("Profit"));
+
+        project.infer_duration_from_effort(0).unwrap();
+
+        assert_eq!(
+            *project.task(0).unwrap().duration().unwrap(),
+            Duration::hours(8)
+        );
+        assert!(project.infer_duration_from_effort(1).is_err());
+        assert!(project.infer_duration_from_effort(2).is_err());
+    }
+
+    #[test]
+    fn roll_forward_honours_the_lag() {
+        let as_of = Utc::now();
+        let mut project = Project::new("World domination");
+        for name in ["Pour the concrete", "Build the walls"] {
+            let mut task = Task::new(name);
+            task.edit_duration(Duration::hours(2).try_into().unwrap());
+            task.edit_start(as_of).unwrap();
+            project.add_task(task);
+        }
+        project
+            .add_time_relationship_with_lag(
+                0,
+                1,
+                TimeRelationship::FinishToStart,
+                Duration::days(2),
+            )
+            .unwrap();
+
+        assert_eq!(project.roll_forward(as_of).unwrap(), vec![1]);
+        assert_eq!(
+            project.task(1).unwrap().start(),
+            Some(as_of + Duration::hours(2) + Duration::days(2))
+        );
+    }
+
+    #[test]
+    fn rolled_up_values_sum_nested_subtasks_and_detect_cycles() {
+        let mut project = Project::new("World domination");
+        let person = project.add_resource(Resource::Personnel {
+            person: Person::new("Sebastiano", "Giordano").unwrap(),
+            hourly_rate: Some(Money::from(10)),
+            availability: Vec::new(),
+        });
+        for (name, hours) in [
+            ("Become world leader", None),
+            ("Get rich", None),
+            ("Rob a bank", Some(2)),
+            ("Buy a castle", Some(3)),
+            ("Be evil", Some(4)),
+        ] {
+            let mut task = Task::new(name);
+            if let Some(hours) = hours {
+                task.edit_duration(Duration::hours(hours).try_into().unwrap());
+            }
+            task.add_resource(person);
+            project.add_task(task);
         }
+        project.add_subtask(0, 1);
+        project.add_subtask(0, 4);
+        project.add_subtask(1, 2);
+        project.add_subtask(1, 3);
+
+        assert_eq!(*project.rolled_up_duration(0).unwrap(), Duration::hours(9));
+        assert_eq!(*project.rolled_up_duration(1).unwrap(), Duration::hours(5));
+        assert_eq!(project.rolled_up_cost(0), Some(Money::from(90)));
+        assert_eq!(project.rolled_up_cost(4), Some(Money::from(40)));
+        assert_eq!(project.rolled_up_cost(7), None);
 
-        #[test]
-        fn update_predecessors_rejects_non_existent_indices(mut project in project_strategy()) {
-            let count: usize = project.tasks().count();
+        project.add_subtask(3, 0);
+        assert!(project.rolled_up_duration(0).is_none());
+        assert!(project.rolled_up_cost(1).is_none());
+    }
 
-            assert!(project.update_predecessors(0, &[count]).is_err())
+    #[test]
+    fn total_cost_charges_materials_and_time() {
+        let mut project = Project::new("World domination");
+        let mut crowbars = Material::new("Crowbar");
+        crowbars.update_quantity(3);
+        crowbars.update_cost_per_unit(7);
+        let mut drill = NonConsumable::new("Drill");
+        drill.update_hourly_rate(4);
+        let mut drill = Material::NonConsumable(drill);
+        drill.update_quantity(1);
+        drill.update_cost_per_unit(100);
+        let drill = Resource::Material(drill);
+        let personnel = Resource::Personnel {
+            person: Person::new("Sebastiano", "Giordano").unwrap(),
+            hourly_rate: Some(Money::from(u16::MAX)),
+            availability: Vec::new(),
+        };
+        project.add_resource(Resource::Material(crowbars));
+        let drill = project.add_resource(drill);
+        let personnel = project.add_resource(personnel);
+        assert_eq!(project.total_cost().unwrap(), Money::from(3 * 7 + 100));
+
+        for (minutes, resources) in [
+            (90, vec![drill, personnel]),
+            (30, vec![personnel]),
+            (45, vec![drill]),
+        ] {
+            let mut task = Task::new(format!("{minutes}m"));
+            task.edit_duration(Duration::minutes(minutes).try_into().unwrap());
+            resources.into_iter().for_each(|r| task.add_resource(r));
+            project.add_task(task);
         }
+        project.add_task(Task::new("No duration"));
 
-        #[test]
-        fn update_successors_rejects_non_existent_indices(mut project in project_strategy()) {
-            let count: usize = project.tasks().count();
+        // The drill runs for 135 minutes, the person works for 120.
+        assert_eq!(
+            project.total_cost().unwrap().amount(),
+            (3 * 7 + 100 + 9) * 100 + 2 * 100 * i64::from(u16::MAX)
+        );
 
-            assert!(project.update_successors(0, &[count]).is_err())
-        }
+        // Costs in different currencies can't be added up.
+        let mut pricey = Material::new("Stimpack");
+        pricey.update_quantity(1);
+        pricey.set_cost_per_unit(Money::new(1, Currency::USD));
+        project.add_resource(Resource::Material(pricey));
+        assert!(project.total_cost().is_err());
+    }
 
-        #[test]
-        fn update_predecessors_removes_them_if_input_is_empty(mut project in project_strategy()) {
-            let mut rng = rng();
-            let task_index1 = rng.random_range(0..project.tasks().count());
-            let mut task_index2 = task_index1;
+    #[test]
+    fn scheduling_with_a_calendar_skips_non_working_time() {
+        // June 6th 2025 is a Friday.
+        let at = |day: u32, hour: u32| {
+            chrono::NaiveDate::from_ymd_opt(2025, 6, day)
+                .unwrap()
+                .and_hms_opt(hour, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+        let mut project = Project::new("World domination").with_calendar(Calendar::default());
+        for hours in [8, 4] {
+            let mut task = Task::new(format!("{hours}h"));
+            task.edit_duration(Duration::hours(hours).try_into().unwrap());
+            project.add_task(task);
+        }
+        project
+            .add_time_relationship(0, 1, TimeRelationship::FinishToStart)
+            .unwrap();
 
-            while task_index2 == task_index1 {
-                task_index2 = rng.random_range(0..project.tasks().count());
-            }
+        project.schedule_backward(at(9, 11)).unwrap();
+        assert_eq!(project.task(1).unwrap().start(), Some(at(6, 15)));
+        assert_eq!(project.task(1).unwrap().finish(), Some(at(9, 11)));
+        assert_eq!(project.task(0).unwrap().start(), Some(at(5, 15)));
+        assert_eq!(project.task(0).unwrap().finish(), Some(at(6, 15)));
+        assert_eq!(project.start_date(), Some(at(5, 15)));
 
-            project.update_predecessors(task_index1, &[task_index2]).unwrap();
-            project.update_predecessors(task_index1, &[]).unwrap();
+        // Starting from Friday afternoon, the first task ends on Monday.
+        assert_eq!(project.roll_forward(at(6, 13)).unwrap(), vec![0, 1]);
+        assert_eq!(project.task(0).unwrap().finish(), Some(at(9, 13)));
+        assert_eq!(project.task(1).unwrap().start(), Some(at(9, 13)));
+        assert_eq!(project.task(1).unwrap().finish(), Some(at(9, 17)));
+        assert_eq!(
+            *project.task(1).unwrap().duration().unwrap(),
+            Duration::hours(4)
+        );
+    }
 
-            assert_eq!(project.predecessors(task_index1).count(), 0);
+    #[test]
+    fn updating_relationships_keeps_their_kind() {
+        let mut project = Project::new("World domination");
+        for name in ["Get rich", "Become world leader", "Be evil", "Profit"] {
+            project.add_task(Task::new(name));
         }
+        project
+            .add_time_relationship(0, 2, TimeRelationship::StartToStart)
+            .unwrap();
 
-        #[test]
-        fn update_predecessors_removes_indices_not_present_in_input(mut project in project_strategy()) {
-            let mut rng = rng();
-            let task_index1 = rng.random_range(0..project.tasks().count());
-            let mut task_index2 = task_index1;
-            let mut task_index3 = task_index1;
-
-            while task_index2 == task_index1 {
-                task_index2 = rng.random_range(0..project.tasks().count());
-            }
-            while task_index3 == task_index1 || task_index3 == task_index2 {
-                task_index3 = rng.random_range(0..project.tasks().count());
-            }
+        project.update_predecessors(2, &[0, 1]).unwrap();
+        assert_eq!(
+            project.time_relationship(0, 2),
+            Some(TimeRelationship::StartToStart)
+        );
+        assert_eq!(
+            project.time_relationship(1, 2),
+            Some(TimeRelationship::FinishToStart)
+        );
 
-            project.update_predecessors(task_index1, &[task_index2, task_index3]).unwrap();
-            project.update_predecessors(task_index1, &[task_index2]).unwrap();
+        project.update_successors(0, &[2, 3]).unwrap();
+        assert_eq!(
+            project.time_relationship(0, 2),
+            Some(TimeRelationship::StartToStart)
+        );
+        assert_eq!(
+            project.time_relationship(0, 3),
+            Some(TimeRelationship::FinishToStart)
+        );
+    }
 
-            let mut predecessors = project.predecessors(task_index1);
-            assert_eq!(predecessors.next(), project.task(task_index2));
-            assert!(predecessors.next().is_none());
+    #[test]
+    fn part_time_assignments_scale_cost_and_effort() {
+        let mut project = Project::new("World domination");
+        project.add_resource(Resource::Material(Material::new("Crowbar")));
+        for (name, surname) in [("Sebastiano", "Giordano"), ("Margherita", "Hack")] {
+            project.add_resource(Resource::Personnel {
+                person: Person::new(name, surname).unwrap(),
+                hourly_rate: Some(Money::from(20)),
+                availability: Vec::new(),
+            });
         }
+        let mut task = Task::new("Find a crowbar");
+        task.edit_effort(Duration::hours(3).try_into().unwrap());
+        task.assign_resource(1, 0.5).unwrap();
+        task.add_resource(2);
+        project.add_task(task);
 
-        #[test]
-        fn update_predecessors_works(mut project in project_strategy()) {
-            let mut rng = rng();
-            let task_index1 = rng.random_range(0..project.tasks().count());
-            let mut task_index2 = task_index1;
+        // One and a half people share three hours of work.
+        project.infer_duration_from_effort(0).unwrap();
+        assert_eq!(project.task(0).unwrap().duration().unwrap().num_hours(), 2);
+        assert_eq!(project.total_cost().unwrap(), Money::from(20 + 40));
+        assert_eq!(project.rolled_up_cost(0), Some(Money::from(60)));
 
-            while task_index2 == task_index1 {
-                task_index2 = rng.random_range(0..project.tasks().count());
-            }
+        // Removing a resource shifts the assignments to the ones after it.
+        project.rm_resource(0).unwrap();
+        let assignments = project.task(0).unwrap().assignments();
+        assert_eq!(assignments[0].resource_index(), 0);
+        assert_eq!(assignments[1].resource_index(), 1);
+        project.rm_resource(0).unwrap();
+        assert_eq!(project.task(0).unwrap().assignments().len(), 1);
+        assert_eq!(project.total_cost().unwrap(), Money::from(40));
+    }
 
-            project.update_predecessors(task_index1, &[task_index2]).unwrap();
+    #[test]
+    fn overallocations_only_count_overlapping_dated_personnel_work() {
+        let start = Utc::now();
+        let person = Resource::Personnel {
+            person: Person::new("Sebastiano", "Giordano").unwrap(),
+            hourly_rate: None,
+            availability: Vec::new(),
+        };
+        let drill = Resource::Material(Material::NonConsumable(NonConsumable::new("Drill")));
+        let mut project = Project::new("World domination");
+        let person = project.add_resource(person);
+        let drill = project.add_resource(drill);
 
-            let mut predecessors = project.predecessors(task_index1);
-            assert_eq!(project.predecessors(task_index1).count(), 1);
-            assert_eq!(predecessors.next(), project.task(task_index2));
+        for offset in [0, 2, 3] {
+            let mut task = Task::new(format!("{offset}h"));
+            task.edit_start(start + Duration::hours(offset)).unwrap();
+            task.edit_duration(Duration::hours(2).try_into().unwrap());
+            task.add_resource(person);
+            task.add_resource(drill);
+            project.add_task(task);
         }
+        let mut undated = Task::new("Undated");
+        undated.add_resource(person);
+        project.add_task(undated);
 
-        #[test]
-        fn update_successors_works(mut project in project_strategy()) {
-            let mut rng = rng();
-            let task_index1 = rng.random_range(0..project.tasks().count());
-            let mut task_index2 = task_index1;
-
-            while task_index2 == task_index1 {
-                task_index2 = rng.random_range(0..project.tasks().count());
-            }
+        // The first task finishes when the second starts, which overlaps with the third.
+        assert_eq!(project.overallocations(), vec![(0, vec![1, 2])]);
 
-            project.update_successors(task_index1, &[task_index2]).unwrap();
+        project
+            .task_mut(2)
+            .unwrap()
+            .assign_resource(0, 0.5)
+            .unwrap();
+        project
+            .task_mut(1)
+            .unwrap()
+            .assign_resource(0, 0.5)
+            .unwrap();
+        assert!(project.overallocations().is_empty());
+    }
 
-            let mut successors = project.successors(task_index1);
-            assert_eq!(successors.next(), project.task(task_index2));
-            assert!(successors.next().is_none());
+    #[test]
+    fn schedule_follows_relationships_and_constraints() {
+        let start = Utc::now();
+        let mut project = Project::builder()
+            .name("World domination")
+            .start_date(start)
+            .build();
+        for hours in [4, 2, 1, 3] {
+            let mut task = Task::new(format!("{hours}h"));
+            task.edit_duration(Duration::hours(hours).try_into().unwrap());
+            project.add_task(task);
         }
+        project
+            .add_time_relationship_with_lag(
+                0,
+                1,
+                TimeRelationship::StartToStart,
+                Duration::hours(1),
+            )
+            .unwrap();
+        project
+            .add_time_relationship(0, 2, TimeRelationship::FinishToFinish)
+            .unwrap();
+        project
+            .add_time_relationship(1, 3, TimeRelationship::FinishToStart)
+            .unwrap();
+        let deadline = start + Duration::hours(5);
+        project
+            .task_mut(3)
+            .unwrap()
+            .set_constraint(TaskConstraint::FinishNoLaterThan(deadline));
 
-        #[test]
-        fn update_successors_removes_them_if_input_is_empty(mut project in project_strategy()) {
-            let mut rng = rng();
-            let task_index1 = rng.random_range(0..project.tasks().count());
-            let mut task_index2 = task_index1;
+        project.schedule().unwrap();
+        let dates = |project: &Project, i: usize| {
+            let task = project.task(i).unwrap();
+            (
+                task.start().unwrap() - start,
+                task.finish().unwrap() - start,
+            )
+        };
+        assert_eq!(dates(&project, 0), (Duration::zero(), Duration::hours(4)));
+        assert_eq!(dates(&project, 1), (Duration::hours(1), Duration::hours(3)));
+        assert_eq!(dates(&project, 2), (Duration::hours(3), Duration::hours(4)));
+        // The constraint can't pull the task before its predecessor, it's reported instead.
+        assert_eq!(dates(&project, 3), (Duration::hours(3), Duration::hours(6)));
+        assert!(
+            project
+                .validate_all()
+                .contains(&ValidationIssue::ConstraintViolated(3))
+        );
 
-            while task_index2 == task_index1 {
-                task_index2 = rng.random_range(0..project.tasks().count());
-            }
+        project
+            .task_mut(3)
+            .unwrap()
+            .set_constraint(TaskConstraint::MustFinishOn(start + Duration::days(1)));
+        project.schedule().unwrap();
+        assert_eq!(dates(&project, 3), (Duration::hours(21), Duration::days(1)));
 
-            project.update_successors(task_index1, &[task_index2]).unwrap();
-            project.update_successors(task_index1, &[]).unwrap();
+        project.add_task(Task::new("No duration"));
+        assert!(project.schedule().is_err());
+        assert!(Project::new("World domination").schedule().is_err());
+    }
 
-            assert_eq!(project.successors(task_index1).count(), 0);
+    #[test]
+    fn percent_complete_weighs_subtasks_by_duration() {
+        let mut project = Project::new("World domination");
+        project.add_task(Task::new("Become world leader"));
+        project.add_task(Task::new("Get rich"));
+        for (hours, percent_complete) in [(2, 50), (6, 100), (0, 0)] {
+            let mut task = Task::new(format!("{hours}h"));
+            task.edit_duration(Duration::hours(hours).try_into().unwrap());
+            task.set_percent_complete(percent_complete);
+            project.add_task(task);
         }
+        let mut undated = Task::new("Undated");
+        undated.set_percent_complete(30);
+        project.add_task(undated);
+        project.add_subtask(0, 1);
+        project.add_subtask(1, 2);
+        project.add_subtask(1, 3);
+        project.add_subtask(0, 4);
+        project.add_subtask(0, 5);
 
-        #[test]
-        fn update_successors_removes_indices_not_present_in_input(mut project in project_strategy()) {
-            let mut rng = rng();
-            let task_index1 = rng.random_range(0..project.tasks().count());
-            let mut task_index2 = task_index1;
-            let mut task_index3 = task_index1;
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        assert!(close(project.percent_complete(0), 87.5));
+        assert!(close(project.percent_complete(4), 0.0));
+        assert!(close(project.percent_complete(5), 30.0));
+        assert!(close(project.percent_complete(42), 0.0));
 
-            while task_index2 == task_index1 {
-                task_index2 = rng.random_range(0..project.tasks().count());
+        // Without durations, every task weighs the same.
+        project.task_mut(2).unwrap().set_milestone(true);
+        project.task_mut(3).unwrap().set_milestone(true);
+        assert!(close(project.percent_complete(0), 45.0));
+    }
+
+    #[test]
+    fn projects_compare_relationships_regardless_of_order() {
+        let build = |relationships: &[(usize, usize)]| {
+            let mut project = Project::new("World domination");
+            for name in ["Find a crowbar", "Find a stimpack", "Profit"] {
+                project.add_task(Task::new(name));
             }
-            while task_index3 == task_index1 || task_index3 == task_index2 {
-                task_index3 = rng.random_range(0..project.tasks().count());
+            for &(predecessor, successor) in relationships {
+                project
+                    .add_time_relationship(predecessor, successor, TimeRelationship::FinishToStart)
+                    .unwrap();
             }
+            project
+        };
+        let project = build(&[(0, 2), (1, 2)]);
+        assert_eq!(project, build(&[(1, 2), (0, 2)]));
+        assert_ne!(project, build(&[(0, 2)]));
 
-            project.update_successors(task_index1, &[task_index2, task_index3]).unwrap();
-            project.update_successors(task_index1, &[task_index2]).unwrap();
+        let mut other = build(&[(0, 2), (1, 2)]);
+        other
+            .add_time_relationship(0, 2, TimeRelationship::StartToStart)
+            .unwrap();
+        assert_ne!(project, other);
 
-            let mut successors = project.successors(task_index1);
-            assert_eq!(successors.next(), project.task(task_index2));
-            assert!(successors.next().is_none());
+        let mut other = build(&[(0, 2), (1, 2)]);
+        other.add_subtask(2, 0);
+        assert_ne!(project, other);
+    }
+
+    #[test]
+    fn tasks_are_grouped_by_phase() {
+        let mut project = Project::new("World domination");
+        for (i, phase) in Phase::ALL.into_iter().enumerate() {
+            for n in 0..=i {
+                let mut task = Task::new(format!("{phase:?} {n}"));
+                task.set_phase(phase);
+                project.add_task(task);
+            }
+        }
+        project.add_task(Task::new("Unphased"));
+
+        for (i, phase) in Phase::ALL.into_iter().enumerate() {
+            let tasks: Vec<&Task> = project.tasks_in_phase(phase).collect();
+            assert_eq!(tasks.len(), i + 1);
+            assert!(tasks.iter().all(|task| task.phase() == Some(phase)));
         }
     }
 
     #[test]
-    fn res_into_consumable_returns_the_correct_errors() {
+    fn cloned_projects_change_independently() {
         let mut project = Project::new("World domination");
+        project.add_task(Task::new("Find a crowbar"));
+        project.add_task(Task::new("Profit"));
+        project.add_subtask(1, 0);
+        project.add_resource(Resource::Material(Material::new("Crowbar")));
 
-        project.add_resource(Resource::Personnel {
-            person: Person::new("Sebastiano", "Giordano").unwrap(),
-            hourly_rate: None,
-        });
+        let mut scenario = project.clone();
+        assert_eq!(scenario, project);
+        scenario
+            .add_time_relationship(0, 1, TimeRelationship::FinishToStart)
+            .unwrap();
+        scenario.task_mut(1).unwrap().edit_name("Profit, sooner");
+        scenario.rm_resource(0).unwrap();
+        scenario.add_task(Task::new("Find a stimpack"));
 
-        // The correct error when trying to convert a reasource that's not a `Material`.
+        assert_ne!(scenario, project);
+        assert_eq!(project.task_count(), 2);
+        assert_eq!(project.task(1).unwrap().name(), "Profit");
+        assert_eq!(project.predecessors_indices(1).count(), 0);
+        assert_eq!(project.resources().len(), 1);
+        assert_eq!(project.subtasks(1), vec![0]);
         assert_eq!(
-            project.res_into_consumable(0),
-            Err(ResourceConversionError::ConversionNotPossible)
+            scenario.task(1).unwrap().id(),
+            project.task(1).unwrap().id()
         );
+    }
 
-        // The correct error when trying to convert a reasource that does not exist.
+    #[test]
+    fn resources_are_overallocated_outside_their_availability() {
+        let monday = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+        let mut crane = NonConsumable::new("Crane");
+        crane
+            .add_availability(monday, monday + Duration::days(5))
+            .unwrap();
+        let mut project = Project::new("World domination");
+        project.add_resource(Resource::Material(Material::NonConsumable(crane)));
+
+        for offset in [0, 4] {
+            let mut task = Task::new(format!("Day {offset}"));
+            task.edit_start(monday + Duration::days(offset)).unwrap();
+            task.edit_duration(Duration::days(2).try_into().unwrap());
+            task.assign_resource(0, 1.0).unwrap();
+            project.add_task(task);
+        }
+
+        // The crane isn't limited in capacity, but it's gone before the second task finishes.
+        assert_eq!(project.overallocations(), vec![(0, vec![1])]);
         assert_eq!(
-            project.res_into_consumable(1),
-            Err(ResourceConversionError::ResourceNotFound)
+            project.validate_strict(),
+            vec![ValidationIssue::ResourceUnavailable {
+                resource: 0,
+                task: 1
+            }]
         );
 
-        // Doesn't return an error when trying to convert a `Consumable` into a `Consumable`.
-        project.add_resource(Resource::Material(Material::Consumable(Consumable::new(
-            "Stimpack",
-        ))));
+        project
+            .resource_mut(0)
+            .unwrap()
+            .add_availability(monday + Duration::days(5), monday + Duration::days(10))
+            .unwrap();
+        assert!(project.overallocations().is_empty());
+    }
 
-        assert!(project.res_into_consumable(1).is_ok());
+    #[test]
+    fn ancestors_and_descendants_mirror_each_other() {
+        // 0 -> 1 -> 2 -> 4, 0 -> 3 -> 4 and 5 on its own.
+        let mut project = Project::new("World domination");
+        for i in 0..6 {
+            project.add_task(Task::new(i.to_string()));
+        }
+        for (predecessor, successor) in [(0, 1), (1, 2), (2, 4), (0, 3), (3, 4)] {
+            project
+                .add_time_relationship(predecessor, successor, TimeRelationship::FinishToStart)
+                .unwrap();
+        }
 
-        // Doesn't change the type of the resource when not needed.
-        if let Resource::Material(Material::Consumable(_)) = project.resources()[1] {
-        } else {
-            panic!("It changed the resource type");
+        assert_eq!(project.descendants(1), vec![2, 4]);
+        assert_eq!(project.ancestors(4), vec![0, 1, 2, 3]);
+        assert!(project.descendants(5).is_empty() && project.ancestors(5).is_empty());
+        assert!(project.descendants(42).is_empty());
+        for i in 0..6 {
+            for j in project.descendants(i) {
+                assert!(project.ancestors(j).contains(&i));
+            }
         }
+    }
 
-        // It changes it when needed.
+    #[test]
+    fn rejected_relationships_report_the_cycle() {
+        let mut project = Project::new("World domination");
+        for i in 0..4 {
+            project.add_task(Task::new(i.to_string()));
+        }
+        for (predecessor, successor) in [(0, 1), (1, 2), (0, 2)] {
+            project
+                .add_time_relationship(predecessor, successor, TimeRelationship::FinishToStart)
+                .unwrap();
+        }
+
+        let error = project
+            .add_time_relationship(2, 0, TimeRelationship::FinishToStart)
+            .unwrap_err();
+        assert!(error.to_string().ends_with(": 0 -> 2 -> 0"), "{error}");
+        let error = project.update_predecessors(0, &[3, 1]).unwrap_err();
+        assert!(error.to_string().ends_with(": 0 -> 1 -> 0"), "{error}");
+        let error = project.update_successors(2, &[2]).unwrap_err();
+        assert!(error.to_string().ends_with(": 2 -> 2"), "{error}");
+        assert_eq!(project.predecessors_indices(0).count(), 0);
+    }
+
+    #[test]
+    fn removing_a_task_keeps_the_other_subtask_links() {
+        // 0 has subtasks 1 and 3, 2 has subtask 4.
+        let mut project = Project::new("World domination");
+        for name in [
+            "Plan",
+            "Find a crowbar",
+            "Prepare",
+            "Find a stimpack",
+            "Find a map",
+        ] {
+            project.add_task(Task::new(name));
+        }
+        for (task, subtask) in [(0, 1), (0, 3), (2, 4)] {
+            project.add_subtask(task, subtask);
+        }
+
+        // The map takes the place of the crowbar.
+        project.rm_task(1).unwrap();
+        assert_eq!(project.task(1).unwrap().name(), "Find a map");
+        fn names(project: &Project, task: usize) -> Vec<&str> {
+            project
+                .subtasks(task)
+                .into_iter()
+                .map(|i| project.task(i).unwrap().name())
+                .collect()
+        }
+        assert_eq!(names(&project, 0), vec!["Find a stimpack"]);
+        assert_eq!(names(&project, 2), vec!["Find a map"]);
+        assert_eq!(project.subtask_pairs().count(), 2);
+
+        // Removing the last task doesn't move anything.
+        project.rm_task(3).unwrap();
+        assert!(names(&project, 0).is_empty());
+        assert_eq!(project.subtask_pairs().count(), 1);
+    }
+
+    #[test]
+    fn rescheduling_moves_every_dated_task_by_the_same_offset() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+        let week = Duration::weeks(1);
+        let mut project = Project::builder()
+            .name("World domination")
+            .start_date(start)
+            .build();
+        assert!(Project::new("Undated").reschedule_from(start).is_err());
+
+        for offset in [0, 3] {
+            let mut task = Task::new(format!("Day {offset}"));
+            task.edit_start(start + Duration::days(offset)).unwrap();
+            task.edit_duration(Duration::days(2).try_into().unwrap());
+            project.add_task(task);
+        }
+        project
+            .task_mut(1)
+            .unwrap()
+            .split_at(start + Duration::days(4))
+            .unwrap();
+        project
+            .task_mut(0)
+            .unwrap()
+            .set_constraint(TaskConstraint::MustStartOn(start));
+        project.add_task(Task::new("Undated"));
+        let before = project.clone();
+
+        project.reschedule_from(start + week).unwrap();
+        assert_eq!(project.start_date(), Some(start + week));
+        for (moved, task) in project.tasks().zip(before.tasks()) {
+            assert_eq!(moved.start(), task.start().map(|d| d + week));
+            assert_eq!(moved.finish(), task.finish().map(|d| d + week));
+            assert_eq!(moved.duration(), task.duration());
+        }
+        assert_eq!(
+            project.task(1).unwrap().segments()[1],
+            (
+                start + Duration::days(4) + week,
+                start + Duration::days(5) + week
+            )
+        );
+        // The constraint stays where it was, so the task is flagged.
+        assert!(
+            project
+                .validate_all()
+                .contains(&ValidationIssue::ConstraintViolated(0))
+        );
+
+        assert!(project.reschedule_from(DateTime::<Utc>::MAX_UTC).is_err());
+        assert_eq!(project.start_date(), Some(start + week));
+    }
+
+    #[test]
+    fn completed_tasks_use_up_consumables() {
+        let mut stimpacks = Material::new("Stimpack");
+        stimpacks.update_quantity(10);
+        let mut project = Project::new("World domination");
+        project.add_resource(Resource::Material(Material::new("Bandage")));
+        project.add_resource(Resource::Material(stimpacks));
         project.add_resource(Resource::Material(Material::NonConsumable(
             NonConsumable::new("Crowbar"),
         )));
-        project.res_into_consumable(2).unwrap();
-        if let Resource::Material(Material::Consumable(_)) = project.resources()[2] {
-        } else {
-            panic!("It didn't change the resource type");
+        for amount in [4, 5, 3] {
+            let mut task = Task::new(format!("Use {amount}"));
+            task.set_consumption(1, amount);
+            task.set_status(TaskStatus::Completed);
+            project.add_task(task);
+        }
+        project.task_mut(1).unwrap().set_status(TaskStatus::Blocked);
+
+        assert_eq!(project.remaining_quantity(1).unwrap(), 3);
+        project
+            .task_mut(1)
+            .unwrap()
+            .set_status(TaskStatus::Completed);
+        assert_eq!(project.remaining_quantity(1).unwrap(), -2);
+        for invalid in [0, 2, 3] {
+            assert!(project.remaining_quantity(invalid).is_err());
         }
+
+        // The consumptions follow the resource when the one before it is removed.
+        project.rm_resource(0).unwrap();
+        assert_eq!(project.task(0).unwrap().consumptions(), &[(0, 4)]);
+        assert_eq!(project.remaining_quantity(0).unwrap(), -2);
     }
 
     #[test]
-    fn res_into_nonconsumable_returns_the_correct_errors() {
+    fn inserted_tasks_take_over_the_relationship() {
         let mut project = Project::new("World domination");
+        project.add_tasks([Task::new("Get rich"), Task::new("Become world leader")]);
+        project
+            .add_time_relationship_with_lag(
+                0,
+                1,
+                TimeRelationship::FinishToFinish,
+                Duration::days(2),
+            )
+            .unwrap();
 
-        project.add_resource(Resource::Personnel {
-            person: Person::new("Sebastiano", "Giordano").unwrap(),
-            hourly_rate: None,
-        });
+        assert!(
+            project
+                .insert_between(1, 0, Task::new("Backwards"))
+                .is_err()
+        );
+        assert_eq!(project.tasks().count(), 2);
 
-        // The correct error when trying to convert a resource that's not a `Material`.
+        let army = project
+            .insert_between(0, 1, Task::new("Buy an army"))
+            .unwrap();
+        assert_eq!(army, 2);
+        let mut relationships: Vec<_> = project.relationships().collect();
+        relationships.sort_unstable_by_key(|&(predecessor, successor, _)| (predecessor, successor));
         assert_eq!(
-            project.res_into_nonconsumable(0),
-            Err(ResourceConversionError::ConversionNotPossible)
+            relationships,
+            vec![
+                (0, 2, TimeRelationship::FinishToFinish),
+                (2, 1, TimeRelationship::FinishToFinish)
+            ]
         );
+        assert_eq!(project.dependency(0, 2).unwrap().lag, Duration::days(2));
+        assert_eq!(project.dependency(2, 1).unwrap().lag, Duration::zero());
+    }
+
+    #[test]
+    fn date_conflicts_follow_the_kind_and_the_lag() {
+        let start = Utc::now();
+        let mut project = Project::new("World domination");
+        for (name, offset) in [
+            ("Get rich", 0),
+            ("Buy an army", 2),
+            ("Become world leader", 4),
+        ] {
+            let mut task = Task::new(name);
+            task.edit_start(start + Duration::hours(offset)).unwrap();
+            task.edit_duration(Duration::hours(2).try_into().unwrap());
+            project.add_task(task);
+        }
+        project.add_task(Task::new("Celebrate"));
+        project
+            .add_time_relationship_with_lag(
+                0,
+                1,
+                TimeRelationship::FinishToStart,
+                Duration::hours(1),
+            )
+            .unwrap();
+        project
+            .add_time_relationship(1, 2, TimeRelationship::FinishToFinish)
+            .unwrap();
+        project
+            .add_time_relationship(0, 2, TimeRelationship::StartToFinish)
+            .unwrap();
+        project
+            .add_time_relationship(2, 3, TimeRelationship::FinishToStart)
+            .unwrap();
 
-        // The correct error when trying to convert a resource that does not exist.
         assert_eq!(
-            project.res_into_nonconsumable(1),
-            Err(ResourceConversionError::ResourceNotFound)
+            project.date_conflicts(),
+            vec![(0, 1, TimeRelationship::FinishToStart)]
         );
 
-        // Doesn't return an error when trying to convert a `NonConsumable` into a `NonConsumable`.
-        project.add_resource(Resource::Material(Material::NonConsumable(
-            NonConsumable::new("Crowbar"),
-        )));
-
-        assert!(project.res_into_nonconsumable(1).is_ok());
+        project
+            .task_mut(1)
+            .unwrap()
+            .edit_start(start + Duration::hours(3))
+            .unwrap();
+        project
+            .task_mut(2)
+            .unwrap()
+            .edit_start(start + Duration::hours(2))
+            .unwrap();
+        assert_eq!(
+            project.date_conflicts(),
+            vec![(1, 2, TimeRelationship::FinishToFinish)]
+        );
+    }
 
-        // Doesn't change the type of the resource when not needed.
-        if let Resource::Material(Material::NonConsumable(_)) = project.resources()[1] {
-        } else {
-            panic!("It changed the resource type");
+    #[test]
+    fn roots_and_leaves_of_a_diamond() {
+        let mut project = Project::new("World domination");
+        for name in [
+            "Get rich",
+            "Buy an army",
+            "Hire a general",
+            "Become world leader",
+        ] {
+            project.add_task(Task::new(name));
         }
+        assert_eq!(project.isolated_tasks(), vec![0, 1, 2, 3]);
 
-        // It changes it when needed.
-        project.add_resource(Resource::Material(Material::Consumable(Consumable::new(
-            "Stimpack",
-        ))));
-        project.res_into_nonconsumable(2).unwrap();
-        if let Resource::Material(Material::NonConsumable(_)) = project.resources()[2] {
-        } else {
-            panic!("It didn't change the resource type");
+        for (predecessor, successor) in [(0, 1), (0, 2), (1, 3), (2, 3)] {
+            project
+                .add_time_relationship(predecessor, successor, TimeRelationship::default())
+                .unwrap();
         }
+        assert_eq!(project.roots(), vec![0]);
+        assert_eq!(project.leaves(), vec![3]);
+        assert!(project.isolated_tasks().is_empty());
+
+        project.rm_task(0).unwrap();
+        // The last task took index 0.
+        assert_eq!(project.roots(), vec![1, 2]);
+        assert_eq!(project.leaves(), vec![0]);
+        assert!(Project::new("World domination").roots().is_empty());
     }
 }
@@ -0,0 +1,418 @@
+use anyhow::Context;
+use chrono::{DateTime, Duration, Utc};
+
+use super::{Project, hourly_rate, thousandths, time_cost};
+use crate::{
+    duration::PositiveDuration,
+    money::{Currency, Money, MoneyError},
+    task::Task,
+};
+
+/// A snapshot of the plan, taken to see later how it evolved.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Baseline {
+    /// The planned values of every task, in the order of the tasks at the time of the snapshot.
+    tasks: Vec<BaselineTask>,
+}
+
+/// The planned values of a task, as stored in a [`Baseline`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BaselineTask {
+    /// The identifier of the task, see [`Task::id`]. Unlike its index, it doesn't change when
+    /// other tasks are removed.
+    pub id: u64,
+    /// The planned start of the task.
+    pub start: Option<DateTime<Utc>>,
+    /// The planned finish of the task.
+    pub finish: Option<DateTime<Utc>>,
+    /// The planned duration of the task.
+    pub duration: Option<PositiveDuration>,
+    /// The planned cost of the task, given by the hourly rates of its resources.
+    pub cost: Money,
+}
+
+/// How the plan changed between two baselines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BaselineComparison {
+    /// The changes of the tasks that are in both baselines, in the order of the earlier one.
+    pub tasks: Vec<TaskDelta>,
+    /// The identifiers of the tasks that are only in the later baseline.
+    pub added: Vec<u64>,
+    /// The identifiers of the tasks that are only in the earlier baseline.
+    pub removed: Vec<u64>,
+}
+
+/// How a task changed between two baselines. Every delta is the value in the later baseline
+/// minus the value in the earlier one, and is `None` when either value is missing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskDelta {
+    /// The identifier of the task, see [`Task::id`].
+    pub task: u64,
+    /// How much the start moved.
+    pub start: Option<Duration>,
+    /// How much the finish moved.
+    pub finish: Option<Duration>,
+    /// How much the duration changed.
+    pub duration: Option<Duration>,
+    /// How much the cost changed.
+    pub cost: Money,
+}
+
+impl TaskDelta {
+    /// Returns true if the task didn't change between the baselines.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Find a crowbar"));
+    /// let a = project.set_baseline().unwrap();
+    /// let b = project.set_baseline().unwrap();
+    ///
+    /// let comparison = project.compare_baselines(a, b).unwrap();
+    /// assert!(comparison.tasks[0].is_unchanged());
+    /// ```
+    pub fn is_unchanged(&self) -> bool {
+        let unchanged = |delta: Option<Duration>| delta.is_none_or(|d| d.is_zero());
+        unchanged(self.start)
+            && unchanged(self.finish)
+            && unchanged(self.duration)
+            && self.cost.amount() == 0
+    }
+}
+
+impl Baseline {
+    /// Returns the planned values of the tasks, in the order of the tasks at the time of the
+    /// snapshot.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Find a crowbar"));
+    /// project.set_baseline().unwrap();
+    ///
+    /// assert_eq!(project.baselines()[0].tasks().len(), 1);
+    /// ```
+    pub fn tasks(&self) -> &[BaselineTask] {
+        &self.tasks
+    }
+
+    /// Returns the planned values of the task with the given identifier, if it was in the
+    /// project at the time of the snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the task, see [`Task::id`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Find a crowbar"));
+    /// project.add_task(Task::new("Profit"));
+    /// project.set_baseline().unwrap();
+    /// let id = project.task(1).unwrap().id();
+    /// project.rm_task(0).unwrap();
+    ///
+    /// assert_eq!(project.baselines()[0].task(id).unwrap().id, id);
+    /// ```
+    pub fn task(&self, id: u64) -> Option<&BaselineTask> {
+        self.tasks.iter().find(|task| task.id == id)
+    }
+}
+
+impl Project {
+    /// Takes a snapshot of the planned dates, durations and costs of every task, and stores it
+    /// as a new baseline. The dates and durations are also stored on every task, see
+    /// [`Task::set_baseline`], so that their schedule variance refers to the newest baseline.
+    ///
+    /// # Returns
+    ///
+    /// The index of the new baseline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cost of a task can't be computed, because the hourly rates of
+    /// its resources are in different currencies or the cost is too large. The project is
+    /// left untouched in that case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::project::Project;
+    ///
+    /// let mut project = Project::new("World domination");
+    /// assert_eq!(project.set_baseline().unwrap(), 0);
+    /// assert_eq!(project.set_baseline().unwrap(), 1);
+    /// ```
+    pub fn set_baseline(&mut self) -> anyhow::Result<usize> {
+        let tasks = self
+            .tasks()
+            .enumerate()
+            .map(|(i, task)| {
+                Ok(BaselineTask {
+                    id: task.id(),
+                    start: task.start(),
+                    finish: task.finish(),
+                    duration: task.duration(),
+                    cost: planned_cost(self, task)
+                        .with_context(|| format!("Couldn't compute the cost of task {i}"))?,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        self.baselines.push(Baseline { tasks });
+        self.tasks_mut().for_each(Task::set_baseline);
+        Ok(self.baselines.len() - 1)
+    }
+
+    /// Returns all the baselines of the project, from the oldest to the newest.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::project::Project;
+    ///
+    /// let mut project = Project::new("World domination");
+    /// assert!(project.baselines().is_empty());
+    /// project.set_baseline().unwrap();
+    /// assert_eq!(project.baselines().len(), 1);
+    /// ```
+    pub fn baselines(&self) -> &[Baseline] {
+        &self.baselines
+    }
+
+    /// Compares two baselines, to show how the plan itself evolved across rebaselines.
+    /// Tasks are matched by identifier, so removing a task in between doesn't mix them up.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The index of the earlier baseline.
+    /// * `b` - The index of the later baseline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either baseline doesn't exist, or if the cost of a task is in
+    /// different currencies in the two baselines.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Find a crowbar"));
+    /// let a = project.set_baseline().unwrap();
+    /// let profit = Task::new("Profit");
+    /// let id = profit.id();
+    /// project.add_task(profit);
+    /// let b = project.set_baseline().unwrap();
+    ///
+    /// let comparison = project.compare_baselines(a, b).unwrap();
+    /// assert_eq!(comparison.added, vec![id]);
+    /// assert!(project.compare_baselines(a, 2).is_err());
+    /// ```
+    pub fn compare_baselines(&self, a: usize, b: usize) -> anyhow::Result<BaselineComparison> {
+        let earlier = self
+            .baselines
+            .get(a)
+            .with_context(|| format!("Baseline {a} doesn't exist"))?;
+        let later = self
+            .baselines
+            .get(b)
+            .with_context(|| format!("Baseline {b} doesn't exist"))?;
+
+        let delta = |from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>| Some(to? - from?);
+        let tasks = earlier
+            .tasks
+            .iter()
+            .filter_map(|from| Some((from, later.task(from.id)?)))
+            .map(|(from, to)| {
+                Ok(TaskDelta {
+                    task: from.id,
+                    start: delta(from.start, to.start),
+                    finish: delta(from.finish, to.finish),
+                    duration: from
+                        .duration
+                        .zip(to.duration)
+                        .map(|(from, to)| to.diff(&from)),
+                    cost: to.cost.checked_sub(from.cost).with_context(|| {
+                        format!("Couldn't compare the costs of task {}", from.id)
+                    })?,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        let only_in = |baseline: &Baseline, other: &Baseline| {
+            (baseline.tasks.iter())
+                .filter(|task| other.task(task.id).is_none())
+                .map(|task| task.id)
+                .collect()
+        };
+
+        Ok(BaselineComparison {
+            tasks,
+            added: only_in(later, earlier),
+            removed: only_in(earlier, later),
+        })
+    }
+}
+
+/// Computes the cost of a task from the hourly rates of the personnel and the non consumable
+/// materials working on it, over its duration, rounded up to the hundredth. It's zero if the
+/// task has no duration.
+///
+/// # Errors
+///
+/// Returns an error if the hourly rates are in different currencies, or if the cost is too large.
+pub(super) fn planned_cost(project: &Project, task: &Task) -> Result<Money, MoneyError> {
+    let zero = Money::zero(Currency::default());
+    let Some(duration) = task.duration() else {
+        return Ok(zero);
+    };
+    let minutes = u64::try_from(duration.num_minutes()).map_err(|_| MoneyError::Overflow)?;
+    project
+        .staffing(task)
+        .into_iter()
+        .filter_map(|(resource, units)| Some((hourly_rate(resource)?, units)))
+        .try_fold(zero, |total, (rate, units)| {
+            total.checked_add(time_cost(rate, minutes * thousandths(units))?)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::TaskDelta;
+
+    use crate::{
+        money::{Currency, Money},
+        person::Person,
+        project::Project,
+        resources::Resource,
+        task::Task,
+    };
+
+    #[test]
+    fn compare_baselines_reports_the_changed_task() {
+        let start = Utc::now();
+        let mut project = Project::new("World domination");
+        let person = project.add_resource(Resource::Personnel {
+            person: Person::new("Sebastiano", "Giordano").unwrap(),
+            hourly_rate: Some(Money::from(50)),
+            availability: Vec::new(),
+        });
+        for name in ["Find a crowbar", "Profit"] {
+            let mut task = Task::new(name);
+            task.add_resource(person);
+            task.edit_start(start).unwrap();
+            task.edit_duration(Duration::hours(2).try_into().unwrap());
+            project.add_task(task);
+        }
+        let first = project.set_baseline().unwrap();
+        project
+            .task_mut(1)
+            .unwrap()
+            .edit_duration(Duration::hours(5).try_into().unwrap());
+        let second = project.set_baseline().unwrap();
+
+        let comparison = project.compare_baselines(first, second).unwrap();
+
+        assert_eq!(comparison.tasks.len(), 2);
+        assert!(comparison.tasks[0].is_unchanged());
+        let changed = &comparison.tasks[1];
+        assert_eq!(changed.start, Some(Duration::zero()));
+        assert_eq!(changed.finish, Some(Duration::hours(3)));
+        assert_eq!(changed.duration, Some(Duration::hours(3)));
+        assert_eq!(changed.cost, Money::from(150));
+        assert!(comparison.added.is_empty() && comparison.removed.is_empty());
+
+        // Comparing the other way around reverses the deltas.
+        let reversed = project.compare_baselines(second, first).unwrap();
+        assert_eq!(reversed.tasks[1].duration, Some(Duration::hours(-3)));
+        assert!(project.compare_baselines(first, 2).is_err());
+    }
+
+    #[test]
+    fn compare_baselines_matches_tasks_across_removals() {
+        let start = Utc::now();
+        let mut project = Project::new("World domination");
+        for name in ["Find a crowbar", "Open the vault", "Profit"] {
+            let mut task = Task::new(name);
+            task.edit_start(start).unwrap();
+            task.edit_duration(Duration::hours(2).try_into().unwrap());
+            project.add_task(task);
+        }
+        let ids: Vec<_> = project.tasks().map(Task::id).collect();
+        let first = project.set_baseline().unwrap();
+        // Removing the first task moves the last one into its index.
+        project.rm_task(0).unwrap();
+        let spare = Task::new("Spare crowbar");
+        let spare_id = spare.id();
+        project.add_task(spare);
+        let second = project.set_baseline().unwrap();
+
+        let comparison = project.compare_baselines(first, second).unwrap();
+
+        assert_eq!(comparison.removed, vec![ids[0]]);
+        assert_eq!(comparison.added, vec![spare_id]);
+        let matched: Vec<_> = comparison.tasks.iter().map(|delta| delta.task).collect();
+        assert_eq!(matched, vec![ids[1], ids[2]]);
+        assert!(comparison.tasks.iter().all(TaskDelta::is_unchanged));
+    }
+
+    #[test]
+    fn mixed_currencies_are_rejected() {
+        let mut project = Project::new("World domination");
+        let mut task = Task::new("Find a crowbar");
+        for (name, currency) in [("Sebastiano", "EUR"), ("Mario", "USD")] {
+            let person = project.add_resource(Resource::Personnel {
+                person: Person::new(name, "Giordano").unwrap(),
+                hourly_rate: Some(Money::new(1000, Currency::new(currency).unwrap())),
+                availability: Vec::new(),
+            });
+            task.add_resource(person);
+        }
+        task.edit_duration(Duration::hours(2).try_into().unwrap());
+        project.add_task(task);
+
+        assert!(project.set_baseline().is_err());
+        assert!(project.baselines().is_empty());
+    }
+
+    #[test]
+    fn set_baseline_snapshots_every_task() {
+        let start = Utc::now();
+        let mut project = Project::new("World domination");
+        for name in ["Find a crowbar", "Profit"] {
+            let mut task = Task::new(name);
+            task.edit_start(start).unwrap();
+            task.edit_duration(Duration::hours(2).try_into().unwrap());
+            project.add_task(task);
+        }
+        project.add_task(Task::new("Undated"));
+        project.set_baseline().unwrap();
+        project
+            .task_mut(1)
+            .unwrap()
+            .edit_duration(Duration::hours(5).try_into().unwrap());
+
+        let variances: Vec<_> = project.tasks().map(Task::schedule_variance).collect();
+        assert_eq!(
+            variances,
+            vec![Some(Duration::zero()), Some(Duration::hours(3)), None]
+        );
+        assert_eq!(
+            project.task(1).unwrap().baseline_duration(),
+            Some(Duration::hours(2).try_into().unwrap())
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use daggy::Dag;
+
+use super::Project;
+use crate::{resources::Resource, stakeholders::Stakeholder, task::Task};
+
+/// Builds a [`Project`] field by field. Every field that isn't set keeps its default value.
+#[derive(Debug, Default)]
+pub struct ProjectBuilder {
+    /// The project being built.
+    project: Project,
+}
+
+impl ProjectBuilder {
+    /// Sets the name of the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::project::Project;
+    ///
+    /// let project = Project::builder().name("World domination").build();
+    /// assert_eq!(project.name(), "World domination");
+    /// ```
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.project.name = name.into();
+        self
+    }
+
+    /// Sets the description of the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - The description of the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::project::Project;
+    ///
+    /// let project = Project::builder()
+    ///     .description("With a crowbar and a stimpack")
+    ///     .build();
+    /// assert_eq!(project.description(), Some("With a crowbar and a stimpack"));
+    /// ```
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.project.description = Some(description.into());
+        self
+    }
+
+    /// Sets the description of the project, or clears it with `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - The description of the project, if any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::project::Project;
+    ///
+    /// let project = Project::builder()
+    ///     .maybe_description(Some("With a crowbar and a stimpack"))
+    ///     .build();
+    /// assert_eq!(project.description(), Some("With a crowbar and a stimpack"));
+    ///
+    /// let project = project.to_builder().maybe_description(None::<String>).build();
+    /// assert_eq!(project.description(), None);
+    /// ```
+    pub fn maybe_description(mut self, description: Option<impl Into<String>>) -> Self {
+        self.project.description = description.map(Into::into);
+        self
+    }
+
+    /// Sets the start date of the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_date` - The start date of the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Utc;
+    /// use planter_core::project::Project;
+    ///
+    /// let start_date = Utc::now();
+    /// let project = Project::builder().start_date(start_date).build();
+    /// assert_eq!(project.start_date(), Some(start_date));
+    /// ```
+    pub fn start_date(mut self, start_date: DateTime<Utc>) -> Self {
+        self.project.start_date = Some(start_date);
+        self
+    }
+
+    /// Sets the start date of the project, or clears it with `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_date` - The start date of the project, if any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Utc;
+    /// use planter_core::project::Project;
+    ///
+    /// let start_date = Utc::now();
+    /// let project = Project::builder().maybe_start_date(Some(start_date)).build();
+    /// assert_eq!(project.start_date(), Some(start_date));
+    ///
+    /// let project = project.to_builder().maybe_start_date(None).build();
+    /// assert_eq!(project.start_date(), None);
+    /// ```
+    pub fn maybe_start_date(mut self, start_date: Option<DateTime<Utc>>) -> Self {
+        self.project.start_date = start_date;
+        self
+    }
+
+    /// Sets the tasks of the project, replacing the existing ones. Since task indices
+    /// change, the time and subtask relationships between the previous tasks are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `tasks` - The tasks of the project. A task whose identifier is already taken by a
+    ///   previous one, like a clone of it, gets a fresh one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let crowbar = Task::new("Find a crowbar");
+    /// let project = Project::builder()
+    ///     .tasks([crowbar.clone(), crowbar, Task::new("Profit")])
+    ///     .build();
+    /// assert_eq!(project.tasks().count(), 3);
+    /// assert_ne!(project.task(0).unwrap().id(), project.task(1).unwrap().id());
+    /// ```
+    pub fn tasks(mut self, tasks: impl IntoIterator<Item = Task>) -> Self {
+        let mut dag = Dag::new();
+        let mut ids = HashSet::new();
+        for mut task in tasks {
+            if !ids.insert(task.id()) {
+                task.renew_id();
+                ids.insert(task.id());
+            }
+            dag.add_node(task);
+        }
+        self.project.tasks = dag;
+        self.project.subtask_relationships.clear();
+        self
+    }
+
+    /// Sets the resources of the project, replacing the existing ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `resources` - The resources of the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, resources::{Material, Resource}};
+    ///
+    /// let project = Project::builder()
+    ///     .resources([Resource::Material(Material::new("Crowbar"))])
+    ///     .build();
+    /// assert_eq!(project.resources().len(), 1);
+    /// ```
+    pub fn resources(mut self, resources: impl IntoIterator<Item = Resource>) -> Self {
+        self.project.resources = resources.into_iter().collect();
+        self
+    }
+
+    /// Sets the stakeholders of the project, replacing the existing ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `stakeholders` - The stakeholders of the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{person::Person, project::Project, stakeholders::{Engagement, Level, Stakeholder}};
+    ///
+    /// let project = Project::builder()
+    ///     .stakeholders([Stakeholder::Individual {
+    ///         person: Person::new("Margherita", "Hack").unwrap(),
+    ///         description: None,
+    ///         influence: Level::High,
+    ///         interest: Level::High,
+    ///         current_engagement: Engagement::Neutral,
+    ///         desired_engagement: Engagement::Neutral,
+    ///     }])
+    ///     .build();
+    /// assert_eq!(project.stakeholders().len(), 1);
+    /// ```
+    pub fn stakeholders(mut self, stakeholders: impl IntoIterator<Item = Stakeholder>) -> Self {
+        self.project.stakeholders = stakeholders.into_iter().collect();
+        self
+    }
+
+    /// Builds the project. If no name was set, the name is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::project::Project;
+    ///
+    /// let project = Project::builder().build();
+    /// assert_eq!(project.name(), "");
+    /// assert!(project.description().is_none());
+    /// ```
+    pub fn build(self) -> Project {
+        self.project
+    }
+}
+
+impl Project {
+    /// Returns a builder for a new project, with every field set to its default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Utc;
+    /// use planter_core::project::Project;
+    ///
+    /// let start_date = Utc::now();
+    /// let project = Project::builder()
+    ///     .name("World domination")
+    ///     .description("With a crowbar and a stimpack")
+    ///     .start_date(start_date)
+    ///     .build();
+    /// assert_eq!(project.name(), "World domination");
+    /// assert_eq!(project.start_date(), Some(start_date));
+    /// ```
+    pub fn builder() -> ProjectBuilder {
+        ProjectBuilder::default()
+    }
+
+    /// Turns the project back into a builder, so that some fields can be changed before
+    /// building it again. Everything that isn't changed, like tasks and their relationships,
+    /// resources and stakeholders, carries over.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Find a crowbar"));
+    ///
+    /// let project = project.to_builder().name("Galaxy domination").build();
+    /// assert_eq!(project.name(), "Galaxy domination");
+    /// assert_eq!(project.tasks().count(), 1);
+    /// ```
+    pub fn to_builder(self) -> ProjectBuilder {
+        ProjectBuilder { project: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use crate::{
+        person::Person,
+        project::{Project, TimeRelationship},
+        resources::Resource,
+        task::Task,
+    };
+
+    #[test]
+    fn to_builder_keeps_everything_that_isnt_changed() {
+        let start_date = Utc::now();
+        let mut project = Project::builder()
+            .name("World domination")
+            .description("With a crowbar and a stimpack")
+            .start_date(start_date)
+            .tasks([
+                Task::new("Get equipped"),
+                Task::new("Find a crowbar"),
+                Task::new("Profit"),
+            ])
+            .resources([Resource::Personnel {
+                person: Person::new("Sebastiano", "Giordano").unwrap(),
+                hourly_rate: None,
+                availability: Vec::new(),
+            }])
+            .build();
+        project.add_subtask(0, 1);
+        project
+            .add_time_relationship(1, 2, TimeRelationship::FinishToStart)
+            .unwrap();
+        project.set_metadata("client", "Vault-Tec");
+
+        let project = project.to_builder().name("x").build();
+
+        assert_eq!(project.name(), "x");
+        assert_eq!(project.description(), Some("With a crowbar and a stimpack"));
+        assert_eq!(project.start_date(), Some(start_date));
+        assert_eq!(project.tasks().count(), 3);
+        assert_eq!(project.task(1).unwrap().name(), "Find a crowbar");
+        assert_eq!(project.subtasks(0), vec![1]);
+        assert_eq!(project.predecessors_indices(2).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(project.resources().len(), 1);
+        assert_eq!(project.metadata("client"), Some("Vault-Tec"));
+    }
+
+    #[test]
+    fn builder_without_fields_matches_default() {
+        let built = Project::builder().build();
+        let default = Project::default();
+
+        assert_eq!(built.name(), default.name());
+        assert_eq!(built.name(), "");
+        assert_eq!(built.description(), default.description());
+        assert_eq!(built.start_date(), default.start_date());
+        assert_eq!(built.tasks().count(), 0);
+        assert!(built.resources().is_empty());
+        assert!(built.stakeholders().is_empty());
+    }
+}
@@ -0,0 +1,278 @@
+use std::io::Read;
+
+use anyhow::{Context, bail};
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+
+use super::{Project, TimeRelationship};
+use crate::{
+    duration::PositiveDuration,
+    task::{Task, TaskStatus},
+};
+
+/// The columns of a CSV file, in the order they are written.
+const COLUMNS: [&str; 7] = [
+    "name",
+    "description",
+    "start",
+    "finish",
+    "duration-hours",
+    "completed",
+    "predecessors",
+];
+
+/// Predecessor indices are separated by this, so that they don't need quoting.
+const PREDECESSOR_SEPARATOR: char = ';';
+
+impl Project {
+    /// Exports the tasks to CSV, one row per task in index order, for spreadsheets.
+    ///
+    /// The columns are name, description, start, finish, duration-hours, completed and
+    /// predecessors. Dates are in RFC 3339 format, in UTC, and missing values are left empty.
+    /// Predecessors are the indices of the predecessors of the task, separated by `;`.
+    /// The kind and the lag of the relationships aren't exported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// let mut task = Task::new("Find a crowbar, then profit");
+    /// task.edit_duration(Duration::minutes(90).try_into().unwrap());
+    /// project.add_task(task);
+    ///
+    /// assert_eq!(
+    ///     project.to_csv(),
+    ///     "name,description,start,finish,duration-hours,completed,predecessors\n\
+    ///      \"Find a crowbar, then profit\",,,,1.5,false,\n"
+    /// );
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let mut csv = COLUMNS.join(",");
+        csv.push('\n');
+
+        for (i, task) in self.tasks().enumerate() {
+            let date = |date: Option<DateTime<Utc>>| {
+                date.map(|d| d.to_rfc3339_opts(SecondsFormat::Secs, true))
+                    .unwrap_or_default()
+            };
+            // Precision loss is irrelevant at the scale of task durations.
+            #[allow(clippy::cast_precision_loss)]
+            let hours = task.duration().map_or_else(String::new, |duration| {
+                (duration.num_milliseconds() as f64 / 3_600_000.0).to_string()
+            });
+            let predecessors = self
+                .predecessors_indices(i)
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+                .join(&PREDECESSOR_SEPARATOR.to_string());
+
+            let row = [
+                quote(task.name()),
+                quote(task.description()),
+                date(task.start()),
+                date(task.finish()),
+                hours,
+                task.completed().to_string(),
+                predecessors,
+            ];
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Imports tasks from CSV, in the format written by [`Project::to_csv`].
+    ///
+    /// The first row holds the column names, which can be in any order. Only the name column
+    /// is required. When a task has both dates, they take precedence over its duration.
+    /// Predecessors become finish to start relationships. The project has an empty name,
+    /// since CSV files don't carry one.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The source to read the CSV from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input isn't valid CSV, if the name column is missing, if a value
+    /// can't be parsed, if a task finishes before it starts, or if the predecessors reference
+    /// tasks that don't exist or form a cycle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::project::Project;
+    ///
+    /// let csv = "name,duration-hours,predecessors\n\
+    ///            Find a crowbar,2,\n\
+    ///            \"Profit, finally\",1,0\n";
+    ///
+    /// let project = Project::from_csv(csv.as_bytes()).unwrap();
+    /// assert_eq!(project.task(1).unwrap().name(), "Profit, finally");
+    /// assert_eq!(project.predecessors_indices(1).collect::<Vec<_>>(), vec![0]);
+    /// ```
+    pub fn from_csv(reader: impl Read) -> anyhow::Result<Project> {
+        let mut reader = csv::Reader::from_reader(reader);
+        let headers = reader.headers().context("Couldn't read the CSV header")?;
+        let column = |name: &str| headers.iter().position(|h| h.trim() == name);
+        let [
+            name,
+            description,
+            start,
+            finish,
+            hours,
+            completed,
+            predecessors,
+        ] = COLUMNS.map(column);
+        let name = name.context("The CSV has no name column")?;
+
+        let mut project = Project::new("");
+        let mut links = Vec::new();
+        for (i, record) in reader.records().enumerate() {
+            let record = record.with_context(|| format!("Couldn't read row {}", i + 1))?;
+            let field = |column: Option<usize>| {
+                column
+                    .and_then(|c| record.get(c))
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+            };
+            let row = || format!("Row {}", i + 1);
+
+            let mut task = Task::new(field(Some(name)).unwrap_or_default());
+            if let Some(description) = field(description) {
+                task.edit_description(description);
+            }
+            if let Some(hours) = field(hours) {
+                task.edit_duration(parse_hours(hours).with_context(row)?);
+            }
+            let start = field(start).map(parse_date).transpose().with_context(row)?;
+            let finish = field(finish)
+                .map(parse_date)
+                .transpose()
+                .with_context(row)?;
+            if let (Some(start), Some(finish)) = (start, finish)
+                && finish < start
+            {
+                bail!("{} finishes before it starts", row());
+            }
+            if let Some(start) = start {
+                task.edit_start(start).with_context(row)?;
+            }
+            if let Some(finish) = finish {
+                task.edit_finish(finish).with_context(row)?;
+            }
+            if let Some(completed) = field(completed) {
+                let completed: bool = completed
+                    .parse()
+                    .with_context(|| format!("{}: invalid completed value {completed}", row()))?;
+                if completed {
+                    task.set_status(TaskStatus::Completed);
+                }
+            }
+            if let Some(predecessors) = field(predecessors) {
+                for predecessor in predecessors.split(PREDECESSOR_SEPARATOR) {
+                    let predecessor: usize = predecessor
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("{}: invalid predecessor {predecessor}", row()))?;
+                    links.push((predecessor, i));
+                }
+            }
+            project.add_task(task);
+        }
+
+        for (predecessor, successor) in links {
+            project
+                .add_time_relationship(predecessor, successor, TimeRelationship::FinishToStart)
+                .with_context(|| format!("Row {}: invalid predecessors", successor + 1))?;
+        }
+        Ok(project)
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break.
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Parses a date in RFC 3339 format.
+fn parse_date(value: &str) -> anyhow::Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.to_utc())
+        .with_context(|| format!("Invalid date {value}"))
+}
+
+/// Parses a duration expressed in hours, possibly with a fractional part.
+fn parse_hours(value: &str) -> anyhow::Result<PositiveDuration> {
+    let hours: f64 = value
+        .parse()
+        .with_context(|| format!("Invalid duration {value}"))?;
+    if !hours.is_finite() {
+        bail!("Invalid duration {value}");
+    }
+    Duration::try_milliseconds((hours * 3_600_000.0).round() as i64)
+        .context("The duration is out of range")?
+        .try_into()
+        .with_context(|| format!("Invalid duration {value}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use crate::{
+        project::{Project, TimeRelationship},
+        task::Task,
+    };
+
+    #[test]
+    fn tasks_round_trip_through_csv() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+        let mut project = Project::new("World domination");
+        let mut task = Task::new("Find a \"crowbar\", quickly");
+        task.edit_description("Any crowbar\nwill do");
+        task.edit_start(start).unwrap();
+        task.edit_duration(Duration::minutes(45).try_into().unwrap());
+        task.toggle_completed();
+        project.add_task(task);
+        project.add_task(Task::new("Find a stimpack"));
+        project.add_task(Task::new("Profit"));
+        for predecessor in [0, 1] {
+            project
+                .add_time_relationship(predecessor, 2, TimeRelationship::FinishToStart)
+                .unwrap();
+        }
+
+        let imported = Project::from_csv(project.to_csv().as_bytes()).unwrap();
+        assert_eq!(imported.tasks().count(), 3);
+        let task = imported.task(0).unwrap();
+        assert_eq!(task.name(), "Find a \"crowbar\", quickly");
+        assert_eq!(task.description(), "Any crowbar\nwill do");
+        assert_eq!(task.start(), Some(start));
+        assert_eq!(task.finish(), Some(start + Duration::minutes(45)));
+        assert!(task.completed());
+        let mut predecessors: Vec<usize> = imported.predecessors_indices(2).collect();
+        predecessors.sort_unstable();
+        assert_eq!(predecessors, vec![0, 1]);
+    }
+
+    #[test]
+    fn invalid_rows_are_rejected() {
+        for csv in [
+            "description\nNo name column\n",
+            "name,start,finish\nBackwards,2024-01-02T00:00:00Z,2024-01-01T00:00:00Z\n",
+            "name,duration-hours\nNegative,-1\n",
+            "name,completed\nMaybe,maybe\n",
+            "name,predecessors\nMissing,3\n",
+            "name,predecessors\nItself,0\n",
+        ] {
+            assert!(Project::from_csv(csv.as_bytes()).is_err(), "{csv}");
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use std::fmt::Write;
+
+use chrono::Duration;
+use daggy::petgraph::visit::EdgeRef;
+
+use super::{
+    Project, TimeRelationship,
+    scheduling::{CriticalPathAnalysis, earliest_successor_start},
+};
+
+/// Tasks with a total float up to this value are highlighted as near critical.
+pub const NEAR_CRITICAL_FLOAT: Duration = Duration::days(1);
+
+/// The color used for critical tasks and the relationships driving them.
+const CRITICAL_COLOR: &str = "red";
+/// The color used for near critical tasks and relationships.
+const NEAR_CRITICAL_COLOR: &str = "\"#ffbf00\"";
+
+impl Project {
+    /// Renders the project as a Graphviz DOT digraph. Tasks are nodes labeled with their names,
+    /// time relationships are edges labeled with their kind, and subtask relationships are
+    /// drawn dashed. Names are escaped, so the output can always be fed to `dot`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Find a \"crowbar\""));
+    /// project.add_task(Task::new("Profit"));
+    /// project.add_time_relationship(0, 1, TimeRelationship::StartToStart).unwrap();
+    ///
+    /// let dot = project.to_dot();
+    /// assert!(dot.starts_with("digraph \"World domination\" {"));
+    /// assert!(dot.contains("0 [label=\"Find a \\\"crowbar\\\"\"];"));
+    /// assert!(dot.contains("0 -> 1 [label=\"SS\"];"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        self.render_dot(None)
+    }
+
+    /// Renders the project as a Graphviz DOT digraph, highlighting the tasks that drive the schedule.
+    ///
+    /// Tasks on the critical path, and the relationships between them that determine their dates,
+    /// are colored red. Tasks that could be delayed by at most [`NEAR_CRITICAL_FLOAT`], and the
+    /// relationships between such tasks, are colored amber. Subtask relationships are drawn dashed.
+    /// If some task has no duration the critical path can't be computed, so nothing is highlighted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// for (name, hours) in [("Find a crowbar", 2), ("Profit", 1)] {
+    ///     let mut task = Task::new(name);
+    ///     task.edit_duration(Duration::hours(hours).try_into().unwrap());
+    ///     project.add_task(task);
+    /// }
+    /// project.add_time_relationship(0, 1, TimeRelationship::FinishToStart).unwrap();
+    ///
+    /// let dot = project.to_dot_highlighted();
+    /// assert!(dot.starts_with("digraph \"World domination\" {"));
+    /// assert!(dot.contains("0 [label=\"Find a crowbar\", color=red, fontcolor=red];"));
+    /// assert!(dot.contains("0 -> 1 [label=\"FS\", color=red];"));
+    /// ```
+    pub fn to_dot_highlighted(&self) -> String {
+        self.render_dot(self.critical_path_analysis().ok().as_ref())
+    }
+
+    /// Renders the project as a DOT digraph, coloring tasks and relationships by criticality
+    /// when an analysis is given.
+    fn render_dot(&self, analysis: Option<&CriticalPathAnalysis>) -> String {
+        let criticality = |i: usize| {
+            analysis.and_then(|analysis| {
+                let float = analysis.total_float(i);
+                if float <= Duration::zero() {
+                    Some(CRITICAL_COLOR)
+                } else if float <= NEAR_CRITICAL_FLOAT {
+                    Some(NEAR_CRITICAL_COLOR)
+                } else {
+                    None
+                }
+            })
+        };
+
+        let mut dot = format!("digraph \"{}\" {{\n", escape(self.name()));
+
+        for (i, task) in self.tasks().enumerate() {
+            let _ = write!(dot, "    {i} [label=\"{}\"", escape(task.name()));
+            if let Some(color) = criticality(i) {
+                let _ = write!(dot, ", color={color}, fontcolor={color}");
+            }
+            dot.push_str("];\n");
+        }
+
+        for edge in self.tasks.graph().edge_references() {
+            let (source, target) = (edge.source().index(), edge.target().index());
+            let dependency = *edge.weight();
+            let _ = write!(
+                dot,
+                "    {source} -> {target} [label=\"{}\"",
+                short_name(dependency.kind)
+            );
+
+            let color = match (criticality(source), criticality(target), analysis) {
+                (Some(CRITICAL_COLOR), Some(CRITICAL_COLOR), Some(analysis))
+                    if earliest_successor_start(
+                        dependency,
+                        analysis.early_start[source],
+                        analysis.early_finish[source],
+                        analysis.durations[target],
+                    ) == analysis.early_start[target] =>
+                {
+                    Some(CRITICAL_COLOR)
+                }
+                (Some(_), Some(_), _) => Some(NEAR_CRITICAL_COLOR),
+                _ => None,
+            };
+            if let Some(color) = color {
+                let _ = write!(dot, ", color={color}");
+            }
+            dot.push_str("];\n");
+        }
+
+        for relationship in &self.subtask_relationships {
+            let _ = writeln!(
+                dot,
+                "    {} -> {} [style=dashed];",
+                relationship.task, relationship.subtask
+            );
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Returns the customary abbreviation of a relationship kind.
+fn short_name(kind: TimeRelationship) -> &'static str {
+    match kind {
+        TimeRelationship::FinishToStart => "FS",
+        TimeRelationship::StartToStart => "SS",
+        TimeRelationship::FinishToFinish => "FF",
+        TimeRelationship::StartToFinish => "SF",
+    }
+}
+
+/// Escapes a string, so that it can be used as a quoted DOT identifier.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use crate::{
+        project::{Project, TimeRelationship},
+        task::Task,
+    };
+
+    #[test]
+    fn to_dot_highlighted_colors_schedule_drivers() {
+        // 0 -> 1 -> 3 is the critical path (55h), 0 -> 2 -> 3 has 20h of float,
+        // 4 is independent and can slip by 54h.
+        let mut project = Project::new("World domination");
+        for hours in [24, 30, 10, 1, 1] {
+            let mut task = Task::new(format!("{hours}h"));
+            task.edit_duration(Duration::hours(hours).try_into().unwrap());
+            project.add_task(task);
+        }
+        for (predecessor, successor) in [(0, 1), (0, 2), (1, 3), (2, 3)] {
+            project
+                .add_time_relationship(predecessor, successor, TimeRelationship::FinishToStart)
+                .unwrap();
+        }
+
+        let dot = project.to_dot_highlighted();
+
+        for critical in [0, 1, 3] {
+            assert!(dot.contains(&format!("    {critical} [label=\"")));
+            let line = dot
+                .lines()
+                .find(|l| l.starts_with(&format!("    {critical} [")))
+                .unwrap();
+            assert!(line.contains("color=red"), "{line}");
+        }
+        assert!(dot.contains("2 [label=\"10h\", color=\"#ffbf00\", fontcolor=\"#ffbf00\"];"));
+        assert!(dot.contains("4 [label=\"1h\"];"));
+        assert!(dot.contains("0 -> 1 [label=\"FS\", color=red];"));
+        assert!(dot.contains("1 -> 3 [label=\"FS\", color=red];"));
+        assert!(dot.contains("0 -> 2 [label=\"FS\", color=\"#ffbf00\"];"));
+    }
+
+    #[test]
+    fn to_dot_highlighted_without_durations_has_no_colors() {
+        let mut project = Project::new("The \"plan\"");
+        project.add_task(Task::new("Find a crowbar"));
+        project.add_task(Task::new("Find a stimpack"));
+        project.add_subtask(0, 1);
+
+        let dot = project.to_dot_highlighted();
+
+        assert!(dot.starts_with("digraph \"The \\\"plan\\\"\" {"));
+        assert!(!dot.contains("color"));
+        assert!(dot.contains("0 -> 1 [style=dashed];"));
+    }
+
+    #[test]
+    fn to_dot_renders_every_relationship() {
+        let mut project = Project::new("World domination");
+        for name in ["Get equipped", "Find a crowbar\nor two", "Back\\slash"] {
+            project.add_task(Task::new(name));
+        }
+        project.add_subtask(0, 1);
+        project
+            .add_time_relationship(1, 2, TimeRelationship::FinishToFinish)
+            .unwrap();
+
+        assert_eq!(
+            project.to_dot(),
+            "digraph \"World domination\" {\n\
+             \x20   0 [label=\"Get equipped\"];\n\
+             \x20   1 [label=\"Find a crowbar\\nor two\"];\n\
+             \x20   2 [label=\"Back\\\\slash\"];\n\
+             \x20   1 -> 2 [label=\"FF\"];\n\
+             \x20   0 -> 1 [style=dashed];\n\
+             }\n"
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+
+use super::{Project, baseline::planned_cost};
+use crate::money::{Currency, Money, MoneyError};
+
+/// The earned value metrics of a project at a given date, to tell whether it's on schedule
+/// and on budget. Every value is a cost, in the currency of the hourly rates of the resources.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EarnedValue {
+    /// The budgeted cost of the work scheduled so far, according to the newest baseline.
+    pub planned_value: Money,
+    /// The budgeted cost of the work actually done so far.
+    pub earned_value: Money,
+    /// The cost of the work actually done so far.
+    pub actual_cost: Money,
+}
+
+impl EarnedValue {
+    /// Returns the schedule performance index, the earned value divided by the planned value.
+    /// Below 1 the project is behind schedule, above 1 it's ahead.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(f64)` - The schedule performance index.
+    /// * `None` - If no work was planned so far, or if the two values are in different
+    ///   currencies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{money::Money, project::EarnedValue};
+    ///
+    /// let ev = EarnedValue {
+    ///     planned_value: Money::from(200),
+    ///     earned_value: Money::from(100),
+    ///     actual_cost: Money::from(100),
+    /// };
+    /// assert_eq!(ev.spi(), Some(0.5));
+    /// ```
+    pub fn spi(&self) -> Option<f64> {
+        ratio(self.earned_value, self.planned_value)
+    }
+
+    /// Returns the cost performance index, the earned value divided by the actual cost.
+    /// Below 1 the project is over budget, above 1 it's under.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(f64)` - The cost performance index.
+    /// * `None` - If nothing was spent so far, or if the two values are in different
+    ///   currencies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{money::Money, project::EarnedValue};
+    ///
+    /// let ev = EarnedValue {
+    ///     planned_value: Money::from(200),
+    ///     earned_value: Money::from(100),
+    ///     actual_cost: Money::from(125),
+    /// };
+    /// assert_eq!(ev.cpi(), Some(0.8));
+    /// ```
+    pub fn cpi(&self) -> Option<f64> {
+        ratio(self.earned_value, self.actual_cost)
+    }
+}
+
+/// Divides two amounts, if the divisor is positive and they're in the same currency.
+fn ratio(dividend: Money, divisor: Money) -> Option<f64> {
+    let same_currency = dividend.amount() == 0 || dividend.currency() == divisor.currency();
+    (divisor.amount() > 0 && same_currency).then(|| dividend.to_major() / divisor.to_major())
+}
+
+impl Project {
+    /// Computes the earned value metrics of the project at the given date, against the newest
+    /// baseline. Tasks are matched to the baseline by identifier.
+    ///
+    /// * The planned value is the baseline cost of every task, times the share of its baseline
+    ///   dates elapsed by `as_of`. Tasks without baseline dates don't count.
+    /// * The earned value is the baseline cost of every task, times its percent complete.
+    /// * The actual cost isn't tracked separately, so it's the cost of the current plan of
+    ///   every task, given by the hourly rates of its resources, times its percent complete.
+    ///   It differs from the earned value when durations or resources changed since the
+    ///   baseline.
+    ///
+    /// Without a baseline, the planned and earned values are zero. Shares of a cost are rounded
+    /// to the hundredth.
+    ///
+    /// # Arguments
+    ///
+    /// * `as_of` - The date to compute the metrics at.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the costs of the tasks are in different currencies, or too large.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::{
+    ///     money::Money, person::Person, project::Project, resources::Resource, task::Task,
+    /// };
+    ///
+    /// let start = Utc::now();
+    /// let mut project = Project::new("World domination");
+    /// let person = project.add_resource(Resource::Personnel {
+    ///     person: Person::new("Sebastiano", "Giordano").unwrap(),
+    ///     hourly_rate: Some(Money::from(10)),
+    ///     availability: Vec::new(),
+    /// });
+    /// let mut task = Task::new("Find a crowbar");
+    /// task.add_resource(person);
+    /// task.edit_start(start).unwrap();
+    /// task.edit_duration(Duration::hours(10).try_into().unwrap());
+    /// project.add_task(task);
+    /// project.set_baseline().unwrap();
+    /// project.task_mut(0).unwrap().set_percent_complete(25);
+    ///
+    /// let ev = project.earned_value(start + Duration::hours(5)).unwrap();
+    /// assert_eq!(ev.planned_value, Money::from(50));
+    /// assert_eq!(ev.earned_value, Money::from(25));
+    /// assert_eq!(ev.spi(), Some(0.5));
+    /// ```
+    pub fn earned_value(&self, as_of: DateTime<Utc>) -> anyhow::Result<EarnedValue> {
+        let zero = Money::zero(Currency::default());
+        let mut earned_value = EarnedValue {
+            planned_value: zero,
+            earned_value: zero,
+            actual_cost: zero,
+        };
+        let baseline = self.baselines.last().map(|b| b.tasks()).unwrap_or_default();
+        for (i, task) in self.tasks().enumerate() {
+            let done = i64::from(task.percent_complete());
+            let add = |total: Money, cost: Result<Money, MoneyError>| {
+                total
+                    .checked_add(cost?)
+                    .with_context(|| format!("Couldn't add up the cost of task {i}"))
+            };
+            let cost = planned_cost(self, task)
+                .with_context(|| format!("Couldn't compute the cost of task {i}"))?;
+            earned_value.actual_cost = add(earned_value.actual_cost, share(cost, done, 100))?;
+
+            let Some(planned) = baseline.iter().find(|planned| planned.id == task.id()) else {
+                continue;
+            };
+            earned_value.earned_value =
+                add(earned_value.earned_value, share(planned.cost, done, 100))?;
+            if let (Some(start), Some(finish)) = (planned.start, planned.finish) {
+                earned_value.planned_value = add(
+                    earned_value.planned_value,
+                    elapsed_cost(planned.cost, start, finish, as_of),
+                )?;
+            }
+        }
+        Ok(earned_value)
+    }
+}
+
+/// Returns the share of `cost` matching the time between `start` and `finish` elapsed by `as_of`.
+fn elapsed_cost(
+    cost: Money,
+    start: DateTime<Utc>,
+    finish: DateTime<Utc>,
+    as_of: DateTime<Utc>,
+) -> Result<Money, MoneyError> {
+    let whole = (finish - start).num_milliseconds();
+    if as_of >= finish || whole <= 0 {
+        Ok(cost)
+    } else if as_of <= start {
+        Ok(Money::zero(cost.currency()))
+    } else {
+        share(cost, (as_of - start).num_milliseconds(), whole)
+    }
+}
+
+/// Returns `part / whole` of `cost`, rounded to the hundredth. `whole` must be positive.
+///
+/// # Errors
+///
+/// Returns an error if the result doesn't fit the amount, which can only happen if `part` is
+/// larger than `whole`.
+fn share(cost: Money, part: i64, whole: i64) -> Result<Money, MoneyError> {
+    let whole = i128::from(whole);
+    let scaled = i128::from(cost.amount()) * i128::from(part);
+    // Rounds half away from zero.
+    let amount = (scaled + scaled.signum() * whole / 2) / whole;
+    let amount = i64::try_from(amount).map_err(|_| MoneyError::Overflow)?;
+    Ok(Money::new(amount, cost.currency()))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::elapsed_cost;
+    use crate::{
+        money::{Currency, Money},
+        person::Person,
+        project::Project,
+        resources::Resource,
+        task::Task,
+    };
+
+    #[test]
+    fn earned_value_compares_progress_with_the_baseline() {
+        let start = Utc::now();
+        let mut project = Project::new("World domination");
+        let person = project.add_resource(Resource::Personnel {
+            person: Person::new("Sebastiano", "Giordano").unwrap(),
+            hourly_rate: Some(Money::from(10)),
+            availability: Vec::new(),
+        });
+        for (name, offset) in [("Find a crowbar", 0), ("Profit", 4)] {
+            let mut task = Task::new(name);
+            task.add_resource(person);
+            task.edit_start(start + Duration::hours(offset)).unwrap();
+            task.edit_duration(Duration::hours(4).try_into().unwrap());
+            project.add_task(task);
+        }
+        project.add_task(Task::new("Not in the baseline"));
+
+        let ev = project.earned_value(start).unwrap();
+        assert_eq!(ev.planned_value.amount(), 0);
+        assert_eq!(ev.earned_value.amount(), 0);
+        assert!(ev.spi().is_none() && ev.cpi().is_none());
+
+        project.set_baseline().unwrap();
+        project.task_mut(0).unwrap().set_percent_complete(100);
+        // Finishing the first task took twice as long as planned.
+        project
+            .task_mut(0)
+            .unwrap()
+            .edit_duration(Duration::hours(8).try_into().unwrap());
+        project.task_mut(2).unwrap().set_percent_complete(50);
+
+        let ev = project.earned_value(start + Duration::hours(6)).unwrap();
+        assert_eq!(ev.planned_value, Money::from(60));
+        assert_eq!(ev.earned_value, Money::from(40));
+        assert_eq!(ev.actual_cost, Money::from(80));
+        assert_eq!(ev.cpi(), Some(0.5));
+
+        let ev = project.earned_value(start + Duration::hours(20)).unwrap();
+        assert_eq!(ev.planned_value, Money::from(80));
+    }
+
+    #[test]
+    fn earned_value_keeps_the_currency_and_rejects_mixed_ones() {
+        let start = Utc::now();
+        let mut project = Project::new("World domination");
+        let mut people = Vec::new();
+        for (name, currency) in [("Sebastiano", Currency::USD), ("Mario", Currency::GBP)] {
+            people.push(project.add_resource(Resource::Personnel {
+                person: Person::new(name, "Giordano").unwrap(),
+                hourly_rate: Some(Money::new(1000, currency)),
+                availability: Vec::new(),
+            }));
+        }
+        for (name, person) in [("Find a crowbar", people[0]), ("Profit", people[1])] {
+            let mut task = Task::new(name);
+            task.add_resource(person);
+            task.edit_start(start).unwrap();
+            task.edit_duration(Duration::hours(3).try_into().unwrap());
+            project.add_task(task);
+        }
+
+        // A single currency is carried through, and shares are rounded to the hundredth.
+        let mut dollars = project.clone();
+        dollars.rm_task(1).unwrap();
+        dollars.set_baseline().unwrap();
+        let ev = dollars.earned_value(start + Duration::hours(1)).unwrap();
+        assert_eq!(ev.planned_value, Money::new(1000, Currency::USD));
+        dollars.task_mut(0).unwrap().set_percent_complete(33);
+        let ev = dollars.earned_value(start + Duration::hours(1)).unwrap();
+        assert_eq!(ev.earned_value, Money::new(990, Currency::USD));
+        assert_eq!(ev.actual_cost, Money::new(990, Currency::USD));
+
+        project.task_mut(0).unwrap().set_percent_complete(50);
+        project.task_mut(1).unwrap().set_percent_complete(50);
+        assert!(project.earned_value(start).is_err());
+    }
+
+    #[test]
+    fn sub_second_tasks_are_planned_in_milliseconds() {
+        let start = Utc::now();
+        let mut project = Project::new("World domination");
+        let mut task = Task::new("Find a crowbar");
+        task.edit_start(start).unwrap();
+        task.edit_duration(Duration::milliseconds(500).try_into().unwrap());
+        project.add_task(task);
+        project.set_baseline().unwrap();
+        assert!(
+            project
+                .earned_value(start + Duration::milliseconds(200))
+                .is_ok()
+        );
+
+        let cost = Money::from(10);
+        let finish = start + Duration::milliseconds(500);
+        let elapsed = |as_of| elapsed_cost(cost, start, finish, as_of).unwrap();
+        assert_eq!(elapsed(start + Duration::milliseconds(200)), Money::from(4));
+        // A task that takes no time is done as soon as it starts.
+        assert_eq!(elapsed_cost(cost, start, start, start).unwrap(), cost);
+    }
+}
@@ -0,0 +1,141 @@
+use daggy::petgraph::visit::EdgeRef;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::{Project, SubtaskRelationship, persistence::TimeRelationshipRepr};
+
+/// The shape of [`Project::to_frontend_json`]: flat lists, whose entries reference each
+/// other by `id`.
+#[derive(Serialize)]
+struct FrontendProject<'a> {
+    tasks: Vec<Value>,
+    edges: Vec<TimeRelationshipRepr>,
+    subtasks: &'a [SubtaskRelationship],
+    resources: Vec<Value>,
+    stakeholders: Vec<Value>,
+}
+
+impl Project {
+    /// Exports the project as JSON for frontends, in a documented and stable shape:
+    ///
+    /// ```text
+    /// { "tasks": [...], "edges": [...], "subtasks": [...], "resources": [...], "stakeholders": [...] }
+    /// ```
+    ///
+    /// Every task, resource and stakeholder carries an explicit `id`, which is its index in the
+    /// project, next to its serialized fields. Tasks also carry their identifier, the one of
+    /// [`Task::id`](crate::task::Task::id), as `uid`. Edges are the time relationships, as
+    /// `{ "predecessor", "successor", "kind", "lag" }` with the lag in milliseconds, and
+    /// subtasks are `{ "task", "subtask" }`. Assignments and roles inside the tasks reference
+    /// resources and stakeholders by `id` too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Find a crowbar"));
+    /// project.add_task(Task::new("Profit"));
+    /// project.add_time_relationship(0, 1, Default::default()).unwrap();
+    ///
+    /// let json = project.to_frontend_json();
+    /// assert_eq!(json["tasks"][1]["id"], 1);
+    /// assert_eq!(json["tasks"][1]["name"], "Profit");
+    /// assert_eq!(json["edges"][0]["predecessor"], 0);
+    /// assert_eq!(json["edges"][0]["successor"], 1);
+    /// ```
+    pub fn to_frontend_json(&self) -> Value {
+        let tasks = self
+            .tasks()
+            .enumerate()
+            .map(|(id, task)| {
+                let mut entry = entry(id, task);
+                if let Value::Object(fields) = &mut entry {
+                    fields.insert("uid".to_owned(), task.id().into());
+                }
+                entry
+            })
+            .collect();
+        let edges = self
+            .tasks
+            .graph()
+            .edge_references()
+            .map(|edge| TimeRelationshipRepr {
+                predecessor: edge.source().index(),
+                successor: edge.target().index(),
+                kind: edge.weight().kind,
+                lag: edge.weight().lag.num_milliseconds(),
+            })
+            .collect();
+        let resources = (self.resources.iter().enumerate())
+            .map(|(id, resource)| entry(id, resource))
+            .collect();
+        let stakeholders = (self.stakeholders.iter().enumerate())
+            .map(|(id, stakeholder)| entry(id, stakeholder))
+            .collect();
+
+        to_value(&FrontendProject {
+            tasks,
+            edges,
+            subtasks: &self.subtask_relationships,
+            resources,
+            stakeholders,
+        })
+    }
+}
+
+/// Serializes a value into a JSON object with an explicit `id`. Values that aren't objects,
+/// like unit variants, end up under `value`.
+fn entry(id: usize, value: &impl Serialize) -> Value {
+    let fields = match to_value(value) {
+        Value::Object(fields) => fields,
+        value => Map::from_iter([("value".to_owned(), value)]),
+    };
+    let mut entry = Map::from_iter([("id".to_owned(), id.into())]);
+    entry.extend(fields.into_iter().filter(|(key, _)| key != "id"));
+    Value::Object(entry)
+}
+
+/// Serializes a part of the project into JSON. Serializing into a [`Value`] only fails on maps
+/// whose keys aren't strings, and every map in a project is keyed by strings, so this can't
+/// fail: `Null` is only a fallback.
+fn to_value(value: &impl Serialize) -> Value {
+    serde_json::to_value(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        project::Project,
+        resources::{Material, Resource},
+        task::Task,
+    };
+
+    #[test]
+    fn entries_are_addressed_by_id() {
+        let mut project = Project::new("World domination");
+        let crowbar = Task::new("Find a crowbar");
+        let uid = crowbar.id();
+        project.add_task(crowbar);
+        project.add_task(Task::new("Profit"));
+        project
+            .add_time_relationship(0, 1, Default::default())
+            .unwrap();
+        project.add_subtask(0, 1);
+        project.add_resource(Resource::Material(Material::new("Crowbar")));
+
+        let json = project.to_frontend_json();
+
+        assert_eq!(json["tasks"][0]["id"], 0);
+        assert_eq!(json["tasks"][0]["uid"], uid);
+        assert_eq!(json["tasks"][0]["name"], "Find a crowbar");
+        assert_eq!(json["edges"][0]["kind"], "FinishToStart");
+        assert_eq!(json["edges"][0]["lag"], 0);
+        assert_eq!(json["subtasks"][0]["task"], 0);
+        assert_eq!(json["subtasks"][0]["subtask"], 1);
+        assert_eq!(json["resources"][0]["id"], 0);
+        assert!(json["resources"][0]["Material"].is_object());
+        assert_eq!(json["stakeholders"], serde_json::json!([]));
+    }
+}
@@ -0,0 +1,183 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use anyhow::bail;
+use chrono::{DateTime, Utc};
+use daggy::petgraph::{Direction, visit::EdgeRef};
+
+use super::{Project, TimeRelationship};
+
+/// A row of a Gantt chart: a task, with everything needed to draw its bar and its arrows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GanttRow {
+    /// The index of the task.
+    pub index: usize,
+    /// The name of the task.
+    pub name: String,
+    /// The start of the task.
+    pub start: Option<DateTime<Utc>>,
+    /// The finish of the task.
+    pub finish: Option<DateTime<Utc>>,
+    /// How deep the task is in the subtask hierarchy: 0 for tasks without a parent.
+    pub depth: usize,
+    /// Whether the task has subtasks, and is drawn as a summary of them.
+    pub is_summary: bool,
+    /// The indices of the predecessors of the task, with the kind of each relationship,
+    /// sorted by index.
+    pub dependencies: Vec<(usize, TimeRelationship)>,
+}
+
+impl Project {
+    /// Lays out the tasks as the rows of a Gantt chart. Every task comes after its
+    /// predecessors, and among the tasks that could come next, the one that starts first
+    /// comes first. Tasks without a start come after the dated ones, by index. Subtask
+    /// relationships pointing to tasks that don't exist are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the time relationships or the subtask relationships form a cycle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Get rich"));
+    /// project.add_task(Task::new("Find a crowbar"));
+    /// project.add_task(Task::new("Profit"));
+    /// project.add_subtask(0, 1);
+    /// project.add_subtask(0, 2);
+    /// project.add_time_relationship(2, 1, TimeRelationship::FinishToStart).unwrap();
+    ///
+    /// let rows = project.gantt_rows().unwrap();
+    /// assert_eq!(rows.iter().map(|row| row.index).collect::<Vec<_>>(), vec![0, 2, 1]);
+    /// assert!(rows[0].is_summary);
+    /// assert_eq!(rows[1].depth, 1);
+    /// assert_eq!(rows[2].dependencies, vec![(2, TimeRelationship::FinishToStart)]);
+    /// ```
+    pub fn gantt_rows(&self) -> anyhow::Result<Vec<GanttRow>> {
+        let count = self.task_count();
+        let mut depths = vec![0; count];
+        for (i, level) in self.outline()? {
+            depths[i] = level - 1;
+        }
+        let mut is_summary = vec![false; count];
+        for (task, _) in self
+            .subtask_pairs()
+            .filter(|&(_, subtask)| self.contains_task(subtask))
+        {
+            if let Some(is_summary) = is_summary.get_mut(task) {
+                *is_summary = true;
+            }
+        }
+
+        let graph = self.tasks.graph();
+        let key = |i: usize| {
+            let start = self.task(i).and_then(|task| task.start());
+            Reverse((start.is_none(), start, i))
+        };
+        let mut pending_predecessors: Vec<usize> = (0..count)
+            .map(|i| graph.edges_directed(i.into(), Direction::Incoming).count())
+            .collect();
+        let mut ready: BinaryHeap<_> = (0..count)
+            .filter(|&i| pending_predecessors[i] == 0)
+            .map(key)
+            .collect();
+
+        let mut rows = Vec::with_capacity(count);
+        while let Some(Reverse((_, _, i))) = ready.pop() {
+            for edge in graph.edges_directed(i.into(), Direction::Outgoing) {
+                let successor = edge.target().index();
+                pending_predecessors[successor] -= 1;
+                if pending_predecessors[successor] == 0 {
+                    ready.push(key(successor));
+                }
+            }
+
+            let Some(task) = self.task(i) else {
+                continue;
+            };
+            let mut dependencies: Vec<(usize, TimeRelationship)> = graph
+                .edges_directed(i.into(), Direction::Incoming)
+                .map(|edge| (edge.source().index(), edge.weight().kind))
+                .collect();
+            dependencies.sort_unstable_by_key(|(predecessor, _)| *predecessor);
+            rows.push(GanttRow {
+                index: i,
+                name: task.name().to_owned(),
+                start: task.start(),
+                finish: task.finish(),
+                depth: depths[i],
+                is_summary: is_summary[i],
+                dependencies,
+            });
+        }
+
+        if rows.len() < count {
+            bail!("The time relationships between tasks form a cycle");
+        }
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::{
+        project::{Project, TimeRelationship},
+        task::Task,
+    };
+
+    #[test]
+    fn gantt_rows_follow_relationships_then_start_dates() {
+        let start = Utc::now();
+        let mut project = Project::new("World domination");
+        for (name, offset) in [
+            ("Profit", None),
+            ("Find a stimpack", Some(2)),
+            ("Find a crowbar", Some(1)),
+            ("Get equipped", Some(0)),
+        ] {
+            let mut task = Task::new(name);
+            if let Some(offset) = offset {
+                task.edit_start(start + Duration::hours(offset)).unwrap();
+            }
+            project.add_task(task);
+        }
+        project.add_subtask(3, 1);
+        project.add_subtask(3, 2);
+        for predecessor in [1, 2] {
+            project
+                .add_time_relationship(predecessor, 0, TimeRelationship::FinishToStart)
+                .unwrap();
+        }
+
+        let rows = project.gantt_rows().unwrap();
+        let order: Vec<usize> = rows.iter().map(|row| row.index).collect();
+        assert_eq!(order, vec![3, 2, 1, 0]);
+        let depths: Vec<usize> = rows.iter().map(|row| row.depth).collect();
+        assert_eq!(depths, vec![0, 1, 1, 0]);
+        assert!(rows[0].is_summary && !rows[1].is_summary);
+        assert_eq!(rows[1].start, Some(start + Duration::hours(1)));
+        assert_eq!(
+            rows[3].dependencies,
+            vec![
+                (1, TimeRelationship::FinishToStart),
+                (2, TimeRelationship::FinishToStart)
+            ]
+        );
+    }
+
+    #[test]
+    fn dangling_subtasks_are_ignored() {
+        let mut project = Project::new("World domination");
+        project.add_task(Task::new("Find a crowbar"));
+        project.add_subtask(0, 5);
+
+        let rows = project.gantt_rows().unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].depth, 0);
+        assert!(!rows[0].is_summary);
+    }
+}
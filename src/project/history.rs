@@ -0,0 +1,274 @@
+use chrono::Duration;
+
+use super::{Dependency, Project, TimeRelationship};
+use crate::task::Task;
+
+/// A change made to a [`Project`], as recorded in its [`ChangeLog`]. Every change carries
+/// what's needed to undo it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// A task was added with the given index.
+    TaskAdded(usize),
+    /// A task was removed, and the last task took its index.
+    TaskRemoved {
+        /// The index the task had.
+        index: usize,
+        /// The removed task.
+        task: Box<Task>,
+        /// The time relationships of the task, as the index of the predecessor, the index of
+        /// the successor, the kind and the lag. Indices are the ones from before the removal.
+        relationships: Vec<(usize, usize, TimeRelationship, Duration)>,
+        /// The subtask relationships of the task, as the index of the parent task and the index
+        /// of the subtask. Indices are the ones from before the removal.
+        subtask_relationships: Vec<(usize, usize)>,
+    },
+    /// The task with the given index was renamed, from the first name to the second one.
+    TaskRenamed(usize, String, String),
+    /// A time relationship was added, or replaced an existing one between the same tasks.
+    RelationshipAdded {
+        /// The index of the predecessor.
+        predecessor: usize,
+        /// The index of the successor.
+        successor: usize,
+        /// The kind of the relationship.
+        kind: TimeRelationship,
+        /// The lag of the relationship.
+        lag: Duration,
+    },
+    /// A time relationship was removed.
+    RelationshipRemoved {
+        /// The index of the predecessor.
+        predecessor: usize,
+        /// The index of the successor.
+        successor: usize,
+        /// The kind the relationship had.
+        kind: TimeRelationship,
+        /// The lag the relationship had.
+        lag: Duration,
+    },
+}
+
+/// The changes made to a [`Project`] since its history was enabled, from the oldest to the
+/// newest. Only the changes made through the methods of the project are recorded: editing a
+/// task through [`Project::task_mut`] isn't.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeLog {
+    /// The recorded changes, from the oldest to the newest.
+    changes: Vec<Change>,
+}
+
+impl ChangeLog {
+    /// Returns the recorded changes, from the oldest to the newest.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::{Change, Project}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.enable_history();
+    /// project.add_task(Task::new("Find a crowbar"));
+    ///
+    /// let history = project.history().unwrap();
+    /// assert_eq!(history.changes(), [Change::TaskAdded(0)]);
+    /// ```
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+}
+
+impl Project {
+    /// Starts recording the changes made to the project, for undo and audit. Until it's
+    /// called, nothing is recorded and nothing is paid for it. Calling it again keeps the
+    /// changes recorded so far.
+    ///
+    /// The history isn't part of the data of the project: it's ignored when comparing
+    /// projects, and it isn't serialized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// assert!(project.history().is_none());
+    ///
+    /// project.enable_history();
+    /// assert!(project.history().unwrap().changes().is_empty());
+    /// ```
+    pub fn enable_history(&mut self) {
+        self.history.get_or_insert_default();
+    }
+
+    /// Returns the changes made to the project since its history was enabled, if it was.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::{project::{Change, Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Find a crowbar"));
+    /// project.enable_history();
+    /// project.add_task(Task::new("Profit"));
+    /// project.add_time_relationship(0, 1, TimeRelationship::FinishToStart).unwrap();
+    /// project.rename_task(1, "Profit, finally").unwrap();
+    ///
+    /// assert_eq!(
+    ///     project.history().unwrap().changes(),
+    ///     [
+    ///         Change::TaskAdded(1),
+    ///         Change::RelationshipAdded {
+    ///             predecessor: 0,
+    ///             successor: 1,
+    ///             kind: TimeRelationship::FinishToStart,
+    ///             lag: Duration::zero(),
+    ///         },
+    ///         Change::TaskRenamed(1, "Profit".to_owned(), "Profit, finally".to_owned()),
+    ///     ]
+    /// );
+    /// ```
+    pub fn history(&self) -> Option<&ChangeLog> {
+        self.history.as_ref()
+    }
+
+    /// Records a change, if the history is enabled.
+    pub(super) fn record(&mut self, change: Change) {
+        if let Some(history) = &mut self.history {
+            history.changes.push(change);
+        }
+    }
+
+    /// Records the addition of a time relationship, if the history is enabled.
+    pub(super) fn record_relationship_added(
+        &mut self,
+        predecessor: usize,
+        successor: usize,
+        dependency: Dependency,
+    ) {
+        self.record(Change::RelationshipAdded {
+            predecessor,
+            successor,
+            kind: dependency.kind,
+            lag: dependency.lag,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use crate::{
+        project::{Change, Project, TimeRelationship},
+        task::Task,
+    };
+
+    fn added(
+        predecessor: usize,
+        successor: usize,
+        kind: TimeRelationship,
+        lag: Duration,
+    ) -> Change {
+        Change::RelationshipAdded {
+            predecessor,
+            successor,
+            kind,
+            lag,
+        }
+    }
+
+    #[test]
+    fn history_follows_the_structure_of_the_project() {
+        let mut project = Project::new("World domination");
+        project.enable_history();
+        let get_rich = Task::new("Get rich");
+        project.add_tasks([get_rich.clone(), Task::new("Become world leader")]);
+        project.update_predecessors(1, &[0]).unwrap();
+        project
+            .add_time_relationship_with_lag(0, 1, TimeRelationship::StartToStart, Duration::days(1))
+            .unwrap();
+        project
+            .insert_between(0, 1, Task::new("Buy an army"))
+            .unwrap();
+        project.add_subtask(2, 0);
+        project.rm_task(0).unwrap();
+
+        let (start_to_start, day) = (TimeRelationship::StartToStart, Duration::days(1));
+        assert_eq!(
+            project.history().unwrap().changes(),
+            [
+                Change::TaskAdded(0),
+                Change::TaskAdded(1),
+                added(0, 1, TimeRelationship::FinishToStart, Duration::zero()),
+                added(0, 1, start_to_start, day),
+                Change::RelationshipRemoved {
+                    predecessor: 0,
+                    successor: 1,
+                    kind: start_to_start,
+                    lag: day,
+                },
+                Change::TaskAdded(2),
+                added(0, 2, start_to_start, day),
+                added(2, 1, start_to_start, Duration::zero()),
+                Change::TaskRemoved {
+                    index: 0,
+                    task: Box::new(get_rich),
+                    relationships: vec![(0, 2, start_to_start, day)],
+                    subtask_relationships: vec![(2, 0)],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn updating_relationships_only_records_what_changes() {
+        let mut project = Project::new("World domination");
+        project.add_tasks([
+            Task::new("Get rich"),
+            Task::new("Buy an army"),
+            Task::new("Become world leader"),
+        ]);
+        project
+            .add_time_relationship_with_lag(0, 2, TimeRelationship::StartToStart, Duration::days(1))
+            .unwrap();
+        project.update_predecessors(2, &[1]).unwrap();
+        project.enable_history();
+
+        project.update_predecessors(2, &[1]).unwrap();
+        project.update_successors(0, &[]).unwrap();
+        assert!(project.history().unwrap().changes().is_empty());
+
+        project.update_predecessors(2, &[0, 1]).unwrap();
+        project.update_successors(1, &[]).unwrap();
+        assert_eq!(
+            project.history().unwrap().changes(),
+            [
+                added(0, 2, TimeRelationship::FinishToStart, Duration::zero()),
+                Change::RelationshipRemoved {
+                    predecessor: 1,
+                    successor: 2,
+                    kind: TimeRelationship::FinishToStart,
+                    lag: Duration::zero(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn history_is_ignored_when_comparing_projects() {
+        let mut with_history = Project::new("World domination");
+        with_history.enable_history();
+        let mut without_history = Project::new("World domination");
+        for project in [&mut with_history, &mut without_history] {
+            project.add_task(Task::new("Find a crowbar"));
+            project.rename_task(0, "Profit").unwrap();
+            assert!(project.rename_task(1, "Gloat").is_err());
+        }
+
+        assert_eq!(with_history, without_history);
+        assert_eq!(with_history.history().unwrap().changes().len(), 2);
+        assert!(without_history.history().is_none());
+    }
+}
@@ -0,0 +1,302 @@
+use chrono::Duration;
+use daggy::{
+    EdgeIndex,
+    petgraph::{Direction, visit::EdgeRef},
+};
+
+use super::{Dependency, Project, TimeRelationship};
+
+/// What [`Project::normalize`] changed in a project.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NormalizationReport {
+    /// How many subtask relationships were dropped, because they referenced missing tasks,
+    /// made a task a subtask of itself or were repeated.
+    pub pruned_subtask_relationships: usize,
+    /// The `(predecessor, successor)` pairs of the finish to start relationships that were
+    /// removed, because they were already implied by other relationships.
+    pub redundant_relationships: Vec<(usize, usize)>,
+    /// How many resources were removed, because they were equal to another resource of the project.
+    pub duplicate_resources: usize,
+}
+
+impl NormalizationReport {
+    /// Returns true if normalizing didn't change anything.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::project::NormalizationReport;
+    ///
+    /// assert!(NormalizationReport::default().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.pruned_subtask_relationships == 0
+            && self.redundant_relationships.is_empty()
+            && self.duplicate_resources == 0
+    }
+}
+
+impl Project {
+    /// Cleans up the project after heavy editing, in a single call.
+    ///
+    /// It prunes the subtask relationships that don't make sense anymore, removes the
+    /// redundant time relationships and dedupes the resources. Task indices are always
+    /// contiguous, so they never need to be rebuilt.
+    ///
+    /// # Returns
+    ///
+    /// A report of everything that was changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Find a crowbar"));
+    /// project.add_task(Task::new("Find a stimpack"));
+    /// project.add_task(Task::new("Profit"));
+    /// project.add_time_relationship(0, 1, TimeRelationship::FinishToStart).unwrap();
+    /// project.add_time_relationship(1, 2, TimeRelationship::FinishToStart).unwrap();
+    /// project.add_time_relationship(0, 2, TimeRelationship::FinishToStart).unwrap();
+    ///
+    /// let report = project.normalize();
+    /// assert_eq!(report.redundant_relationships, vec![(0, 2)]);
+    /// assert!(project.normalize().is_empty());
+    /// ```
+    pub fn normalize(&mut self) -> NormalizationReport {
+        NormalizationReport {
+            pruned_subtask_relationships: self.prune_subtask_relationships(),
+            redundant_relationships: self.remove_redundant_relationships(),
+            duplicate_resources: self.dedupe_resources(),
+        }
+    }
+
+    /// Removes the subtask relationships that reference tasks that don't exist, that make
+    /// a task a subtask of itself, or that repeat another relationship.
+    ///
+    /// # Returns
+    ///
+    /// How many relationships were removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Get equipped"));
+    /// project.add_task(Task::new("Find a crowbar"));
+    /// project.add_subtask(0, 1);
+    /// project.add_subtask(0, 1);
+    /// project.add_subtask(0, 7);
+    ///
+    /// assert_eq!(project.prune_subtask_relationships(), 2);
+    /// assert_eq!(project.subtasks(0), vec![1]);
+    /// ```
+    pub fn prune_subtask_relationships(&mut self) -> usize {
+        let count = self.task_count();
+        let before = self.subtask_relationships.len();
+        let mut seen = Vec::with_capacity(before);
+
+        self.subtask_relationships.retain(|r| {
+            let pair = (r.task, r.subtask);
+            let keep = r.task < count && r.subtask < count && r.task != r.subtask;
+            if !keep || seen.contains(&pair) {
+                return false;
+            }
+            seen.push(pair);
+            true
+        });
+
+        before - self.subtask_relationships.len()
+    }
+
+    /// Removes the finish to start relationships that are already implied by a chain of
+    /// other finish to start relationships between the same tasks.
+    ///
+    /// # Returns
+    ///
+    /// The `(predecessor, successor)` pairs of the removed relationships.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Find a crowbar"));
+    /// project.add_task(Task::new("Find a stimpack"));
+    /// project.add_task(Task::new("Profit"));
+    /// project.add_time_relationship(0, 1, TimeRelationship::FinishToStart).unwrap();
+    /// project.add_time_relationship(1, 2, TimeRelationship::FinishToStart).unwrap();
+    /// project.add_time_relationship(0, 2, TimeRelationship::FinishToStart).unwrap();
+    ///
+    /// assert_eq!(project.remove_redundant_relationships(), vec![(0, 2)]);
+    /// assert_eq!(project.predecessors_indices(2).collect::<Vec<_>>(), vec![1]);
+    /// ```
+    pub fn remove_redundant_relationships(&mut self) -> Vec<(usize, usize)> {
+        let mut removed = Vec::new();
+
+        // Removing an edge changes the indices of the others, so look for one at a time.
+        while let Some(edge) = self
+            .tasks
+            .graph()
+            .edge_references()
+            .find(|edge| self.is_redundant(edge.id()))
+        {
+            let pair = (edge.source().index(), edge.target().index());
+            let id = edge.id();
+            self.tasks.remove_edge(id);
+            removed.push(pair);
+        }
+
+        removed
+    }
+
+    /// Removes the resources that are equal to a resource coming before them.
+    ///
+    /// # Returns
+    ///
+    /// How many resources were removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{person::Person, project::Project, resources::Resource};
+    ///
+    /// let person = Resource::Personnel {
+    ///     person: Person::new("Sebastiano", "Giordano").unwrap(),
+    ///     hourly_rate: None,
+    ///     availability: Vec::new(),
+    /// };
+    /// let mut project = Project::new("World domination");
+    /// project.add_resource(person.clone());
+    /// project.add_resource(person);
+    ///
+    /// assert_eq!(project.dedupe_resources(), 1);
+    /// assert_eq!(project.resources().len(), 1);
+    /// ```
+    pub fn dedupe_resources(&mut self) -> usize {
+        let before = self.resources.len();
+        let mut unique = Vec::with_capacity(before);
+        let mut new_indices = Vec::with_capacity(before);
+        for resource in self.resources.drain(..) {
+            match unique.iter().position(|r| *r == resource) {
+                Some(index) => new_indices.push(index),
+                None => {
+                    new_indices.push(unique.len());
+                    unique.push(resource);
+                }
+            }
+        }
+        self.resources = unique;
+        for task in self.tasks_mut() {
+            task.reindex_resources(|i| new_indices.get(i).copied());
+        }
+        before - self.resources.len()
+    }
+
+    /// Checks whether the edge is a finish to start relationship without lag that is implied
+    /// by another chain of finish to start relationships without leads.
+    fn is_redundant(&self, id: EdgeIndex<usize>) -> bool {
+        let graph = self.tasks.graph();
+        let is_finish_to_start =
+            |dependency: &Dependency| matches!(dependency.kind, TimeRelationship::FinishToStart);
+        let (Some(dependency), Some((source, target))) =
+            (graph.edge_weight(id), graph.edge_endpoints(id))
+        else {
+            return false;
+        };
+        if !is_finish_to_start(dependency) || !dependency.lag.is_zero() {
+            return false;
+        }
+
+        let mut visited = vec![false; graph.node_count()];
+        let mut pending = vec![source];
+        while let Some(node) = pending.pop() {
+            for edge in graph.edges_directed(node, Direction::Outgoing) {
+                if edge.id() == id
+                    || !is_finish_to_start(edge.weight())
+                    || edge.weight().lag < Duration::zero()
+                {
+                    continue;
+                }
+                let next = edge.target();
+                if next == target {
+                    return true;
+                }
+                if !visited[next.index()] {
+                    visited[next.index()] = true;
+                    pending.push(next);
+                }
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        person::Person,
+        project::{NormalizationReport, Project, TimeRelationship},
+        resources::Resource,
+        task::Task,
+    };
+
+    #[test]
+    fn normalize_reports_every_cleanup() {
+        let person = Resource::Personnel {
+            person: Person::new("Sebastiano", "Giordano").unwrap(),
+            hourly_rate: None,
+            availability: Vec::new(),
+        };
+        let mut project = Project::new("World domination");
+        project.add_resource(person.clone());
+        project.add_resource(person);
+        for name in [
+            "Get equipped",
+            "Find a crowbar",
+            "Find a stimpack",
+            "Profit",
+        ] {
+            project.add_task(Task::new(name));
+        }
+        project.add_subtask(0, 1);
+        project.add_subtask(0, 2);
+        project.add_subtask(0, 2);
+        project.add_subtask(3, 3);
+        project.add_subtask(0, 4);
+        for (predecessor, successor, kind) in [
+            (1, 2, TimeRelationship::FinishToStart),
+            (2, 3, TimeRelationship::FinishToStart),
+            (1, 3, TimeRelationship::FinishToStart),
+            (0, 1, TimeRelationship::StartToStart),
+            (0, 3, TimeRelationship::FinishToStart),
+        ] {
+            project
+                .add_time_relationship(predecessor, successor, kind)
+                .unwrap();
+        }
+
+        let report = project.normalize();
+
+        assert_eq!(
+            report,
+            NormalizationReport {
+                pruned_subtask_relationships: 3,
+                redundant_relationships: vec![(1, 3)],
+                duplicate_resources: 1,
+            }
+        );
+        assert_eq!(project.subtasks(0), vec![1, 2]);
+        assert!(project.subtasks(3).is_empty());
+        // The chain from task 0 starts with a start to start relationship, so it doesn't
+        // imply that task 0 is finished before task 3 starts.
+        let mut predecessors: Vec<usize> = project.predecessors_indices(3).collect();
+        predecessors.sort_unstable();
+        assert_eq!(predecessors, vec![0, 2]);
+        assert!(project.normalize().is_empty());
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{DateTime, Duration, Utc};
+use daggy::{Dag, petgraph::visit::EdgeRef};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use super::{Baseline, Dependency, Project, SubtaskRelationship, TimeRelationship};
+use crate::{
+    calendar::Calendar, duration::MAX_DURATION, resources::Resource, risk::Risk,
+    stakeholders::Stakeholder, task::Task,
+};
+
+/// A time relationship, as it's serialized: the tasks are referenced by index.
+#[derive(Serialize, Deserialize)]
+pub(super) struct TimeRelationshipRepr {
+    pub(super) predecessor: usize,
+    pub(super) successor: usize,
+    pub(super) kind: TimeRelationship,
+    /// The lag in milliseconds, negative for a lead.
+    #[serde(default)]
+    pub(super) lag: i64,
+}
+
+/// The serialized form of a [`Project`], borrowing from it.
+#[derive(Serialize)]
+struct ProjectRef<'a> {
+    name: &'a str,
+    description: Option<&'a str>,
+    start_date: Option<DateTime<Utc>>,
+    tasks: Vec<&'a Task>,
+    time_relationships: Vec<TimeRelationshipRepr>,
+    subtask_relationships: &'a [SubtaskRelationship],
+    resources: &'a [Resource],
+    stakeholders: &'a [Stakeholder],
+    metadata: &'a BTreeMap<String, String>,
+    baselines: &'a [Baseline],
+    calendar: Option<&'a Calendar>,
+    include_buffers: bool,
+    risks: &'a [Risk],
+}
+
+/// The serialized form of a [`Project`], to rebuild it from.
+#[derive(Deserialize)]
+struct ProjectRepr {
+    name: String,
+    description: Option<String>,
+    start_date: Option<DateTime<Utc>>,
+    tasks: Vec<Task>,
+    time_relationships: Vec<TimeRelationshipRepr>,
+    subtask_relationships: Vec<SubtaskRelationship>,
+    resources: Vec<Resource>,
+    stakeholders: Vec<Stakeholder>,
+    metadata: BTreeMap<String, String>,
+    baselines: Vec<Baseline>,
+    #[serde(default)]
+    calendar: Option<Calendar>,
+    #[serde(default)]
+    include_buffers: bool,
+    #[serde(default)]
+    risks: Vec<Risk>,
+}
+
+/// Tasks are serialized as a list, in index order, and time relationships reference them by
+/// their position in the list, so that indices stay the same once the project is deserialized.
+impl Serialize for Project {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let time_relationships = self
+            .tasks
+            .graph()
+            .edge_references()
+            .map(|edge| TimeRelationshipRepr {
+                predecessor: edge.source().index(),
+                successor: edge.target().index(),
+                kind: edge.weight().kind,
+                lag: edge.weight().lag.num_milliseconds(),
+            })
+            .collect();
+
+        ProjectRef {
+            name: &self.name,
+            description: self.description.as_deref(),
+            start_date: self.start_date,
+            tasks: self.tasks().collect(),
+            time_relationships,
+            subtask_relationships: &self.subtask_relationships,
+            resources: &self.resources,
+            stakeholders: &self.stakeholders,
+            metadata: &self.metadata,
+            baselines: &self.baselines,
+            calendar: self.calendar.as_ref(),
+            include_buffers: self.include_buffers,
+            risks: &self.risks,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Fails if a time relationship references a task that doesn't exist, or if the time
+/// relationships form a cycle.
+impl<'de> Deserialize<'de> for Project {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = ProjectRepr::deserialize(deserializer)?;
+
+        let mut tasks = Dag::new();
+        let mut ids = HashSet::new();
+        for task in repr.tasks {
+            if !ids.insert(task.id()) {
+                return Err(de::Error::custom(format!(
+                    "More than one task has the identifier {}",
+                    task.id()
+                )));
+            }
+            tasks.add_node(task);
+        }
+        for relationship in repr.time_relationships {
+            let (predecessor, successor) = (relationship.predecessor, relationship.successor);
+            if predecessor >= tasks.node_count() || successor >= tasks.node_count() {
+                return Err(de::Error::custom(format!(
+                    "The time relationship {predecessor} -> {successor} references a task that doesn't exist"
+                )));
+            }
+            if relationship.lag.unsigned_abs() > MAX_DURATION.unsigned_abs() {
+                return Err(de::Error::custom(format!(
+                    "The lag of the time relationship {predecessor} -> {successor} exceeds the maximum duration"
+                )));
+            }
+            tasks
+                .add_edge(
+                    predecessor.into(),
+                    successor.into(),
+                    Dependency {
+                        kind: relationship.kind,
+                        lag: Duration::milliseconds(relationship.lag),
+                    },
+                )
+                .map_err(|_| {
+                    de::Error::custom(format!(
+                        "The time relationship {predecessor} -> {successor} would create a cycle"
+                    ))
+                })?;
+        }
+
+        Ok(Project {
+            name: repr.name,
+            description: repr.description,
+            start_date: repr.start_date,
+            tasks,
+            subtask_relationships: repr.subtask_relationships,
+            resources: repr.resources,
+            stakeholders: repr.stakeholders,
+            metadata: repr.metadata,
+            baselines: repr.baselines,
+            calendar: repr.calendar,
+            include_buffers: repr.include_buffers,
+            risks: repr.risks,
+            history: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{
+        project::{Project, TimeRelationship, test_utils::project_graph_strategy},
+        task::Task,
+    };
+
+    proptest! {
+        #[test]
+        fn projects_round_trip_through_json(mut project in project_graph_strategy()) {
+            let count = project.tasks().count();
+            if count > 1 {
+                project.add_subtask(0, count - 1);
+            }
+            project.set_metadata("client", "Vault-Tec");
+
+            let json = serde_json::to_string(&project).unwrap();
+            let deserialized: Project = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(serde_json::to_string(&deserialized).unwrap(), json);
+            assert_eq!(deserialized, project);
+        }
+    }
+
+    #[test]
+    fn invalid_relationships_are_rejected() {
+        let mut project = Project::new("World domination");
+        project.add_task(Task::new("Find a crowbar"));
+        project.add_task(Task::new("Profit"));
+        project
+            .add_time_relationship(0, 1, TimeRelationship::FinishToStart)
+            .unwrap();
+        let json = serde_json::to_string(&project).unwrap();
+
+        let cyclic = json.replace(
+            r#""time_relationships":[{"predecessor":0,"successor":1,"kind":"FinishToStart","lag":0}]"#,
+            r#""time_relationships":[{"predecessor":0,"successor":1,"kind":"FinishToStart","lag":0},{"predecessor":1,"successor":0,"kind":"FinishToStart"}]"#,
+        );
+        assert_ne!(cyclic, json);
+        assert!(serde_json::from_str::<Project>(&cyclic).is_err());
+
+        let dangling = json.replace(r#""successor":1"#, r#""successor":7"#);
+        assert!(serde_json::from_str::<Project>(&dangling).is_err());
+
+        for lag in [i64::MIN, i64::MAX, -1_000_000_000_000] {
+            let too_long = json.replace(r#""lag":0"#, &format!(r#""lag":{lag}"#));
+            assert_ne!(too_long, json);
+            assert!(serde_json::from_str::<Project>(&too_long).is_err());
+        }
+    }
+
+    #[test]
+    fn duplicate_task_ids_are_rejected() {
+        let mut project = Project::new("World domination");
+        let crowbar = Task::new("Find a crowbar");
+        let id = crowbar.id();
+        project.add_task(crowbar);
+        let profit = Task::new("Profit");
+        let other_id = profit.id();
+        project.add_task(profit);
+        let json = serde_json::to_string(&project).unwrap();
+
+        let duplicate = json.replace(&format!(r#""id":{other_id}"#), &format!(r#""id":{id}"#));
+        assert_ne!(duplicate, json);
+        assert!(serde_json::from_str::<Project>(&duplicate).is_err());
+    }
+}
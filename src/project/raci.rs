@@ -0,0 +1,148 @@
+use super::Project;
+use crate::task::Raci;
+
+impl Project {
+    /// Lays out the roles of the stakeholders in the tasks as a RACI matrix, with a row for
+    /// every task and a column for every stakeholder, both by index. Tasks and stakeholders
+    /// without roles get an empty row or column, and roles of stakeholders that don't exist
+    /// are left out.
+    ///
+    /// # Returns
+    ///
+    /// The role of every stakeholder in every task, indexed as `matrix[task][stakeholder]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{
+    ///     project::Project,
+    ///     stakeholders::{Engagement, Level, Stakeholder},
+    ///     task::{Raci, Task},
+    /// };
+    ///
+    /// let mut project = Project::new("World domination");
+    /// for name in ["Acme", "Umbrella"] {
+    ///     project.add_stakeholder(Stakeholder::Organization {
+    ///         name: name.to_owned(),
+    ///         description: None,
+    ///         influence: Level::High,
+    ///         interest: Level::Low,
+    ///         current_engagement: Engagement::Neutral,
+    ///         desired_engagement: Engagement::Neutral,
+    ///     });
+    /// }
+    /// let mut task = Task::new("Get rich");
+    /// task.assign_raci(1, Raci::Accountable).unwrap();
+    /// project.add_task(task);
+    /// project.add_task(Task::new("Become world leader"));
+    ///
+    /// assert_eq!(
+    ///     project.raci_matrix(),
+    ///     vec![vec![None, Some(Raci::Accountable)], vec![None, None]]
+    /// );
+    /// ```
+    pub fn raci_matrix(&self) -> Vec<Vec<Option<Raci>>> {
+        let stakeholders = self.stakeholders().len();
+        self.tasks()
+            .map(|task| {
+                let mut row = vec![None; stakeholders];
+                for &(stakeholder, raci) in task.raci() {
+                    if let Some(cell) = row.get_mut(stakeholder) {
+                        *cell = Some(raci);
+                    }
+                }
+                row
+            })
+            .collect()
+    }
+
+    /// Finds the tasks that nobody is accountable for, a common gap in governance.
+    ///
+    /// # Returns
+    ///
+    /// The sorted indices of the tasks without an accountable stakeholder that exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{
+    ///     project::Project,
+    ///     stakeholders::{Engagement, Level, Stakeholder},
+    ///     task::{Raci, Task},
+    /// };
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_stakeholder(Stakeholder::Organization {
+    ///     name: "Acme".to_owned(),
+    ///     description: None,
+    ///     influence: Level::High,
+    ///     interest: Level::Low,
+    ///     current_engagement: Engagement::Neutral,
+    ///     desired_engagement: Engagement::Neutral,
+    /// });
+    /// let mut task = Task::new("Get rich");
+    /// task.assign_raci(0, Raci::Accountable).unwrap();
+    /// project.add_task(task);
+    /// let mut task = Task::new("Become world leader");
+    /// task.assign_raci(0, Raci::Responsible).unwrap();
+    /// project.add_task(task);
+    ///
+    /// assert_eq!(project.tasks_without_accountable(), vec![1]);
+    /// ```
+    pub fn tasks_without_accountable(&self) -> Vec<usize> {
+        self.raci_matrix()
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| !row.contains(&Some(Raci::Accountable)))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        project::Project,
+        stakeholders::{Engagement, Level, Stakeholder},
+        task::{Raci, Task},
+    };
+
+    #[test]
+    fn every_task_and_stakeholder_gets_a_place_in_the_matrix() {
+        let mut project = Project::new("World domination");
+        for name in ["Acme", "Umbrella", "Cyberdyne"] {
+            project.add_stakeholder(Stakeholder::Organization {
+                name: name.to_owned(),
+                description: None,
+                influence: Level::High,
+                interest: Level::Low,
+                current_engagement: Engagement::Neutral,
+                desired_engagement: Engagement::Neutral,
+            });
+        }
+        let mut task = Task::new("Get rich");
+        task.assign_raci(0, Raci::Accountable).unwrap();
+        task.assign_raci(2, Raci::Consulted).unwrap();
+        project.add_task(task);
+        let mut task = Task::new("Become world leader");
+        task.assign_raci(2, Raci::Accountable).unwrap();
+        task.assign_raci(0, Raci::Informed).unwrap();
+        project.add_task(task);
+        project.add_task(Task::new("Profit"));
+
+        assert_eq!(
+            project.raci_matrix(),
+            vec![
+                vec![Some(Raci::Accountable), None, Some(Raci::Consulted)],
+                vec![Some(Raci::Informed), None, Some(Raci::Accountable)],
+                vec![None, None, None],
+            ]
+        );
+        assert_eq!(project.tasks_without_accountable(), vec![2]);
+
+        // The accountable stakeholder of the second task is gone.
+        project.rm_stakeholder(2).unwrap();
+        assert_eq!(project.raci_matrix()[1], vec![Some(Raci::Informed), None]);
+        assert_eq!(project.tasks_without_accountable(), vec![1, 2]);
+    }
+}
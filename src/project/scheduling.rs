@@ -0,0 +1,1051 @@
+use std::{
+    cmp::Reverse,
+    ops::{Add, Sub},
+};
+
+use anyhow::{Context, bail};
+use chrono::{DateTime, Duration, Utc};
+use daggy::petgraph::{Direction, algo::toposort, unionfind::UnionFind, visit::EdgeRef};
+
+use super::{Dependency, Project, TimeRelationship, shift};
+use crate::{duration::PositiveDuration, task::Task};
+
+/// Computes the earliest a successor can start, so that the relationship with a
+/// predecessor holds, lag included.
+///
+/// # Arguments
+///
+/// * `dependency` - The relationship between the tasks.
+/// * `predecessor_start` - The start of the predecessor.
+/// * `predecessor_finish` - The finish of the predecessor.
+/// * `successor_duration` - The duration of the successor.
+pub(super) fn earliest_successor_start<T>(
+    dependency: Dependency,
+    predecessor_start: T,
+    predecessor_finish: T,
+    successor_duration: Duration,
+) -> T
+where
+    T: Add<Duration, Output = T> + Sub<Duration, Output = T>,
+{
+    let start = match dependency.kind {
+        TimeRelationship::FinishToStart => predecessor_finish,
+        TimeRelationship::StartToStart => predecessor_start,
+        TimeRelationship::FinishToFinish => predecessor_finish - successor_duration,
+        TimeRelationship::StartToFinish => predecessor_start - successor_duration,
+    };
+    start + dependency.lag
+}
+
+/// Computes the latest a predecessor can finish, so that the relationship with a
+/// successor holds, lag included.
+///
+/// # Arguments
+///
+/// * `dependency` - The relationship between the tasks.
+/// * `successor_start` - The start of the successor.
+/// * `successor_finish` - The finish of the successor.
+/// * `predecessor_duration` - The duration of the predecessor.
+pub(super) fn latest_predecessor_finish<T>(
+    dependency: Dependency,
+    successor_start: T,
+    successor_finish: T,
+    predecessor_duration: Duration,
+) -> T
+where
+    T: Add<Duration, Output = T> + Sub<Duration, Output = T>,
+{
+    let finish = match dependency.kind {
+        TimeRelationship::FinishToStart => successor_start,
+        TimeRelationship::StartToStart => successor_start + predecessor_duration,
+        TimeRelationship::FinishToFinish => successor_finish,
+        TimeRelationship::StartToFinish => successor_finish + predecessor_duration,
+    };
+    finish - dependency.lag
+}
+
+/// The outcome of the critical path method, with every value indexed like the tasks
+/// and expressed as an offset from the beginning of the project.
+pub(super) struct CriticalPathAnalysis {
+    pub(super) early_start: Vec<Duration>,
+    pub(super) early_finish: Vec<Duration>,
+    pub(super) late_start: Vec<Duration>,
+    pub(super) durations: Vec<Duration>,
+}
+
+impl CriticalPathAnalysis {
+    /// Returns how much the task with index `i` can be delayed without delaying the project.
+    pub(super) fn total_float(&self, i: usize) -> Duration {
+        self.late_start[i] - self.early_start[i]
+    }
+}
+
+impl Project {
+    /// Returns the indices of all tasks in topological order, so that every task comes after
+    /// its predecessors. The relative order of unrelated tasks is unspecified.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the relationships between tasks form a cycle, which shouldn't be
+    /// possible since they are checked when added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Profit"));
+    /// project.add_task(Task::new("Find a crowbar"));
+    /// project.add_time_relationship(1, 0, TimeRelationship::FinishToStart).unwrap();
+    ///
+    /// assert_eq!(project.tasks_topological().unwrap(), vec![1, 0]);
+    /// ```
+    pub fn tasks_topological(&self) -> anyhow::Result<Vec<usize>> {
+        match toposort(self.tasks.graph(), None) {
+            Ok(order) => Ok(order.into_iter().map(|i| i.index()).collect()),
+            Err(cycle) => bail!(
+                "A cycle was detected in the task graph at task {}",
+                cycle.node_id().index()
+            ),
+        }
+    }
+
+    /// Computes how long the project takes, from the beginning of the first task to the end
+    /// of the last one, when every task starts as soon as its relationships allow.
+    ///
+    /// It's the length of the critical path, the minimum time the project takes given the
+    /// durations and the relationships, lags included. It doesn't depend on the start date
+    /// of the project or on the dates of the tasks, so it can be computed before any of them
+    /// is set. See [`Project::longest_path_duration`] to compute it while some tasks off the
+    /// critical path still have no duration.
+    ///
+    /// Groups of tasks that aren't related to each other run in parallel, so the makespan
+    /// of a project made of independent components is the longest of their makespans.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any task has no duration, or if the result exceeds the maximum duration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// for hours in [2, 3, 4] {
+    ///     let mut task = Task::new("Task");
+    ///     task.edit_duration(Duration::hours(hours).try_into().unwrap());
+    ///     project.add_task(task);
+    /// }
+    /// project.add_time_relationship(0, 1, TimeRelationship::FinishToStart).unwrap();
+    ///
+    /// // 0 -> 1 takes 5 hours, 2 runs alongside it and takes 4.
+    /// assert_eq!(project.makespan().unwrap().num_hours(), 5);
+    /// ```
+    pub fn makespan(&self) -> anyhow::Result<PositiveDuration> {
+        let order = self.tasks_topological()?;
+        let durations = self.task_durations()?;
+        let (_, early_finish) = self.forward_pass(&order, &durations);
+        early_finish
+            .into_iter()
+            .fold(Duration::zero(), Duration::max)
+            .try_into()
+            .context("The project exceeds the maximum duration")
+    }
+
+    /// Computes the length of the longest chain of dependent tasks, the minimum time the
+    /// project takes given the durations and the relationships, lags included. Like
+    /// [`Project::makespan`], it doesn't depend on any date.
+    ///
+    /// Tasks without a duration count as taking no time while looking for the longest chain,
+    /// so only the tasks on it need one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a task on the longest chain has no duration, or if the result
+    /// exceeds the maximum duration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// for hours in [2, 3] {
+    ///     let mut task = Task::new("Task");
+    ///     task.edit_duration(Duration::hours(hours).try_into().unwrap());
+    ///     project.add_task(task);
+    /// }
+    /// project.add_task(Task::new("Not estimated yet"));
+    /// project.add_time_relationship(0, 1, TimeRelationship::FinishToStart).unwrap();
+    ///
+    /// assert!(project.makespan().is_err());
+    /// assert_eq!(project.longest_path_duration().unwrap().num_hours(), 5);
+    ///
+    /// project.add_time_relationship(1, 2, TimeRelationship::FinishToStart).unwrap();
+    /// assert!(project.longest_path_duration().is_err());
+    /// ```
+    pub fn longest_path_duration(&self) -> anyhow::Result<PositiveDuration> {
+        let order = self.tasks_topological()?;
+        let durations: Vec<Duration> = self
+            .tasks()
+            .map(|task| self.task_duration(task).unwrap_or_default())
+            .collect();
+        let (early_start, early_finish) = self.forward_pass(&order, &durations);
+
+        // Walk the longest chain back from its last task, through the predecessors that drive
+        // the start of each task. Ties go to the task that comes last, which depends on the others.
+        let mut current = order.iter().copied().max_by_key(|&i| early_finish[i]);
+        while let Some(i) = current {
+            let missing = self
+                .task(i)
+                .filter(|task| self.task_duration(task).is_none());
+            if let Some(task) = missing {
+                bail!(
+                    "Task {i} ({}) is on the longest path and has no duration",
+                    task.name()
+                );
+            }
+            current = self
+                .tasks
+                .graph()
+                .edges_directed(i.into(), Direction::Incoming)
+                .filter(|edge| {
+                    let predecessor = edge.source().index();
+                    earliest_successor_start(
+                        *edge.weight(),
+                        early_start[predecessor],
+                        early_finish[predecessor],
+                        durations[i],
+                    ) == early_start[i]
+                })
+                .map(|edge| edge.source().index())
+                .max_by_key(|&predecessor| early_finish[predecessor]);
+        }
+
+        early_finish
+            .into_iter()
+            .fold(Duration::zero(), Duration::max)
+            .try_into()
+            .context("The project exceeds the maximum duration")
+    }
+
+    /// Computes when the project finishes, if every task starts as soon as its relationships
+    /// allow from the start date of the project. With a calendar, the project only moves
+    /// forward during working time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the project has no start date, if any task has no duration, or if
+    /// the end date would be out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let start = Utc::now();
+    /// let mut project = Project::builder().name("World domination").start_date(start).build();
+    /// for hours in [2, 3] {
+    ///     let mut task = Task::new("Task");
+    ///     task.edit_duration(Duration::hours(hours).try_into().unwrap());
+    ///     project.add_task(task);
+    /// }
+    /// project.add_time_relationship(0, 1, TimeRelationship::FinishToStart).unwrap();
+    ///
+    /// assert_eq!(project.end_date().unwrap(), start + Duration::hours(5));
+    /// assert!(Project::new("World domination").end_date().is_err());
+    /// ```
+    pub fn end_date(&self) -> anyhow::Result<DateTime<Utc>> {
+        let start = self
+            .start_date
+            .context("The project has no start date to schedule from")?;
+        let makespan = self.makespan()?;
+        shift(self.calendar.as_ref(), start, *makespan)
+    }
+
+    /// Returns the critical path of the project: the chain of tasks that can't slip without
+    /// delaying the project.
+    ///
+    /// When the project is made of independent components, this is the critical path of
+    /// the component that takes the longest, see [`Project::all_critical_paths`] for the others.
+    ///
+    /// # Returns
+    ///
+    /// The indices of the tasks on the critical path, ordered from the first to the last.
+    /// Empty if the project has no tasks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any task has no duration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// for hours in [2, 3, 1] {
+    ///     let mut task = Task::new("Task");
+    ///     task.edit_duration(Duration::hours(hours).try_into().unwrap());
+    ///     project.add_task(task);
+    /// }
+    /// project.add_time_relationship(0, 1, TimeRelationship::FinishToStart).unwrap();
+    /// project.add_time_relationship(0, 2, TimeRelationship::FinishToStart).unwrap();
+    ///
+    /// assert_eq!(project.critical_path().unwrap(), vec![0, 1]);
+    /// ```
+    pub fn critical_path(&self) -> anyhow::Result<Vec<usize>> {
+        Ok(self
+            .all_critical_paths()?
+            .into_iter()
+            .next()
+            .unwrap_or_default())
+    }
+
+    /// Returns every critical path of the project, across all of its independent components.
+    ///
+    /// Tasks connected by time relationships form a component. The critical paths of a
+    /// component are the chains of tasks that can't slip without delaying the end of that
+    /// component, so every component contributes at least one path, even if it ends before
+    /// the rest of the project. Paths of components that take longer come first, ties are
+    /// broken by the lowest task index.
+    ///
+    /// # Returns
+    ///
+    /// The critical paths, each ordered from the first task to the last.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any task has no duration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// for hours in [2, 3, 4] {
+    ///     let mut task = Task::new("Task");
+    ///     task.edit_duration(Duration::hours(hours).try_into().unwrap());
+    ///     project.add_task(task);
+    /// }
+    /// project.add_time_relationship(0, 1, TimeRelationship::FinishToStart).unwrap();
+    ///
+    /// assert_eq!(project.all_critical_paths().unwrap(), vec![vec![0, 1], vec![2]]);
+    /// ```
+    pub fn all_critical_paths(&self) -> anyhow::Result<Vec<Vec<usize>>> {
+        let order = self.tasks_topological()?;
+        let durations = self.task_durations()?;
+        let (early_start, early_finish) = self.forward_pass(&order, &durations);
+
+        // Every component is analysed against its own end, not the end of the whole project.
+        let mut components = self.components();
+        let component_end = |tasks: &[usize]| {
+            tasks
+                .iter()
+                .map(|&i| early_finish[i])
+                .fold(Duration::zero(), Duration::max)
+        };
+        components.sort_by_key(|tasks| Reverse(component_end(tasks)));
+        let mut ends = vec![Duration::zero(); order.len()];
+        for tasks in &components {
+            let end = component_end(tasks);
+            tasks.iter().for_each(|&i| ends[i] = end);
+        }
+        let (late_start, _) = self.backward_pass(&order, &durations, &ends);
+
+        let graph = self.tasks.graph();
+        let critical_successors = |i: usize| -> Vec<usize> {
+            graph
+                .edges_directed(i.into(), Direction::Outgoing)
+                .filter(|edge| {
+                    let j = edge.target().index();
+                    late_start[j] == early_start[j]
+                        && earliest_successor_start(
+                            *edge.weight(),
+                            early_start[i],
+                            early_finish[i],
+                            durations[j],
+                        ) == early_start[j]
+                })
+                .map(|edge| edge.target().index())
+                .collect()
+        };
+        let mut has_critical_predecessor = vec![false; order.len()];
+        for &i in &order {
+            if late_start[i] == early_start[i] {
+                for j in critical_successors(i) {
+                    has_critical_predecessor[j] = true;
+                }
+            }
+        }
+
+        let mut paths = Vec::new();
+        for tasks in components {
+            let sources = tasks
+                .into_iter()
+                .filter(|&i| late_start[i] == early_start[i] && !has_critical_predecessor[i]);
+            for source in sources {
+                let mut pending = vec![vec![source]];
+                while let Some(path) = pending.pop() {
+                    let Some(&last) = path.last() else {
+                        continue;
+                    };
+                    let next = critical_successors(last);
+                    if next.is_empty() {
+                        paths.push(path);
+                        continue;
+                    }
+                    // Pushed in reverse, so that paths come out in index order.
+                    for j in next.into_iter().rev() {
+                        let mut longer = path.clone();
+                        longer.push(j);
+                        pending.push(longer);
+                    }
+                }
+            }
+        }
+
+        Ok(paths)
+    }
+
+    /// Computes the total float of a task: how much it can slip without delaying the
+    /// completion of the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_index` - The index of the task.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the task doesn't exist, or if any task has no duration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// for hours in [3, 1] {
+    ///     let mut task = Task::new("Task");
+    ///     task.edit_duration(Duration::hours(hours).try_into().unwrap());
+    ///     project.add_task(task);
+    /// }
+    ///
+    /// assert!(project.total_float(0).unwrap().is_zero());
+    /// assert_eq!(project.total_float(1).unwrap().num_hours(), 2);
+    /// ```
+    pub fn total_float(&self, task_index: usize) -> anyhow::Result<PositiveDuration> {
+        self.check_task_exists(task_index)?;
+        self.critical_path_analysis()?
+            .total_float(task_index)
+            .try_into()
+            .context("The float exceeds the maximum duration")
+    }
+
+    /// Computes the free float of a task: how much it can slip without delaying any of its
+    /// successors. A task without successors can slip until the completion of the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_index` - The index of the task.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the task doesn't exist, or if any task has no duration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::{project::{Project, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// for hours in [1, 3, 1] {
+    ///     let mut task = Task::new("Task");
+    ///     task.edit_duration(Duration::hours(hours).try_into().unwrap());
+    ///     project.add_task(task);
+    /// }
+    /// project.add_time_relationship(0, 2, TimeRelationship::FinishToStart).unwrap();
+    /// project.add_time_relationship(1, 2, TimeRelationship::FinishToStart).unwrap();
+    ///
+    /// // Task 2 can't start before task 1 is done, so task 0 has 2 hours of slack.
+    /// assert_eq!(project.free_float(0).unwrap().num_hours(), 2);
+    /// assert!(project.free_float(1).unwrap().is_zero());
+    /// ```
+    pub fn free_float(&self, task_index: usize) -> anyhow::Result<PositiveDuration> {
+        self.check_task_exists(task_index)?;
+        let analysis = self.critical_path_analysis()?;
+        let i = task_index;
+        let end = analysis
+            .early_finish
+            .iter()
+            .copied()
+            .fold(Duration::zero(), Duration::max);
+
+        self.tasks
+            .graph()
+            .edges_directed(i.into(), Direction::Outgoing)
+            .map(|edge| {
+                let j = edge.target().index();
+                analysis.early_start[j]
+                    - earliest_successor_start(
+                        *edge.weight(),
+                        analysis.early_start[i],
+                        analysis.early_finish[i],
+                        analysis.durations[j],
+                    )
+            })
+            .fold(end - analysis.early_finish[i], Duration::min)
+            .try_into()
+            .context("The float exceeds the maximum duration")
+    }
+
+    /// Returns an error if there is no task with index `task_index`.
+    fn check_task_exists(&self, task_index: usize) -> anyhow::Result<()> {
+        if self.task(task_index).is_none() {
+            bail!("Task {task_index} doesn't exist");
+        }
+        Ok(())
+    }
+
+    /// Groups the tasks connected by time relationships, each group sorted by index and the
+    /// groups sorted by their lowest index.
+    fn components(&self) -> Vec<Vec<usize>> {
+        let graph = self.tasks.graph();
+        let mut sets = UnionFind::new(graph.node_count());
+        for edge in graph.edge_references() {
+            sets.union(edge.source().index(), edge.target().index());
+        }
+
+        let mut components: Vec<Vec<usize>> = Vec::new();
+        let mut component_of_root: Vec<Option<usize>> = vec![None; graph.node_count()];
+        for i in 0..graph.node_count() {
+            let root = sets.find(i);
+            match component_of_root[root] {
+                Some(c) => components[c].push(i),
+                None => {
+                    component_of_root[root] = Some(components.len());
+                    components.push(vec![i]);
+                }
+            }
+        }
+        components
+    }
+}
+
+impl Project {
+    /// Runs the forward and the backward pass of the critical path method.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any task has no duration.
+    pub(super) fn critical_path_analysis(&self) -> anyhow::Result<CriticalPathAnalysis> {
+        let order = self.tasks_topological()?;
+        let durations = self.task_durations()?;
+        let (early_start, early_finish) = self.forward_pass(&order, &durations);
+        let end = early_finish
+            .iter()
+            .copied()
+            .fold(Duration::zero(), Duration::max);
+        let (late_start, _) = self.backward_pass(&order, &durations, &vec![end; order.len()]);
+
+        Ok(CriticalPathAnalysis {
+            early_start,
+            early_finish,
+            late_start,
+            durations,
+        })
+    }
+
+    /// Returns the duration of every task, indexed like the tasks. Split tasks last from
+    /// their start to their finish, pauses included. If the project includes buffers, they
+    /// are added on top, except to milestones.
+    pub(super) fn task_durations(&self) -> anyhow::Result<Vec<Duration>> {
+        self.tasks()
+            .enumerate()
+            .map(|(i, task)| {
+                self.task_duration(task)
+                    .with_context(|| format!("Task {i} ({}) has no duration", task.name()))
+            })
+            .collect()
+    }
+
+    /// Returns how long a task takes when scheduling it, buffer included if the project
+    /// includes buffers, or `None` if it has no duration.
+    fn task_duration(&self, task: &Task) -> Option<Duration> {
+        let span = task.span()?;
+        let buffer = match task.buffer() {
+            Some(buffer) if self.include_buffers && !task.is_milestone() => *buffer,
+            _ => Duration::zero(),
+        };
+        Some(*span + buffer)
+    }
+
+    /// Computes the earliest the task with index `i` can start, given the starts and
+    /// finishes already computed for its predecessors. Tasks never start before the
+    /// beginning of the project.
+    pub(super) fn earliest_start(
+        &self,
+        i: usize,
+        starts: &[Duration],
+        finishes: &[Duration],
+        durations: &[Duration],
+    ) -> Duration {
+        self.tasks
+            .graph()
+            .edges_directed(i.into(), Direction::Incoming)
+            .map(|edge| {
+                let predecessor = edge.source().index();
+                earliest_successor_start(
+                    *edge.weight(),
+                    starts[predecessor],
+                    finishes[predecessor],
+                    durations[i],
+                )
+            })
+            .fold(Duration::zero(), Duration::max)
+    }
+
+    /// Performs the forward pass of the critical path method.
+    ///
+    /// Every task starts as soon as the relationships with its predecessors allow.
+    ///
+    /// # Returns
+    ///
+    /// The early start and the early finish of every task, indexed like the tasks.
+    pub(super) fn forward_pass(
+        &self,
+        order: &[usize],
+        durations: &[Duration],
+    ) -> (Vec<Duration>, Vec<Duration>) {
+        let mut early_start = vec![Duration::zero(); durations.len()];
+        let mut early_finish = vec![Duration::zero(); durations.len()];
+
+        for &i in order {
+            early_start[i] = self.earliest_start(i, &early_start, &early_finish, durations);
+            early_finish[i] = early_start[i] + durations[i];
+        }
+
+        (early_start, early_finish)
+    }
+
+    /// Performs the backward pass of the critical path method.
+    ///
+    /// Every task finishes as late as possible, without going past its entry in `ends`,
+    /// while keeping all relationships with its successors.
+    ///
+    /// # Returns
+    ///
+    /// The late start and the late finish of every task, indexed like the tasks.
+    pub(super) fn backward_pass(
+        &self,
+        order: &[usize],
+        durations: &[Duration],
+        ends: &[Duration],
+    ) -> (Vec<Duration>, Vec<Duration>) {
+        let mut late_start = ends.to_vec();
+        let mut late_finish = ends.to_vec();
+
+        for &i in order.iter().rev() {
+            let finish = self
+                .tasks
+                .graph()
+                .edges_directed(i.into(), Direction::Outgoing)
+                .map(|edge| {
+                    let successor = edge.target().index();
+                    latest_predecessor_finish(
+                        *edge.weight(),
+                        late_start[successor],
+                        late_finish[successor],
+                        durations[i],
+                    )
+                })
+                .fold(ends[i], Duration::min);
+            late_finish[i] = finish;
+            late_start[i] = finish - durations[i];
+        }
+
+        (late_start, late_finish)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use crate::{
+        calendar::Calendar,
+        project::{Project, TimeRelationship},
+        task::Task,
+    };
+
+    #[test]
+    fn independent_chains_have_their_own_critical_paths() {
+        // 0 -> 1 -> 2 takes 6h, 3 -> 4 takes 9h, 5 is on its own and takes 1h.
+        let mut project = Project::new("World domination");
+        for hours in [1, 2, 3, 4, 5, 1] {
+            let mut task = Task::new(format!("{hours}h"));
+            task.edit_duration(Duration::hours(hours).try_into().unwrap());
+            project.add_task(task);
+        }
+        for (predecessor, successor) in [(0, 1), (1, 2), (3, 4)] {
+            project
+                .add_time_relationship(predecessor, successor, TimeRelationship::FinishToStart)
+                .unwrap();
+        }
+
+        assert_eq!(*project.makespan().unwrap(), Duration::hours(9));
+        assert_eq!(project.critical_path().unwrap(), vec![3, 4]);
+        assert_eq!(
+            project.all_critical_paths().unwrap(),
+            vec![vec![3, 4], vec![0, 1, 2], vec![5]]
+        );
+    }
+
+    #[test]
+    fn empty_projects_have_no_critical_path() {
+        let project = Project::new("World domination");
+
+        assert!(project.makespan().unwrap().is_zero());
+        assert!(project.critical_path().unwrap().is_empty());
+        assert!(project.all_critical_paths().unwrap().is_empty());
+    }
+
+    #[test]
+    fn critical_path_follows_the_longest_chain() {
+        // 0 -> 1 -> 3 takes 8h, 0 -> 2 -> 3 takes 5h.
+        let mut project = Project::new("World domination");
+        for hours in [2, 5, 2, 1] {
+            let mut task = Task::new(format!("{hours}h"));
+            task.edit_duration(Duration::hours(hours).try_into().unwrap());
+            project.add_task(task);
+        }
+        for (predecessor, successor) in [(0, 1), (0, 2), (1, 3), (2, 3)] {
+            project
+                .add_time_relationship(predecessor, successor, TimeRelationship::FinishToStart)
+                .unwrap();
+        }
+
+        assert_eq!(project.critical_path().unwrap(), vec![0, 1, 3]);
+        assert_eq!(*project.makespan().unwrap(), Duration::hours(8));
+    }
+
+    #[test]
+    fn critical_path_reports_tasks_without_duration() {
+        let mut project = Project::new("World domination");
+        let mut task = Task::new("Find a crowbar");
+        task.edit_duration(Duration::hours(1).try_into().unwrap());
+        project.add_task(task);
+        project.add_task(Task::new("Find a stimpack"));
+
+        let error = project.critical_path().unwrap_err().to_string();
+        assert!(
+            error.contains("Task 1 (Find a stimpack) has no duration"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn floats_measure_slack_against_project_and_successors() {
+        // 0 -> 1 -> 3 is critical (6h), 2 -> 3 leaves task 2 with 4h of slack,
+        // task 4 only feeds task 5 which is on its own branch.
+        let mut project = Project::new("World domination");
+        for hours in [2, 3, 1, 1, 1, 1] {
+            let mut task = Task::new(format!("{hours}h"));
+            task.edit_duration(Duration::hours(hours).try_into().unwrap());
+            project.add_task(task);
+        }
+        for (predecessor, successor) in [(0, 1), (1, 3), (2, 3), (4, 5)] {
+            project
+                .add_time_relationship(predecessor, successor, TimeRelationship::FinishToStart)
+                .unwrap();
+        }
+
+        let total = |i| *project.total_float(i).unwrap();
+        let free = |i| *project.free_float(i).unwrap();
+        for critical in [0, 1, 3] {
+            assert!(total(critical).is_zero());
+            assert!(free(critical).is_zero());
+        }
+        assert_eq!(total(2), Duration::hours(4));
+        assert_eq!(free(2), Duration::hours(4));
+        // Task 4 can slip 4h overall, but any slip delays task 5.
+        assert_eq!(total(4), Duration::hours(4));
+        assert!(free(4).is_zero());
+        assert_eq!(free(5), Duration::hours(4));
+
+        assert!(project.total_float(6).is_err());
+        project.add_task(Task::new("Profit"));
+        assert!(project.free_float(0).is_err());
+    }
+
+    #[test]
+    fn lag_delays_the_successor() {
+        // 0 -> 1 with 2 days of lag, 0 -> 2 without: 2 has all the slack.
+        let mut project = Project::new("World domination");
+        for hours in [4, 3, 3] {
+            let mut task = Task::new(format!("{hours}h"));
+            task.edit_duration(Duration::hours(hours).try_into().unwrap());
+            project.add_task(task);
+        }
+        project
+            .add_time_relationship_with_lag(
+                0,
+                1,
+                TimeRelationship::FinishToStart,
+                Duration::days(2),
+            )
+            .unwrap();
+        project
+            .add_time_relationship(0, 2, TimeRelationship::FinishToStart)
+            .unwrap();
+
+        assert_eq!(
+            *project.makespan().unwrap(),
+            Duration::hours(4) + Duration::days(2) + Duration::hours(3)
+        );
+        assert_eq!(project.critical_path().unwrap(), vec![0, 1]);
+        assert_eq!(*project.total_float(2).unwrap(), Duration::days(2));
+    }
+
+    #[test]
+    fn lead_pulls_the_successor_earlier() {
+        // 0 -> 1 and 2 -> 3 with 2 days of lead: 1 overlaps 0, 3 can't start before the project.
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+        let mut project = Project::builder()
+            .name("World domination")
+            .start_date(start)
+            .build();
+        for days in [3, 2, 1, 1] {
+            let mut task = Task::new(format!("{days}d"));
+            task.edit_duration(Duration::days(days).try_into().unwrap());
+            project.add_task(task);
+        }
+        for (predecessor, successor) in [(0, 1), (2, 3)] {
+            project
+                .add_time_relationship_with_lag(
+                    predecessor,
+                    successor,
+                    TimeRelationship::FinishToStart,
+                    Duration::days(-2),
+                )
+                .unwrap();
+        }
+
+        assert_eq!(*project.makespan().unwrap(), Duration::days(3));
+        project.schedule().unwrap();
+        assert_eq!(
+            project.task(1).unwrap().start(),
+            Some(start + Duration::days(1))
+        );
+        assert_eq!(project.task(3).unwrap().start(), Some(start));
+    }
+
+    #[test]
+    fn end_date_follows_the_calendar() {
+        // Monday 9:00, two 6 hours tasks in a row end on Tuesday at 13:00.
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+        let mut project = Project::builder()
+            .name("World domination")
+            .start_date(start)
+            .build()
+            .with_calendar(Calendar::default());
+        for _ in 0..2 {
+            let mut task = Task::new("6h");
+            task.edit_duration(Duration::hours(6).try_into().unwrap());
+            project.add_task(task);
+        }
+        project
+            .add_time_relationship(0, 1, TimeRelationship::FinishToStart)
+            .unwrap();
+        assert_eq!(
+            project.end_date().unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 2, 13, 0, 0).unwrap()
+        );
+
+        project.add_task(Task::new("No duration"));
+        assert!(project.end_date().is_err());
+    }
+
+    #[test]
+    fn successors_wait_for_the_last_segment() {
+        // 0 is split after a day and paused for two, so 1 starts after 4 days instead of 2.
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+        let mut project = Project::builder()
+            .name("World domination")
+            .start_date(start)
+            .build();
+        for days in [2, 1] {
+            let mut task = Task::new(format!("{days}d"));
+            task.edit_duration(Duration::days(days).try_into().unwrap());
+            project.add_task(task);
+        }
+        project
+            .add_time_relationship(0, 1, TimeRelationship::FinishToStart)
+            .unwrap();
+        project.schedule().unwrap();
+
+        let task = project.task_mut(0).unwrap();
+        task.split_at(start + Duration::days(1)).unwrap();
+        task.delay_segment(1, Duration::days(2).try_into().unwrap())
+            .unwrap();
+        project.schedule().unwrap();
+
+        let task = project.task(0).unwrap();
+        assert_eq!(*task.duration().unwrap(), Duration::days(2));
+        assert_eq!(task.segments().len(), 2);
+        assert_eq!(
+            project.task(1).unwrap().start(),
+            Some(start + Duration::days(4))
+        );
+    }
+
+    #[test]
+    fn every_relationship_kind_constrains_the_successor() {
+        // 0 (2h) -> 1 (3h) finish to start, then 1 -> 2 (1h) with the kind under test.
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+        let hours = Duration::hours;
+        for (kind, successor_start) in [
+            (TimeRelationship::FinishToStart, hours(5)),
+            (TimeRelationship::StartToStart, hours(2)),
+            (TimeRelationship::FinishToFinish, hours(4)),
+            (TimeRelationship::StartToFinish, hours(1)),
+        ] {
+            let mut project = Project::builder()
+                .name("World domination")
+                .start_date(start)
+                .build();
+            for duration in [2, 3, 1] {
+                let mut task = Task::new(format!("{duration}h"));
+                task.edit_duration(hours(duration).try_into().unwrap());
+                project.add_task(task);
+            }
+            project
+                .add_time_relationship(0, 1, TimeRelationship::FinishToStart)
+                .unwrap();
+            project.add_time_relationship(1, 2, kind).unwrap();
+            project.schedule().unwrap();
+
+            let predecessor = project.task(1).unwrap();
+            let successor = project.task(2).unwrap();
+            assert_eq!(predecessor.start(), Some(start + hours(2)), "{kind:?}");
+            assert_eq!(successor.start(), Some(start + successor_start), "{kind:?}");
+            let (successor_date, predecessor_date) = match kind {
+                TimeRelationship::FinishToStart => (successor.start(), predecessor.finish()),
+                TimeRelationship::StartToStart => (successor.start(), predecessor.start()),
+                TimeRelationship::FinishToFinish => (successor.finish(), predecessor.finish()),
+                TimeRelationship::StartToFinish => (successor.finish(), predecessor.start()),
+            };
+            assert_eq!(successor_date, predecessor_date, "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn longest_path_only_needs_its_own_durations() {
+        // 0 (2h) -> 1 (3h) and 0 -> 2 (not estimated), 3 (4h) with a 2h lead on 4 (not estimated).
+        // 0 -> 1 is the longest chain, 2 and 4 finish before 1.
+        let mut project = Project::new("World domination");
+        for hours in [Some(2), Some(3), None, Some(4), None] {
+            let mut task = Task::new("Task");
+            if let Some(hours) = hours {
+                task.edit_duration(Duration::hours(hours).try_into().unwrap());
+            }
+            project.add_task(task);
+        }
+        project
+            .add_time_relationship(0, 1, TimeRelationship::FinishToStart)
+            .unwrap();
+        project
+            .add_time_relationship(0, 2, TimeRelationship::FinishToStart)
+            .unwrap();
+        project
+            .add_time_relationship_with_lag(
+                3,
+                4,
+                TimeRelationship::FinishToStart,
+                Duration::hours(-2),
+            )
+            .unwrap();
+        assert_eq!(
+            *project.longest_path_duration().unwrap(),
+            Duration::hours(5)
+        );
+
+        // Now 4 waits for 1, so the longest chain ends with it, and it has no duration.
+        project
+            .add_time_relationship(1, 4, TimeRelationship::FinishToStart)
+            .unwrap();
+        let error = project.longest_path_duration().unwrap_err().to_string();
+        assert!(error.contains("Task 4"), "{error}");
+
+        project
+            .task_mut(4)
+            .unwrap()
+            .edit_duration(Duration::hours(1).try_into().unwrap());
+        assert_eq!(
+            *project.longest_path_duration().unwrap(),
+            Duration::hours(6)
+        );
+        assert!(project.makespan().is_err());
+    }
+
+    #[test]
+    fn successors_wait_for_included_buffers() {
+        let start = Utc::now();
+        let mut project = Project::builder().start_date(start).build();
+        for name in ["Find a crowbar", "Profit"] {
+            let mut task = Task::new(name);
+            task.edit_duration(Duration::hours(4).try_into().unwrap());
+            task.edit_buffer(Duration::hours(1).try_into().unwrap());
+            project.add_task(task);
+        }
+        project
+            .add_time_relationship(0, 1, TimeRelationship::FinishToStart)
+            .unwrap();
+        assert_eq!(*project.makespan().unwrap(), Duration::hours(8));
+
+        project.set_include_buffers(true);
+        assert_eq!(*project.makespan().unwrap(), Duration::hours(10));
+        project.schedule().unwrap();
+        let dates = |i| {
+            let task = project.task(i).unwrap();
+            (
+                task.start().unwrap() - start,
+                task.finish().unwrap() - start,
+            )
+        };
+        assert_eq!(dates(0), (Duration::zero(), Duration::hours(4)));
+        assert_eq!(dates(1), (Duration::hours(5), Duration::hours(9)));
+        assert_eq!(
+            project.task(1).unwrap().duration().unwrap(),
+            Duration::hours(4).try_into().unwrap()
+        );
+
+        // June 2nd 2025 is a Monday, work happens from 9:00 to 17:00.
+        let monday = Utc.with_ymd_and_hms(2025, 6, 2, 9, 0, 0).unwrap();
+        let mut project = project
+            .to_builder()
+            .start_date(monday)
+            .build()
+            .with_calendar(Calendar::default());
+        project.schedule().unwrap();
+        let dates = |i| {
+            let task = project.task(i).unwrap();
+            (task.start().unwrap(), task.finish().unwrap())
+        };
+        assert_eq!(dates(0), (monday, monday + Duration::hours(4)));
+        assert_eq!(
+            dates(1),
+            (monday + Duration::hours(5), monday + Duration::hours(25))
+        );
+    }
+}
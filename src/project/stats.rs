@@ -0,0 +1,99 @@
+use super::Project;
+
+/// A summary of the size and the shape of a project, for dashboards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProjectStats {
+    /// How many tasks the project has.
+    pub task_count: usize,
+    /// How many time relationships there are between the tasks.
+    pub relationship_count: usize,
+    /// How many tasks are milestones.
+    pub milestone_count: usize,
+    /// How many tasks the longest chain of time relationships goes through. It's 1 if no
+    /// tasks are related, and 0 if there are no tasks.
+    pub max_depth: usize,
+    /// How many tasks have neither predecessors nor successors.
+    pub isolated_task_count: usize,
+    /// How many tasks are completed.
+    pub completed_count: usize,
+}
+
+impl Project {
+    /// Summarizes the size and the shape of the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::{Project, ProjectStats, TimeRelationship}, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// for name in ["Get rich", "Buy an army", "Become world leader", "Gloat"] {
+    ///     project.add_task(Task::new(name));
+    /// }
+    /// project.task_mut(2).unwrap().set_milestone(true);
+    /// project.add_time_relationship(0, 1, TimeRelationship::default()).unwrap();
+    /// project.add_time_relationship(1, 2, TimeRelationship::default()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     project.stats(),
+    ///     ProjectStats {
+    ///         task_count: 4,
+    ///         relationship_count: 2,
+    ///         milestone_count: 1,
+    ///         max_depth: 3,
+    ///         isolated_task_count: 1,
+    ///         completed_count: 0,
+    ///     }
+    /// );
+    /// ```
+    pub fn stats(&self) -> ProjectStats {
+        // The graph is acyclic, so there is always a topological order.
+        let order = self.tasks_topological().unwrap_or_default();
+        let mut depths = vec![1; order.len()];
+        for &i in &order {
+            for successor in self.successors_indices(i) {
+                depths[successor] = depths[successor].max(depths[i] + 1);
+            }
+        }
+
+        ProjectStats {
+            task_count: self.tasks.node_count(),
+            relationship_count: self.tasks.edge_count(),
+            milestone_count: self.tasks().filter(|task| task.is_milestone()).count(),
+            max_depth: depths.into_iter().max().unwrap_or(0),
+            isolated_task_count: self.isolated_tasks().len(),
+            completed_count: self.tasks().filter(|task| task.completed()).count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        project::{Project, ProjectStats, TimeRelationship},
+        task::Task,
+    };
+
+    #[test]
+    fn max_depth_follows_the_longest_chain() {
+        assert_eq!(Project::new("Empty").stats(), ProjectStats::default());
+
+        // 0 -> 1 -> 2 -> 3 and 0 -> 3 go through at most 4 tasks, 4 -> 5 through 2.
+        let mut project = Project::new("World domination");
+        for i in 0..7 {
+            project.add_task(Task::new(i.to_string()));
+        }
+        for (predecessor, successor) in [(0, 3), (0, 1), (1, 2), (2, 3), (4, 5)] {
+            project
+                .add_time_relationship(predecessor, successor, TimeRelationship::FinishToStart)
+                .unwrap();
+        }
+        project.task_mut(4).unwrap().toggle_completed();
+
+        let stats = project.stats();
+        assert_eq!(stats.max_depth, 4);
+        assert_eq!(stats.relationship_count, 5);
+        assert_eq!(stats.isolated_task_count, 1);
+        assert_eq!(stats.completed_count, 1);
+    }
+}
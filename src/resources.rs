@@ -1,6 +1,13 @@
-use crate::person::Person;
+use anyhow::bail;
+use chrono::{DateTime, Utc};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+use crate::{money::Money, person::Person};
+
+/// A time interval when a resource can be used, from its start to its finish.
+pub type AvailabilityWindow = (DateTime<Utc>, DateTime<Utc>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents a resource that can be used in a project. A resource can be either a material or personnel.
 pub enum Resource {
     /// Represents a material resource that can be used in a project.
@@ -10,11 +17,15 @@ pub enum Resource {
         /// Information about the person.
         person: Person,
         /// Hourly rate of the person.
-        hourly_rate: Option<u16>,
+        hourly_rate: Option<Money>,
+        /// When the person can work, sorted and without overlaps. Empty means always.
+        #[cfg_attr(feature = "serde", serde(default))]
+        availability: Vec<AvailabilityWindow>,
     },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents a material resource that can be used in a project.
 /// It can be either consumable or non-consumable.
 pub enum Material {
@@ -30,7 +41,8 @@ impl Default for Material {
     }
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents a consumable material resource that can be used in a project.
 pub struct Consumable {
     /// Name of the consumable material.
@@ -38,10 +50,11 @@ pub struct Consumable {
     /// Available quantity of the consumable material.
     quantity: Option<u16>,
     /// Cost to buy this material.
-    cost_per_unit: Option<u16>,
+    cost_per_unit: Option<Money>,
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents a non-consumable material resource that can be used in a project.
 pub struct NonConsumable {
     /// Name of the non-consumable material.
@@ -49,9 +62,12 @@ pub struct NonConsumable {
     /// Available quantity of the non-consumable material.
     quantity: Option<u16>,
     /// Cost to buy this material.
-    cost_per_unit: Option<u16>,
+    cost_per_unit: Option<Money>,
     /// Some non consumable materials can have a hourly rate. For example, due to energy consumption.
-    hourly_rate: Option<u16>,
+    hourly_rate: Option<Money>,
+    /// When the material can be used, sorted and without overlaps. Empty means always.
+    #[cfg_attr(feature = "serde", serde(default))]
+    availability: Vec<AvailabilityWindow>,
 }
 
 impl From<NonConsumable> for Consumable {
@@ -59,7 +75,7 @@ impl From<NonConsumable> for Consumable {
         Consumable {
             name: value.name,
             quantity: value.quantity,
-            cost_per_unit: value.quantity,
+            cost_per_unit: value.cost_per_unit,
         }
     }
 }
@@ -71,6 +87,7 @@ impl From<Consumable> for NonConsumable {
             quantity: value.quantity,
             cost_per_unit: value.cost_per_unit,
             hourly_rate: None,
+            availability: Vec::new(),
         }
     }
 }
@@ -81,6 +98,66 @@ impl Material {
         Material::Consumable(Consumable::new(name))
     }
 
+    /// Whether the material is consumable.
+    /// # Example
+    /// ```
+    /// use planter_core::resources::Material;
+    ///
+    /// let material = Material::new("Stimpack");
+    /// assert!(material.is_consumable());
+    /// assert!(!material.into_non_consumable().is_consumable());
+    /// ```
+    pub fn is_consumable(&self) -> bool {
+        matches!(self, Material::Consumable(_))
+    }
+
+    /// Whether the material is non-consumable.
+    /// # Example
+    /// ```
+    /// use planter_core::resources::Material;
+    ///
+    /// let material = Material::new("Crowbar");
+    /// assert!(!material.is_non_consumable());
+    /// assert!(material.into_non_consumable().is_non_consumable());
+    /// ```
+    pub fn is_non_consumable(&self) -> bool {
+        matches!(self, Material::NonConsumable(_))
+    }
+
+    /// Turns the material into a consumable one, keeping its name, quantity and cost per unit.
+    /// Consumable materials are returned as they are.
+    /// # Example
+    /// ```
+    /// use planter_core::resources::{Material, NonConsumable};
+    ///
+    /// let material = Material::NonConsumable(NonConsumable::new("Stimpack")).into_consumable();
+    /// assert!(material.is_consumable());
+    /// assert_eq!(material.name(), "Stimpack");
+    /// ```
+    pub fn into_consumable(self) -> Material {
+        match self {
+            Material::Consumable(_) => self,
+            Material::NonConsumable(non_consumable) => Material::Consumable(non_consumable.into()),
+        }
+    }
+
+    /// Turns the material into a non-consumable one, keeping its name, quantity and cost per
+    /// unit. Non-consumable materials are returned as they are.
+    /// # Example
+    /// ```
+    /// use planter_core::resources::Material;
+    ///
+    /// let material = Material::new("Crowbar").into_non_consumable();
+    /// assert!(material.is_non_consumable());
+    /// assert_eq!(material.name(), "Crowbar");
+    /// ```
+    pub fn into_non_consumable(self) -> Material {
+        match self {
+            Material::Consumable(consumable) => Material::NonConsumable(consumable.into()),
+            Material::NonConsumable(_) => self,
+        }
+    }
+
     /// Returns the name of the material.
     /// # Example
     /// ```
@@ -168,23 +245,23 @@ impl Material {
     /// let material = Material::new("Steel".to_owned());
     /// assert_eq!(material.cost_per_unit(), None);
     /// ```
-    pub fn cost_per_unit(&self) -> Option<u16> {
+    pub fn cost_per_unit(&self) -> Option<Money> {
         match self {
             Material::Consumable(consumable) => consumable.cost_per_unit,
             Material::NonConsumable(non_consumable) => non_consumable.cost_per_unit,
         }
     }
 
-    /// Updates the cost per unit of the material.
+    /// Sets the cost per unit of the material.
     /// # Example
     /// ```
-    /// use planter_core::resources::Material;
+    /// use planter_core::{money::{Currency, Money}, resources::Material};
     ///
     /// let mut material = Material::new("Steel".to_owned());
-    /// material.update_cost_per_unit(3);
-    /// assert_eq!(material.cost_per_unit(), Some(3));
+    /// material.set_cost_per_unit(Money::new(350, Currency::USD));
+    /// assert_eq!(material.cost_per_unit(), Some(Money::new(350, Currency::USD)));
     /// ```
-    pub fn update_cost_per_unit(&mut self, cost_per_unit: u16) {
+    pub fn set_cost_per_unit(&mut self, cost_per_unit: Money) {
         match self {
             Material::Consumable(consumable) => consumable.cost_per_unit = Some(cost_per_unit),
             Material::NonConsumable(non_consumable) => {
@@ -193,6 +270,19 @@ impl Material {
         }
     }
 
+    /// Updates the cost per unit of the material, in whole units of the default currency.
+    /// # Example
+    /// ```
+    /// use planter_core::{money::Money, resources::Material};
+    ///
+    /// let mut material = Material::new("Steel".to_owned());
+    /// material.update_cost_per_unit(3);
+    /// assert_eq!(material.cost_per_unit(), Some(Money::from(3)));
+    /// ```
+    pub fn update_cost_per_unit(&mut self, cost_per_unit: u16) {
+        self.set_cost_per_unit(cost_per_unit.into());
+    }
+
     /// Remove the cost per unit of the material.
     /// # Example
     /// ```
@@ -200,7 +290,7 @@ impl Material {
     ///
     /// let mut material = Material::new("Steel".to_owned());
     /// material.update_cost_per_unit(3);
-    /// assert_eq!(material.cost_per_unit(), Some(3));
+    /// assert!(material.cost_per_unit().is_some());
     /// material.remove_cost_per_unit();
     /// assert_eq!(material.cost_per_unit(), None);
     /// ```
@@ -210,6 +300,199 @@ impl Material {
             Material::NonConsumable(non_consumable) => non_consumable.cost_per_unit = None,
         }
     }
+
+    /// Returns the cost of all the units of the material, if both the quantity and the cost
+    /// per unit are known.
+    /// # Example
+    /// ```
+    /// use planter_core::{money::{Currency, Money}, resources::Material};
+    ///
+    /// let mut material = Material::new("Steel".to_owned());
+    /// material.update_quantity(u16::MAX);
+    /// assert_eq!(material.total_cost(), None);
+    ///
+    /// material.set_cost_per_unit(Money::new(250, Currency::USD));
+    /// assert_eq!(
+    ///     material.total_cost(),
+    ///     Some(Money::new(250 * i64::from(u16::MAX), Currency::USD))
+    /// );
+    /// ```
+    pub fn total_cost(&self) -> Option<Money> {
+        self.cost_per_unit()?
+            .checked_mul(self.quantity()?.into())
+            .ok()
+    }
+}
+
+impl Resource {
+    /// Returns what the resource costs when used for the given hours. Personnel and
+    /// non-consumable materials cost their hourly rate for every hour, consumable materials
+    /// cost their [`Material::total_cost`], whatever the hours.
+    ///
+    /// # Arguments
+    ///
+    /// * `hours` - How long the resource is used.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Money)` - The cost of the resource.
+    /// * `None` - If the hourly rate, or the quantity or cost per unit of a consumable, is
+    ///   missing, or if the cost is too large.
+    ///
+    /// # Example
+    /// ```
+    /// use planter_core::{
+    ///     money::Money,
+    ///     person::Person,
+    ///     resources::{Material, NonConsumable, Resource},
+    /// };
+    ///
+    /// let personnel = Resource::Personnel {
+    ///     person: Person::new("Margherita", "Hack").unwrap(),
+    ///     hourly_rate: Some(Money::from(40)),
+    ///     availability: Vec::new(),
+    /// };
+    /// assert_eq!(personnel.total_cost(8), Some(Money::from(320)));
+    ///
+    /// let crowbar = Resource::Material(Material::NonConsumable(NonConsumable::new("Crowbar")));
+    /// assert_eq!(crowbar.total_cost(8), None);
+    /// ```
+    pub fn total_cost(&self, hours: u32) -> Option<Money> {
+        let hourly_rate = match self {
+            Resource::Personnel { hourly_rate, .. } => (*hourly_rate)?,
+            Resource::Material(Material::NonConsumable(non_consumable)) => {
+                non_consumable.hourly_rate?
+            }
+            Resource::Material(material) => return material.total_cost(),
+        };
+        hourly_rate.checked_mul(hours.into()).ok()
+    }
+
+    /// Returns when the resource can be used. Consumable materials are always available,
+    /// so they have no windows.
+    ///
+    /// # Returns
+    ///
+    /// The availability windows, sorted and without overlaps. Empty if the resource is always
+    /// available.
+    ///
+    /// # Example
+    /// ```
+    /// use planter_core::resources::{Material, Resource};
+    ///
+    /// let stimpack = Resource::Material(Material::new("Stimpack"));
+    /// assert!(stimpack.availability().is_empty());
+    /// ```
+    pub fn availability(&self) -> &[AvailabilityWindow] {
+        match self {
+            Resource::Personnel { availability, .. } => availability,
+            Resource::Material(Material::NonConsumable(non_consumable)) => {
+                non_consumable.availability()
+            }
+            Resource::Material(Material::Consumable(_)) => &[],
+        }
+    }
+
+    /// Makes the resource available from `start` to `finish`. Windows that overlap or touch
+    /// are merged. Once a resource has a window, it's only available within its windows.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - When the resource becomes available.
+    /// * `finish` - When the resource stops being available.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resource is a consumable material, or if `finish` isn't
+    /// after `start`.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::{person::Person, resources::Resource};
+    ///
+    /// let monday = Utc::now();
+    /// let mut person = Resource::Personnel {
+    ///     person: Person::new("Margherita", "Hack").unwrap(),
+    ///     hourly_rate: None,
+    ///     availability: Vec::new(),
+    /// };
+    /// person.add_availability(monday, monday + Duration::days(5)).unwrap();
+    /// assert_eq!(person.availability(), &[(monday, monday + Duration::days(5))]);
+    /// assert!(person.add_availability(monday, monday).is_err());
+    /// ```
+    pub fn add_availability(
+        &mut self,
+        start: DateTime<Utc>,
+        finish: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        match self {
+            Resource::Personnel { availability, .. } => add_window(availability, start, finish),
+            Resource::Material(Material::NonConsumable(non_consumable)) => {
+                non_consumable.add_availability(start, finish)
+            }
+            Resource::Material(Material::Consumable(_)) => {
+                bail!("Consumable materials are always available")
+            }
+        }
+    }
+
+    /// Tells whether the resource can be used for the whole time from `start` to `finish`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - When the use of the resource starts.
+    /// * `finish` - When the use of the resource finishes.
+    ///
+    /// # Returns
+    ///
+    /// * `true` - If the resource has no availability windows, or if one of them contains
+    ///   the whole interval.
+    /// * `false` - Otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::resources::{Material, NonConsumable, Resource};
+    ///
+    /// let monday = Utc::now();
+    /// let mut crane = NonConsumable::new("Crane");
+    /// crane.add_availability(monday, monday + Duration::days(7)).unwrap();
+    /// let crane = Resource::Material(Material::NonConsumable(crane));
+    ///
+    /// assert!(crane.is_available(monday, monday + Duration::days(2)));
+    /// assert!(!crane.is_available(monday + Duration::days(6), monday + Duration::days(8)));
+    /// ```
+    pub fn is_available(&self, start: DateTime<Utc>, finish: DateTime<Utc>) -> bool {
+        let availability = self.availability();
+        availability.is_empty()
+            || availability
+                .iter()
+                .any(|&(from, until)| from <= start && finish <= until)
+    }
+}
+
+/// Adds a window to a sorted list of windows, merging the ones that overlap or touch.
+fn add_window(
+    windows: &mut Vec<AvailabilityWindow>,
+    start: DateTime<Utc>,
+    finish: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    if finish <= start {
+        bail!("An availability window must finish after it starts");
+    }
+    let (mut start, mut finish) = (start, finish);
+    windows.retain(|&(from, until)| {
+        let overlaps = from <= finish && start <= until;
+        if overlaps {
+            start = start.min(from);
+            finish = finish.max(until);
+        }
+        !overlaps
+    });
+    let position = windows.partition_point(|&(from, _)| from < start);
+    windows.insert(position, (start, finish));
+    Ok(())
 }
 
 impl Consumable {
@@ -221,6 +504,40 @@ impl Consumable {
             cost_per_unit: None,
         }
     }
+
+    /// Uses up some of the consumable material, taking it out of the available quantity.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - How many units are used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the quantity isn't known, or if there isn't enough material left.
+    /// The quantity is left untouched in both cases.
+    ///
+    /// # Example
+    /// ```
+    /// use planter_core::resources::{Consumable, Material};
+    ///
+    /// let mut stimpacks = Material::new("Stimpack");
+    /// stimpacks.update_quantity(5);
+    /// let Material::Consumable(mut stimpacks) = stimpacks else { unreachable!() };
+    ///
+    /// stimpacks.consume(3).unwrap();
+    /// assert!(stimpacks.consume(3).is_err());
+    /// assert_eq!(Material::Consumable(stimpacks).quantity(), Some(2));
+    /// ```
+    pub fn consume(&mut self, amount: u16) -> anyhow::Result<()> {
+        let Some(quantity) = self.quantity else {
+            bail!("The quantity of {} isn't known", self.name);
+        };
+        let Some(left) = quantity.checked_sub(amount) else {
+            bail!("Only {quantity} of {} left, {amount} needed", self.name);
+        };
+        self.quantity = Some(left);
+        Ok(())
+    }
 }
 
 impl NonConsumable {
@@ -231,6 +548,7 @@ impl NonConsumable {
             quantity: None,
             hourly_rate: None,
             cost_per_unit: None,
+            availability: Vec::new(),
         }
     }
 
@@ -242,21 +560,35 @@ impl NonConsumable {
     /// let non_consumable = NonConsumable::new("Steel".to_owned());
     /// assert_eq!(non_consumable.hourly_rate(), None);
     /// ```
-    pub fn hourly_rate(&self) -> Option<u16> {
+    pub fn hourly_rate(&self) -> Option<Money> {
         self.hourly_rate
     }
 
-    /// Updates the hourly_rate of the non consumable material.
+    /// Sets the hourly rate of the non consumable material.
     /// # Example
     /// ```
-    /// use planter_core::resources::NonConsumable;
+    /// use planter_core::{money::{Currency, Money}, resources::NonConsumable};
+    ///
+    /// let mut non_consumable = NonConsumable::new("Steel".to_owned());
+    /// non_consumable.set_hourly_rate(Money::new(350, Currency::GBP));
+    /// assert_eq!(non_consumable.hourly_rate(), Some(Money::new(350, Currency::GBP)));
+    /// ```
+    pub fn set_hourly_rate(&mut self, hourly_rate: Money) {
+        self.hourly_rate = Some(hourly_rate);
+    }
+
+    /// Updates the hourly_rate of the non consumable material, in whole units of the default
+    /// currency.
+    /// # Example
+    /// ```
+    /// use planter_core::{money::Money, resources::NonConsumable};
     ///
     /// let mut non_consumable = NonConsumable::new("Steel".to_owned());
     /// non_consumable.update_hourly_rate(3);
-    /// assert_eq!(non_consumable.hourly_rate(), Some(3));
+    /// assert_eq!(non_consumable.hourly_rate(), Some(Money::from(3)));
     /// ```
     pub fn update_hourly_rate(&mut self, hourly_rate: u16) {
-        self.hourly_rate = Some(hourly_rate);
+        self.set_hourly_rate(hourly_rate.into());
     }
 
     /// Remove the cost per unit of the non consumable material.
@@ -266,11 +598,131 @@ impl NonConsumable {
     ///
     /// let mut non_consumable = NonConsumable::new("Steel".to_owned());
     /// non_consumable.update_hourly_rate(3);
-    /// assert_eq!(non_consumable.hourly_rate(), Some(3));
+    /// assert!(non_consumable.hourly_rate().is_some());
     /// non_consumable.remove_hourly_rate();
     /// assert_eq!(non_consumable.hourly_rate(), None);
     /// ```
     pub fn remove_hourly_rate(&mut self) {
         self.hourly_rate = None;
     }
+
+    /// Returns when the non consumable material can be used, sorted and without overlaps.
+    /// Empty means always.
+    /// # Example
+    /// ```
+    /// use planter_core::resources::NonConsumable;
+    ///
+    /// let crane = NonConsumable::new("Crane");
+    /// assert!(crane.availability().is_empty());
+    /// ```
+    pub fn availability(&self) -> &[AvailabilityWindow] {
+        &self.availability
+    }
+
+    /// Makes the non consumable material available from `start` to `finish`, merging the
+    /// windows that overlap or touch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `finish` isn't after `start`.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::resources::NonConsumable;
+    ///
+    /// let monday = Utc::now();
+    /// let mut crane = NonConsumable::new("Crane");
+    /// crane.add_availability(monday, monday + Duration::days(2)).unwrap();
+    /// crane.add_availability(monday + Duration::days(1), monday + Duration::days(3)).unwrap();
+    /// assert_eq!(crane.availability(), &[(monday, monday + Duration::days(3))]);
+    /// ```
+    pub fn add_availability(
+        &mut self,
+        start: DateTime<Utc>,
+        finish: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        add_window(&mut self.availability, start, finish)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::money::Currency;
+
+    #[test]
+    fn conversions_keep_quantity_and_cost_per_unit() {
+        let mut material = Material::new("Stimpack");
+        material.update_quantity(3);
+        material.set_cost_per_unit(Money::new(1250, Currency::USD));
+        let Material::Consumable(consumable) = material else {
+            panic!("Materials are consumable by default");
+        };
+
+        let non_consumable = NonConsumable::from(consumable.clone());
+        assert_eq!(non_consumable.quantity, Some(3));
+        assert_eq!(non_consumable.cost_per_unit, consumable.cost_per_unit);
+        assert_eq!(Consumable::from(non_consumable), consumable);
+    }
+
+    #[test]
+    fn toggling_a_material_round_trips() {
+        let mut material = Material::new("Stimpack");
+        material.update_quantity(3);
+        material.set_cost_per_unit(Money::new(1250, Currency::USD));
+
+        let toggled = material.clone().into_non_consumable();
+        assert!(toggled.is_non_consumable());
+        assert_eq!(toggled.quantity(), material.quantity());
+        assert_eq!(toggled.cost_per_unit(), material.cost_per_unit());
+        assert_eq!(toggled.clone().into_non_consumable(), toggled);
+        assert_eq!(toggled.into_consumable(), material);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn resources_round_trip_through_json() {
+        let mut consumable = Material::new("Stimpack");
+        consumable.update_quantity(3);
+        consumable.set_cost_per_unit(Money::new(1250, Currency::USD));
+        let mut non_consumable = NonConsumable::new("Crowbar");
+        non_consumable.update_hourly_rate(2);
+
+        for resource in [
+            Resource::Material(consumable),
+            Resource::Material(Material::NonConsumable(non_consumable)),
+            Resource::Personnel {
+                person: Person::new("Margherita", "Hack").unwrap(),
+                hourly_rate: Some(Money::from(40)),
+                availability: Vec::new(),
+            },
+        ] {
+            let json = serde_json::to_string(&resource).unwrap();
+            assert_eq!(serde_json::from_str::<Resource>(&json).unwrap(), resource);
+        }
+    }
+
+    #[test]
+    fn availability_windows_are_sorted_and_merged() {
+        let monday = Utc::now();
+        let day = |n| monday + chrono::Duration::days(n);
+        let mut crane = NonConsumable::new("Crane");
+        crane.add_availability(day(10), day(12)).unwrap();
+        crane.add_availability(day(0), day(2)).unwrap();
+        crane.add_availability(day(2), day(3)).unwrap();
+        crane.add_availability(day(5), day(6)).unwrap();
+        assert_eq!(
+            crane.availability(),
+            &[(day(0), day(3)), (day(5), day(6)), (day(10), day(12))]
+        );
+
+        crane.add_availability(day(1), day(11)).unwrap();
+        assert_eq!(crane.availability(), &[(day(0), day(12))]);
+        assert!(crane.add_availability(day(1), day(0)).is_err());
+
+        let mut stimpack = Resource::Material(Material::new("Stimpack"));
+        assert!(stimpack.add_availability(day(0), day(1)).is_err());
+        assert!(stimpack.is_available(day(0), day(100)));
+    }
 }
@@ -0,0 +1,147 @@
+use crate::stakeholders::Level;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// An uncertain event that, if it happens, affects the project.
+pub struct Risk {
+    /// What could happen.
+    description: String,
+    /// How likely it is to happen.
+    probability: Level,
+    /// How much it would affect the project.
+    impact: Level,
+    /// The index of the stakeholder who owns the risk, if anyone does.
+    owner: Option<usize>,
+}
+
+impl Risk {
+    /// Creates a new risk, without an owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - What could happen.
+    /// * `probability` - How likely it is to happen.
+    /// * `impact` - How much it would affect the project.
+    ///
+    /// # Returns
+    ///
+    /// A new `Risk` instance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{risk::Risk, stakeholders::Level};
+    ///
+    /// let risk = Risk::new("The death ray overheats", Level::Medium, Level::High);
+    /// assert_eq!(risk.description(), "The death ray overheats");
+    /// assert!(risk.owner().is_none());
+    /// ```
+    pub fn new(description: impl Into<String>, probability: Level, impact: Level) -> Self {
+        Risk {
+            description: description.into(),
+            probability,
+            impact,
+            owner: None,
+        }
+    }
+
+    /// Sets the stakeholder who owns the risk.
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - The index of the stakeholder in the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{risk::Risk, stakeholders::Level};
+    ///
+    /// let risk = Risk::new("The death ray overheats", Level::Medium, Level::High).with_owner(0);
+    /// assert_eq!(risk.owner(), Some(0));
+    /// ```
+    pub fn with_owner(mut self, owner: usize) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Returns what could happen.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{risk::Risk, stakeholders::Level};
+    ///
+    /// let risk = Risk::new("The death ray overheats", Level::Medium, Level::High);
+    /// assert_eq!(risk.description(), "The death ray overheats");
+    /// ```
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Returns how likely the risk is to happen.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{risk::Risk, stakeholders::Level};
+    ///
+    /// let risk = Risk::new("The death ray overheats", Level::Medium, Level::High);
+    /// assert_eq!(risk.probability(), Level::Medium);
+    /// ```
+    pub fn probability(&self) -> Level {
+        self.probability
+    }
+
+    /// Returns how much the risk would affect the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{risk::Risk, stakeholders::Level};
+    ///
+    /// let risk = Risk::new("The death ray overheats", Level::Medium, Level::High);
+    /// assert_eq!(risk.impact(), Level::High);
+    /// ```
+    pub fn impact(&self) -> Level {
+        self.impact
+    }
+
+    /// Returns the index of the stakeholder who owns the risk, if anyone does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{risk::Risk, stakeholders::Level};
+    ///
+    /// let risk = Risk::new("The death ray overheats", Level::Medium, Level::High);
+    /// assert!(risk.owner().is_none());
+    /// ```
+    pub fn owner(&self) -> Option<usize> {
+        self.owner
+    }
+
+    /// Combines probability and impact into a single score, as in a probability and impact
+    /// matrix. Each level counts from 1 for [`Level::Low`] to 3 for [`Level::High`], and the
+    /// score is their product.
+    ///
+    /// # Returns
+    ///
+    /// A score from 1, for unlikely risks with little impact, to 9.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{risk::Risk, stakeholders::Level};
+    ///
+    /// let risk = Risk::new("The death ray overheats", Level::Medium, Level::High);
+    /// assert_eq!(risk.severity(), 6);
+    /// ```
+    pub fn severity(&self) -> u8 {
+        (self.probability as u8 + 1) * (self.impact as u8 + 1)
+    }
+
+    /// Rewrites the owner of the risk, dropping it if the mapping returns `None`.
+    pub(crate) fn reindex_owner(&mut self, mut map: impl FnMut(usize) -> Option<usize>) {
+        self.owner = self.owner.and_then(&mut map);
+    }
+}
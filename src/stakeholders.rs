@@ -1,6 +1,7 @@
 use crate::person::Person;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Stakeholders are all those individuals, organizations or entities who have an interest in the project.
 /// Their interest could be constructive or destructive.
 pub enum Stakeholder {
@@ -10,6 +11,16 @@ pub enum Stakeholder {
         person: Person,
         /// A description of the individual's interest in the project.
         description: Option<String>,
+        /// How much the individual can affect the project.
+        influence: Level,
+        /// How much the individual is affected by, or cares about, the project.
+        interest: Level,
+        /// How engaged the individual currently is with the project.
+        #[cfg_attr(feature = "serde", serde(default))]
+        current_engagement: Engagement,
+        /// How engaged the individual should be with the project.
+        #[cfg_attr(feature = "serde", serde(default))]
+        desired_engagement: Engagement,
     },
     /// An organization that has an interest in the project.
     Organization {
@@ -17,5 +28,296 @@ pub enum Stakeholder {
         name: String,
         /// A description of the organization's interest in the project.
         description: Option<String>,
+        /// How much the organization can affect the project.
+        influence: Level,
+        /// How much the organization is affected by, or cares about, the project.
+        interest: Level,
+        /// How engaged the organization currently is with the project.
+        #[cfg_attr(feature = "serde", serde(default))]
+        current_engagement: Engagement,
+        /// How engaged the organization should be with the project.
+        #[cfg_attr(feature = "serde", serde(default))]
+        desired_engagement: Engagement,
     },
 }
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A coarse measure of the influence or the interest of a stakeholder.
+pub enum Level {
+    /// Little or none.
+    Low,
+    /// Some, the default when it's unknown.
+    #[default]
+    Medium,
+    /// A lot.
+    High,
+}
+
+impl Level {
+    /// All the levels, from the lowest to the highest.
+    pub const ALL: [Level; 3] = [Level::Low, Level::Medium, Level::High];
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// How engaged a stakeholder is with the project, from the least to the most engaged.
+pub enum Engagement {
+    /// Unaware of the project and its potential impacts.
+    Unaware,
+    /// Aware of the project, but resistant to the changes it brings.
+    Resistant,
+    /// Aware of the project, but neither supportive nor resistant.
+    /// The default when it's unknown.
+    #[default]
+    Neutral,
+    /// Aware of the project and supportive of it.
+    Supportive,
+    /// Aware of the project and actively engaged in making it succeed.
+    Leading,
+}
+
+impl Engagement {
+    /// All the engagement levels, from the least to the most engaged.
+    pub const ALL: [Engagement; 5] = [
+        Engagement::Unaware,
+        Engagement::Resistant,
+        Engagement::Neutral,
+        Engagement::Supportive,
+        Engagement::Leading,
+    ];
+}
+
+impl Stakeholder {
+    /// Returns how much the stakeholder can affect the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::stakeholders::{Engagement, Level, Stakeholder};
+    ///
+    /// let stakeholder = Stakeholder::Organization {
+    ///     name: "Acme".to_owned(),
+    ///     description: None,
+    ///     influence: Level::High,
+    ///     interest: Level::Low,
+    ///     current_engagement: Engagement::Neutral,
+    ///     desired_engagement: Engagement::Neutral,
+    /// };
+    /// assert_eq!(stakeholder.influence(), Level::High);
+    /// ```
+    pub fn influence(&self) -> Level {
+        match self {
+            Stakeholder::Individual { influence, .. }
+            | Stakeholder::Organization { influence, .. } => *influence,
+        }
+    }
+
+    /// Returns how much the stakeholder is affected by, or cares about, the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::stakeholders::{Engagement, Level, Stakeholder};
+    ///
+    /// let stakeholder = Stakeholder::Organization {
+    ///     name: "Acme".to_owned(),
+    ///     description: None,
+    ///     influence: Level::High,
+    ///     interest: Level::Low,
+    ///     current_engagement: Engagement::Neutral,
+    ///     desired_engagement: Engagement::Neutral,
+    /// };
+    /// assert_eq!(stakeholder.interest(), Level::Low);
+    /// ```
+    pub fn interest(&self) -> Level {
+        match self {
+            Stakeholder::Individual { interest, .. }
+            | Stakeholder::Organization { interest, .. } => *interest,
+        }
+    }
+
+    /// Sets how much the stakeholder can affect the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The influence of the stakeholder.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::stakeholders::{Engagement, Level, Stakeholder};
+    ///
+    /// let stakeholder = Stakeholder::Organization {
+    ///     name: "Acme".to_owned(),
+    ///     description: None,
+    ///     influence: Level::default(),
+    ///     interest: Level::default(),
+    ///     current_engagement: Engagement::Neutral,
+    ///     desired_engagement: Engagement::Neutral,
+    /// }
+    /// .with_influence(Level::High);
+    /// assert_eq!(stakeholder.influence(), Level::High);
+    /// ```
+    pub fn with_influence(mut self, level: Level) -> Self {
+        match &mut self {
+            Stakeholder::Individual { influence, .. }
+            | Stakeholder::Organization { influence, .. } => *influence = level,
+        }
+        self
+    }
+
+    /// Sets how much the stakeholder is affected by, or cares about, the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The interest of the stakeholder.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::stakeholders::{Engagement, Level, Stakeholder};
+    ///
+    /// let stakeholder = Stakeholder::Organization {
+    ///     name: "Acme".to_owned(),
+    ///     description: None,
+    ///     influence: Level::default(),
+    ///     interest: Level::default(),
+    ///     current_engagement: Engagement::Neutral,
+    ///     desired_engagement: Engagement::Neutral,
+    /// }
+    /// .with_interest(Level::Low);
+    /// assert_eq!(stakeholder.interest(), Level::Low);
+    /// ```
+    pub fn with_interest(mut self, level: Level) -> Self {
+        match &mut self {
+            Stakeholder::Individual { interest, .. }
+            | Stakeholder::Organization { interest, .. } => *interest = level,
+        }
+        self
+    }
+
+    /// Returns how engaged the stakeholder currently is with the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::stakeholders::{Engagement, Level, Stakeholder};
+    ///
+    /// let stakeholder = Stakeholder::Organization {
+    ///     name: "Acme".to_owned(),
+    ///     description: None,
+    ///     influence: Level::High,
+    ///     interest: Level::Low,
+    ///     current_engagement: Engagement::Resistant,
+    ///     desired_engagement: Engagement::Supportive,
+    /// };
+    /// assert_eq!(stakeholder.current_engagement(), Engagement::Resistant);
+    /// ```
+    pub fn current_engagement(&self) -> Engagement {
+        match self {
+            Stakeholder::Individual {
+                current_engagement, ..
+            }
+            | Stakeholder::Organization {
+                current_engagement, ..
+            } => *current_engagement,
+        }
+    }
+
+    /// Returns how engaged the stakeholder should be with the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::stakeholders::{Engagement, Level, Stakeholder};
+    ///
+    /// let stakeholder = Stakeholder::Organization {
+    ///     name: "Acme".to_owned(),
+    ///     description: None,
+    ///     influence: Level::High,
+    ///     interest: Level::Low,
+    ///     current_engagement: Engagement::Resistant,
+    ///     desired_engagement: Engagement::Supportive,
+    /// };
+    /// assert_eq!(stakeholder.desired_engagement(), Engagement::Supportive);
+    /// ```
+    pub fn desired_engagement(&self) -> Engagement {
+        match self {
+            Stakeholder::Individual {
+                desired_engagement, ..
+            }
+            | Stakeholder::Organization {
+                desired_engagement, ..
+            } => *desired_engagement,
+        }
+    }
+
+    /// Sets how engaged the stakeholder currently is with the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `engagement` - The current engagement of the stakeholder.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::stakeholders::{Engagement, Level, Stakeholder};
+    ///
+    /// let stakeholder = Stakeholder::Organization {
+    ///     name: "Acme".to_owned(),
+    ///     description: None,
+    ///     influence: Level::default(),
+    ///     interest: Level::default(),
+    ///     current_engagement: Engagement::default(),
+    ///     desired_engagement: Engagement::default(),
+    /// }
+    /// .with_current_engagement(Engagement::Unaware);
+    /// assert_eq!(stakeholder.current_engagement(), Engagement::Unaware);
+    /// ```
+    pub fn with_current_engagement(mut self, engagement: Engagement) -> Self {
+        match &mut self {
+            Stakeholder::Individual {
+                current_engagement, ..
+            }
+            | Stakeholder::Organization {
+                current_engagement, ..
+            } => *current_engagement = engagement,
+        }
+        self
+    }
+
+    /// Sets how engaged the stakeholder should be with the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `engagement` - The desired engagement of the stakeholder.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::stakeholders::{Engagement, Level, Stakeholder};
+    ///
+    /// let stakeholder = Stakeholder::Organization {
+    ///     name: "Acme".to_owned(),
+    ///     description: None,
+    ///     influence: Level::default(),
+    ///     interest: Level::default(),
+    ///     current_engagement: Engagement::default(),
+    ///     desired_engagement: Engagement::default(),
+    /// }
+    /// .with_desired_engagement(Engagement::Leading);
+    /// assert_eq!(stakeholder.desired_engagement(), Engagement::Leading);
+    /// ```
+    pub fn with_desired_engagement(mut self, engagement: Engagement) -> Self {
+        match &mut self {
+            Stakeholder::Individual {
+                desired_engagement, ..
+            }
+            | Stakeholder::Organization {
+                desired_engagement, ..
+            } => *desired_engagement = engagement,
+        }
+        self
+    }
+}
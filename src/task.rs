@@ -1,25 +1,383 @@
-use crate::{duration::PositiveDuration, resources::Resource};
-use anyhow::Context;
-use chrono::{DateTime, Utc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    hash::{Hash, Hasher},
+    sync::atomic::{AtomicU64, Ordering},
+};
 
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+use crate::{calendar::Calendar, duration::PositiveDuration, project::Phase};
+use anyhow::{Context, bail};
+use chrono::{DateTime, Duration, Utc};
+
+mod builder;
+
+pub use builder::TaskBuilder;
+
+/// The identifier the next task will get.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Returns a new task identifier, different from all the ones handed out so far.
+fn next_id() -> u64 {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Deserializes a task identifier, making sure the tasks created afterwards don't reuse it.
+#[cfg(feature = "serde")]
+fn deserialize_id<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    let id = <u64 as serde::Deserialize>::deserialize(deserializer)?;
+    NEXT_ID.fetch_max(id.saturating_add(1), Ordering::Relaxed);
+    Ok(id)
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A task is a unit of work that can be completed by a person or a group of people.
 /// It can be assigned resources and can have a start, finish, and duration.
 pub struct Task {
+    /// The identifier of the task, which doesn't change when other tasks are removed.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "next_id", deserialize_with = "deserialize_id")
+    )]
+    id: u64,
     /// The name of the task.
     name: String,
     /// The description of the task.
     description: String,
-    /// Whether the task is completed.
-    completed: bool,
+    /// The progress state of the task.
+    status: TaskStatus,
+    /// The phase of the project the task belongs to.
+    phase: Option<Phase>,
+    /// How much of the task is done, from 0 to 100.
+    percent_complete: u8,
     /// The start time of the task.
     start: Option<DateTime<Utc>>,
     /// The finish time of the task.
     finish: Option<DateTime<Utc>>,
     /// The duration of the task.
     duration: Option<PositiveDuration>,
-    /// The resources assigned to the task.
-    resources: Vec<Resource>,
+    /// The start of the task when the baseline was set.
+    baseline_start: Option<DateTime<Utc>>,
+    /// The finish of the task when the baseline was set.
+    baseline_finish: Option<DateTime<Utc>>,
+    /// The duration of the task when the baseline was set.
+    baseline_duration: Option<PositiveDuration>,
+    /// Whether the task is a milestone, a marker of a key date without duration.
+    milestone: bool,
+    /// The date the task is bound to, on top of its relationships.
+    constraint: Option<TaskConstraint>,
+    /// The date the task should be finished by.
+    deadline: Option<DateTime<Utc>>,
+    /// The amount of work the task requires, regardless of how many people work on it.
+    effort: Option<PositiveDuration>,
+    /// The contingency added to the duration, in risk-adjusted plans.
+    #[cfg_attr(feature = "serde", serde(default))]
+    buffer: Option<PositiveDuration>,
+    /// The project resources working on the task, with the share of their capacity.
+    assignments: Vec<Assignment>,
+    /// How much of each consumable project resource the task uses, by resource index.
+    #[cfg_attr(feature = "serde", serde(default))]
+    consumptions: Vec<(usize, u16)>,
+    /// Arbitrary key-value fields, for information the model doesn't cover.
+    custom_fields: BTreeMap<String, String>,
+    /// Labels to group and filter tasks by, like "frontend" or "risk".
+    tags: BTreeSet<String>,
+    /// The role of project stakeholders in the task, by stakeholder index.
+    #[cfg_attr(feature = "serde", serde(default))]
+    responsibilities: Vec<(usize, Raci)>,
+    /// The intervals the task is worked on, in order, if it was split. Empty when the task
+    /// is worked on in one block.
+    #[cfg_attr(feature = "serde", serde(default))]
+    segments: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+}
+
+/// Tasks are equal when all their data is, regardless of their identifiers, so that a task
+/// can be compared with an imported copy of it.
+impl PartialEq for Task {
+    fn eq(&self, other: &Self) -> bool {
+        // Destructuring makes sure new fields aren't forgotten here.
+        let Task {
+            id: _,
+            name,
+            description,
+            status,
+            phase,
+            percent_complete,
+            start,
+            finish,
+            duration,
+            baseline_start,
+            baseline_finish,
+            baseline_duration,
+            milestone,
+            constraint,
+            deadline,
+            effort,
+            buffer,
+            assignments,
+            consumptions,
+            custom_fields,
+            tags,
+            responsibilities,
+            segments,
+        } = self;
+        *name == other.name
+            && *description == other.description
+            && *status == other.status
+            && *phase == other.phase
+            && *percent_complete == other.percent_complete
+            && *start == other.start
+            && *finish == other.finish
+            && *duration == other.duration
+            && *baseline_start == other.baseline_start
+            && *baseline_finish == other.baseline_finish
+            && *baseline_duration == other.baseline_duration
+            && *milestone == other.milestone
+            && *constraint == other.constraint
+            && *deadline == other.deadline
+            && *effort == other.effort
+            && *buffer == other.buffer
+            && *assignments == other.assignments
+            && *consumptions == other.consumptions
+            && *custom_fields == other.custom_fields
+            && *tags == other.tags
+            && *responsibilities == other.responsibilities
+            && *segments == other.segments
+    }
+}
+
+impl Eq for Task {}
+
+/// Consistently with equality, the identifier isn't hashed.
+impl Hash for Task {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Task {
+            id: _,
+            name,
+            description,
+            status,
+            phase,
+            percent_complete,
+            start,
+            finish,
+            duration,
+            baseline_start,
+            baseline_finish,
+            baseline_duration,
+            milestone,
+            constraint,
+            deadline,
+            effort,
+            buffer,
+            assignments,
+            consumptions,
+            custom_fields,
+            tags,
+            responsibilities,
+            segments,
+        } = self;
+        name.hash(state);
+        description.hash(state);
+        status.hash(state);
+        phase.hash(state);
+        percent_complete.hash(state);
+        start.hash(state);
+        finish.hash(state);
+        duration.hash(state);
+        baseline_start.hash(state);
+        baseline_finish.hash(state);
+        baseline_duration.hash(state);
+        milestone.hash(state);
+        constraint.hash(state);
+        deadline.hash(state);
+        effort.hash(state);
+        buffer.hash(state);
+        assignments.hash(state);
+        consumptions.hash(state);
+        custom_fields.hash(state);
+        tags.hash(state);
+        responsibilities.hash(state);
+        segments.hash(state);
+    }
+}
+
+/// A task without a name, with a new identifier.
+impl Default for Task {
+    fn default() -> Self {
+        Task::new(String::new())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The state of a task in its lifecycle.
+pub enum TaskStatus {
+    /// Work on the task hasn't started yet.
+    #[default]
+    NotStarted,
+    /// Work on the task is ongoing.
+    InProgress,
+    /// Work on the task can't proceed until something else happens.
+    Blocked,
+    /// The task is done.
+    Completed,
+}
+
+impl TaskStatus {
+    /// All the possible statuses, in lifecycle order.
+    pub const ALL: [TaskStatus; 4] = [
+        TaskStatus::NotStarted,
+        TaskStatus::InProgress,
+        TaskStatus::Blocked,
+        TaskStatus::Completed,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A date a task is bound to, on top of the relationships with other tasks.
+pub enum TaskConstraint {
+    /// The task has to start exactly on the given date.
+    MustStartOn(DateTime<Utc>),
+    /// The task has to finish exactly on the given date.
+    MustFinishOn(DateTime<Utc>),
+    /// The task can't start before the given date.
+    StartNoEarlierThan(DateTime<Utc>),
+    /// The task can't start after the given date.
+    StartNoLaterThan(DateTime<Utc>),
+    /// The task can't finish before the given date.
+    FinishNoEarlierThan(DateTime<Utc>),
+    /// The task can't finish after the given date.
+    FinishNoLaterThan(DateTime<Utc>),
+}
+
+impl TaskConstraint {
+    /// Checks whether a task with the given dates respects the constraint.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The start of the task.
+    /// * `finish` - The finish of the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::task::TaskConstraint;
+    ///
+    /// let now = Utc::now();
+    /// let constraint = TaskConstraint::FinishNoLaterThan(now);
+    /// assert!(constraint.is_satisfied_by(now - Duration::hours(2), now));
+    /// assert!(!constraint.is_satisfied_by(now, now + Duration::hours(2)));
+    /// ```
+    pub fn is_satisfied_by(&self, start: DateTime<Utc>, finish: DateTime<Utc>) -> bool {
+        match *self {
+            TaskConstraint::MustStartOn(date) => start == date,
+            TaskConstraint::MustFinishOn(date) => finish == date,
+            TaskConstraint::StartNoEarlierThan(date) => start >= date,
+            TaskConstraint::StartNoLaterThan(date) => start <= date,
+            TaskConstraint::FinishNoEarlierThan(date) => finish >= date,
+            TaskConstraint::FinishNoLaterThan(date) => finish <= date,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The role of a stakeholder in a task, as in a RACI matrix.
+pub enum Raci {
+    /// Does the work to complete the task.
+    Responsible,
+    /// Answers for the task and signs it off. A task has at most one.
+    Accountable,
+    /// Gives their opinion before and while the task is worked on.
+    Consulted,
+    /// Is kept up to date on the progress of the task.
+    Informed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "AssignmentRepr")
+)]
+/// The assignment of a project resource to a task, at a share of its capacity.
+pub struct Assignment {
+    /// The index of the resource in the project.
+    resource_index: usize,
+    /// The share of the resource capacity spent on the task, where 1.0 is full time.
+    units: f32,
+}
+
+// Units are checked to be finite when the assignment is created, so they're never NaN.
+impl Eq for Assignment {}
+
+// Units are also positive, so equal units have the same bits.
+impl Hash for Assignment {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.resource_index.hash(state);
+        self.units.to_bits().hash(state);
+    }
+}
+
+/// The serialized form of an [`Assignment`], validated when it's turned back into one.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct AssignmentRepr {
+    resource_index: usize,
+    units: f32,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<AssignmentRepr> for Assignment {
+    type Error = anyhow::Error;
+
+    fn try_from(repr: AssignmentRepr) -> Result<Self, Self::Error> {
+        Assignment::new(repr.resource_index, repr.units)
+    }
+}
+
+impl Assignment {
+    /// Creates an assignment, checking that `units` is a finite positive number.
+    fn new(resource_index: usize, units: f32) -> anyhow::Result<Self> {
+        if !units.is_finite() || units <= 0.0 {
+            bail!("Units must be a finite positive number, got {units}");
+        }
+        Ok(Assignment {
+            resource_index,
+            units,
+        })
+    }
+
+    /// Returns the index of the assigned resource in the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.assign_resource(3, 0.5).unwrap();
+    /// assert_eq!(task.assignments()[0].resource_index(), 3);
+    /// ```
+    pub fn resource_index(&self) -> usize {
+        self.resource_index
+    }
+
+    /// Returns the share of the resource capacity spent on the task, where 1.0 is full time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.assign_resource(3, 0.5).unwrap();
+    /// assert_eq!(task.assignments()[0].units(), 0.5);
+    /// ```
+    pub fn units(&self) -> f32 {
+        self.units
+    }
 }
 
 impl Task {
@@ -43,20 +401,59 @@ impl Task {
     /// ```
     pub fn new(name: impl Into<String>) -> Self {
         Task {
+            id: next_id(),
             name: name.into(),
             description: String::new(),
-            completed: false,
+            status: TaskStatus::NotStarted,
+            phase: None,
+            percent_complete: 0,
             start: None,
             finish: None,
             duration: None,
-            resources: Vec::new(),
+            baseline_start: None,
+            baseline_finish: None,
+            baseline_duration: None,
+            milestone: false,
+            constraint: None,
+            deadline: None,
+            effort: None,
+            buffer: None,
+            assignments: Vec::new(),
+            consumptions: Vec::new(),
+            custom_fields: BTreeMap::new(),
+            tags: BTreeSet::new(),
+            responsibilities: Vec::new(),
+            segments: Vec::new(),
         }
     }
 
+    /// Returns the identifier of the task. Unlike its index in a project, it stays the same
+    /// when other tasks are removed. Every new task gets a different identifier, while a
+    /// cloned task keeps the identifier of the original.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let task = Task::new("Become world leader");
+    /// assert_ne!(task.id(), Task::new("Become world leader").id());
+    /// assert_eq!(task.id(), task.clone().id());
+    /// ```
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Gives the task a new identifier, for when its current one is already taken.
+    pub(crate) fn renew_id(&mut self) {
+        self.id = next_id();
+    }
+
     /// Edits the start time of the task.
     /// If a duration is already set, the finish time will be updated accordingly.
     /// If there is a finish time set, but not a duration, the duration will be updated accordingly.
     /// The finish time will be pushed ahead if the start time is after the finish time.
+    /// A split task is moved as a whole, keeping its segments and the pauses between them.
     ///
     /// # Arguments
     ///
@@ -65,7 +462,9 @@ impl Task {
     /// # Errors
     ///
     /// Returns an error if the task has a finish date and the start date passed
-    /// as parameter is too far from that.
+    /// as parameter is too far from that, or if the task has a duration and the
+    /// finish date would be past the latest representable date. The task is left
+    /// untouched in both cases.
     ///
     /// # Example
     ///
@@ -78,27 +477,32 @@ impl Task {
     /// task.edit_start(start_time);
     /// assert_eq!(task.start().unwrap(), start_time);
     /// ```
-    #[allow(clippy::expect_used)]
     pub fn edit_start(&mut self, start: DateTime<Utc>) -> anyhow::Result<()> {
-        self.start = Some(start);
-
-        if let Some(duration) = self.duration {
-            self.finish = Some(start + *duration);
+        if let Some(old_start) = self.start
+            && !self.segments.is_empty()
+        {
+            return self.shift_segments(0, start - old_start);
         }
+        let finish = match self.duration {
+            Some(duration) => Some(
+                start
+                    .checked_add_signed(*duration)
+                    .context("The finish time would be out of range")?,
+            ),
+            None => self.finish.map(|finish| finish.max(start)),
+        };
+        let duration = match (self.duration, finish) {
+            (None, Some(finish)) => Some(
+                (finish - start)
+                    .try_into()
+                    .context("Start time and finish time were too far apart")?,
+            ),
+            (duration, _) => duration,
+        };
 
-        if let Some(finish) = self.finish {
-            if finish < start {
-                self.finish = Some(start);
-            }
-            if self.duration().is_none() {
-                let duration = finish - start;
-                self.duration = Some(
-                    duration
-                        .try_into()
-                        .context("Start time and finish time were too far apart")?,
-                );
-            }
-        }
+        self.start = Some(start);
+        self.finish = finish;
+        self.duration = duration;
         Ok(())
     }
 
@@ -124,6 +528,8 @@ impl Task {
     /// Edits the finish time of the task.
     /// If there is a start time already set, duration will be updated accordingly.
     /// Start time will be pushed back if it's after the finish time.
+    /// Milestones are moved as a whole: their start time becomes the finish time.
+    /// A split task is joined back into one block.
     ///
     /// # Arguments
     ///
@@ -132,8 +538,7 @@ impl Task {
     /// # Errors
     ///
     /// Returns an error if the task has a start date and the finish date passed
-    /// as parameter is too far from that.
-    ///
+    /// as parameter is too far from that. The task is left untouched in that case.
     ///
     /// # Example
     ///
@@ -148,27 +553,34 @@ impl Task {
     /// task.edit_finish(finish_time).unwrap();
     /// assert_eq!(task.finish().unwrap(), finish_time);
     /// ```
-    #[allow(clippy::expect_used)]
     pub fn edit_finish(&mut self, finish: DateTime<Utc>) -> anyhow::Result<()> {
-        self.finish = Some(finish);
-
+        self.segments.clear();
+        if self.milestone {
+            self.start = Some(finish);
+            self.finish = Some(finish);
+            return Ok(());
+        }
         if let Some(start) = self.start() {
-            let start = if finish < start {
-                self.start = Some(finish);
-                finish
-            } else {
-                start
-            };
-            let duration = finish - start;
-            self.duration = Some(
-                duration
-                    .try_into()
-                    .context("Start time and finish time were too far apart")?,
-            );
+            let start = start.min(finish);
+            let duration = (finish - start)
+                .try_into()
+                .context("Start time and finish time were too far apart")?;
+            self.start = Some(start);
+            self.duration = Some(duration);
         }
+        self.finish = Some(finish);
         Ok(())
     }
 
+    /// Sets the dates of a task scheduled on a working calendar. Its duration is working
+    /// time, so it's kept as it is even if it doesn't match the time between the dates.
+    /// A split task is joined back into one block.
+    pub(crate) fn set_working_dates(&mut self, start: DateTime<Utc>, finish: DateTime<Utc>) {
+        self.segments.clear();
+        self.start = Some(start);
+        self.finish = Some(finish);
+    }
+
     /// Returns the finish time of the task. It's None by default.
     ///
     /// # Example
@@ -188,6 +600,9 @@ impl Task {
     }
 
     /// Edits the duration of the task. If the task has a start time, finish time will be updated accordingly.
+    /// If the finish time would be past the latest representable date, it's clamped to it.
+    /// Milestones always last zero, so their duration is reset whatever the new one is.
+    /// A split task is joined back into one block.
     ///
     /// # Arguments
     ///
@@ -205,55 +620,385 @@ impl Task {
     /// assert_eq!(task.duration().unwrap(), Duration::minutes(30).try_into().unwrap());
     /// ```
     pub fn edit_duration(&mut self, duration: PositiveDuration) {
+        let duration = if self.milestone {
+            PositiveDuration::ZERO
+        } else {
+            duration
+        };
         self.duration = Some(duration);
+        self.segments.clear();
 
         if let Some(start) = self.start() {
-            let finish = start + *duration;
+            let finish = start
+                .checked_add_signed(*duration)
+                .unwrap_or(DateTime::<Utc>::MAX_UTC);
+            self.finish = Some(finish);
+        }
+    }
+
+    /// Edits the duration of the task, as working time on a calendar. If the task has a start
+    /// time, the finish time is found by moving over the working hours of `calendar` only,
+    /// skipping nights, weekends and holidays. Otherwise it behaves like
+    /// [`Task::edit_duration`]. If the finish time would be past the latest representable
+    /// date, it's clamped to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The new duration of the task, in working time.
+    /// * `calendar` - The calendar to find the finish time on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, NaiveDate};
+    /// use planter_core::{calendar::Calendar, task::Task};
+    ///
+    /// let monday = NaiveDate::from_ymd_opt(2025, 6, 9).unwrap();
+    /// let tuesday = NaiveDate::from_ymd_opt(2025, 6, 10).unwrap();
+    /// let mut task = Task::new("Become world leader");
+    /// task.edit_start(monday.and_hms_opt(16, 0, 0).unwrap().and_utc()).unwrap();
+    ///
+    /// task.edit_duration_with_calendar(Duration::hours(8).try_into().unwrap(), &Calendar::default());
+    /// assert_eq!(task.finish(), Some(tuesday.and_hms_opt(16, 0, 0).unwrap().and_utc()));
+    /// assert_eq!(*task.duration().unwrap(), Duration::hours(8));
+    /// ```
+    pub fn edit_duration_with_calendar(&mut self, duration: PositiveDuration, calendar: &Calendar) {
+        self.edit_duration(duration);
+        if let Some(start) = self.start
+            && !self.milestone
+        {
+            let finish = calendar
+                .add_working_time(start, *duration)
+                .unwrap_or(DateTime::<Utc>::MAX_UTC);
             self.finish = Some(finish);
         }
     }
 
-    /// Adds a [`Resource`] to the task.
+    /// Assigns a project resource to the task full time, like [`Task::assign_resource`] with
+    /// units of 1.0.
+    ///
+    /// # Arguments
+    ///
+    /// * `resource_index` - The index of the resource in the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{
+    ///     project::Project,
+    ///     resources::{Material, NonConsumable, Resource},
+    ///     task::Task,
+    /// };
+    ///
+    /// let mut project = Project::new("World domination");
+    /// let crowbar = project.add_resource(Resource::Material(Material::NonConsumable(
+    ///     NonConsumable::new("Crowbar"),
+    /// )));
+    /// let mut task = Task::new("Become world leader");
+    /// task.add_resource(crowbar);
+    ///
+    /// assert_eq!(task.assignments()[0].resource_index(), crowbar);
+    /// assert_eq!(task.assignments()[0].units(), 1.0);
+    /// ```
+    pub fn add_resource(&mut self, resource_index: usize) {
+        self.set_assignment(Assignment {
+            resource_index,
+            units: 1.0,
+        });
+    }
+
+    /// Assigns a project resource to the task, at a share of its capacity. Assigning a resource
+    /// that is already assigned replaces its units.
+    ///
+    /// # Arguments
+    ///
+    /// * `resource_index` - The index of the resource in the project.
+    /// * `units` - The share of the resource capacity spent on the task, where 1.0 is full time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `units` isn't a finite positive number.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.assign_resource(0, 0.5).unwrap();
+    /// task.assign_resource(0, 0.25).unwrap();
+    ///
+    /// assert_eq!(task.assignments().len(), 1);
+    /// assert_eq!(task.assignments()[0].units(), 0.25);
+    /// assert!(task.assign_resource(1, 0.0).is_err());
+    /// ```
+    pub fn assign_resource(&mut self, resource_index: usize, units: f32) -> anyhow::Result<()> {
+        self.set_assignment(Assignment::new(resource_index, units)?);
+        Ok(())
+    }
+
+    /// Adds an assignment, replacing the one of the same resource if there is one.
+    fn set_assignment(&mut self, assignment: Assignment) {
+        match self
+            .assignments
+            .iter_mut()
+            .find(|a| a.resource_index == assignment.resource_index)
+        {
+            Some(existing) => *existing = assignment,
+            None => self.assignments.push(assignment),
+        }
+    }
+
+    /// Removes the assignment of a project resource from the task.
     ///
     /// # Arguments
     ///
-    /// * `resource` - The resource to add to the task.
+    /// * `resource_index` - The index of the resource in the project.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Assignment)` - The removed assignment.
+    /// * `None` - If the resource wasn't assigned to the task.
     ///
     /// # Example
     ///
     /// ```
-    /// use planter_core::{resources::{Resource, Material, NonConsumable}, task::Task};
+    /// use planter_core::task::Task;
     ///
     /// let mut task = Task::new("Become world leader");
-    /// let resource = Resource::Material(Material::NonConsumable(
-    ///   NonConsumable::new("Crowbar"),
-    /// ));
-    /// task.add_resource(resource);
+    /// task.assign_resource(0, 0.5).unwrap();
     ///
-    /// assert_eq!(task.resources().len(), 1);
+    /// assert!(task.unassign_resource(0).is_some());
+    /// assert!(task.assignments().is_empty());
     /// ```
-    pub fn add_resource(&mut self, resource: Resource) {
-        self.resources.push(resource);
+    pub fn unassign_resource(&mut self, resource_index: usize) -> Option<Assignment> {
+        let position = self
+            .assignments
+            .iter()
+            .position(|a| a.resource_index == resource_index)?;
+        Some(self.assignments.remove(position))
     }
 
-    /// Returns the list of [`Resource`] assigned to the task.
+    /// Returns the project resources assigned to the task, with the share of their capacity.
     ///
     /// # Example
     ///
     /// ```
     /// use planter_core::task::Task;
-    /// use planter_core::resources::{Resource, Material, NonConsumable};
     ///
     /// let mut task = Task::new("Become world leader");
-    /// assert!(task.resources().is_empty());
-    /// let resource = Resource::Material(Material::NonConsumable(
-    ///   NonConsumable::new("Crowbar"),
-    /// ));
-    /// task.add_resource(resource);
-    /// assert_eq!(task.resources().len(), 1);
+    /// assert!(task.assignments().is_empty());
+    /// task.assign_resource(0, 0.5).unwrap();
+    /// assert_eq!(task.assignments().len(), 1);
+    /// ```
+    pub fn assignments(&self) -> &[Assignment] {
+        &self.assignments
+    }
+
+    /// Gives a project stakeholder a role in the task. Assigning a role to a stakeholder that
+    /// already has one replaces it.
+    ///
+    /// # Arguments
+    ///
+    /// * `stakeholder_index` - The index of the stakeholder in the project.
+    /// * `raci` - The role of the stakeholder.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the role is [`Raci::Accountable`] and another stakeholder is already
+    /// accountable for the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::{Raci, Task};
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.assign_raci(0, Raci::Accountable).unwrap();
+    /// task.assign_raci(1, Raci::Responsible).unwrap();
+    ///
+    /// assert_eq!(task.raci(), &[(0, Raci::Accountable), (1, Raci::Responsible)]);
+    /// assert!(task.assign_raci(1, Raci::Accountable).is_err());
+    /// ```
+    pub fn assign_raci(&mut self, stakeholder_index: usize, raci: Raci) -> anyhow::Result<()> {
+        if raci == Raci::Accountable
+            && let Some((accountable, _)) = self
+                .responsibilities
+                .iter()
+                .find(|(i, r)| *r == Raci::Accountable && *i != stakeholder_index)
+        {
+            bail!("Stakeholder {accountable} is already accountable for the task");
+        }
+
+        match self
+            .responsibilities
+            .iter_mut()
+            .find(|(i, _)| *i == stakeholder_index)
+        {
+            Some((_, existing)) => *existing = raci,
+            None => self.responsibilities.push((stakeholder_index, raci)),
+        }
+        Ok(())
+    }
+
+    /// Removes the role of a project stakeholder from the task.
+    ///
+    /// # Arguments
+    ///
+    /// * `stakeholder_index` - The index of the stakeholder in the project.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Raci)` - The removed role.
+    /// * `None` - If the stakeholder had no role in the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::{Raci, Task};
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.assign_raci(0, Raci::Informed).unwrap();
+    ///
+    /// assert_eq!(task.unassign_raci(0), Some(Raci::Informed));
+    /// assert!(task.raci().is_empty());
+    /// ```
+    pub fn unassign_raci(&mut self, stakeholder_index: usize) -> Option<Raci> {
+        let position = self
+            .responsibilities
+            .iter()
+            .position(|(i, _)| *i == stakeholder_index)?;
+        Some(self.responsibilities.remove(position).1)
+    }
+
+    /// Returns the roles of the project stakeholders in the task, by stakeholder index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::{Raci, Task};
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// assert!(task.raci().is_empty());
+    /// task.assign_raci(2, Raci::Consulted).unwrap();
+    /// assert_eq!(task.raci(), &[(2, Raci::Consulted)]);
+    /// ```
+    pub fn raci(&self) -> &[(usize, Raci)] {
+        &self.responsibilities
+    }
+
+    /// Updates the stakeholder indices of the responsibilities after the project stakeholders
+    /// changed. `new_index` maps the old index of a stakeholder to the new one, or to `None` if
+    /// the stakeholder was removed, in which case its role is dropped.
+    pub(crate) fn reindex_responsibilities(&mut self, new_index: impl Fn(usize) -> Option<usize>) {
+        self.responsibilities = self
+            .responsibilities
+            .iter()
+            .filter_map(|&(i, raci)| Some((new_index(i)?, raci)))
+            .collect();
+    }
+
+    /// Records how much of a consumable project resource the task uses, replacing what was
+    /// recorded before. Recording zero removes the consumption.
+    ///
+    /// # Arguments
+    ///
+    /// * `resource_index` - The index of the consumable resource in the project.
+    /// * `amount` - How many units of the resource the task uses.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Patch up the troops");
+    /// task.set_consumption(0, 5);
+    /// task.set_consumption(0, 3);
+    /// assert_eq!(task.consumption(0), 3);
+    ///
+    /// task.set_consumption(0, 0);
+    /// assert!(task.consumptions().is_empty());
+    /// ```
+    pub fn set_consumption(&mut self, resource_index: usize, amount: u16) {
+        self.consumptions.retain(|(i, _)| *i != resource_index);
+        if amount > 0 {
+            self.consumptions.push((resource_index, amount));
+        }
+    }
+
+    /// Returns how much of a project resource the task uses, zero if it doesn't use it.
+    ///
+    /// # Arguments
+    ///
+    /// * `resource_index` - The index of the resource in the project.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Patch up the troops");
+    /// assert_eq!(task.consumption(0), 0);
+    /// task.set_consumption(0, 5);
+    /// assert_eq!(task.consumption(0), 5);
+    /// ```
+    pub fn consumption(&self, resource_index: usize) -> u16 {
+        self.consumptions
+            .iter()
+            .find(|(i, _)| *i == resource_index)
+            .map_or(0, |(_, amount)| *amount)
+    }
+
+    /// Returns the consumable project resources the task uses, by resource index, with how
+    /// many units of each.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Patch up the troops");
+    /// task.set_consumption(2, 5);
+    /// assert_eq!(task.consumptions(), &[(2, 5)]);
     /// ```
-    pub fn resources(&self) -> &[Resource] {
-        &self.resources
+    pub fn consumptions(&self) -> &[(usize, u16)] {
+        &self.consumptions
+    }
+
+    /// Updates the resource indices of the assignments and of the consumptions after the
+    /// project resources changed. `new_index` maps the old index of a resource to the new one,
+    /// or to `None` if the resource was removed, in which case the assignment and the
+    /// consumption are dropped. If two resources are merged into one, the first assignment is
+    /// kept, and the consumptions are added up.
+    pub(crate) fn reindex_resources(&mut self, new_index: impl Fn(usize) -> Option<usize>) {
+        let mut consumptions: Vec<(usize, u16)> = Vec::with_capacity(self.consumptions.len());
+        for &(resource_index, amount) in &self.consumptions {
+            let Some(resource_index) = new_index(resource_index) else {
+                continue;
+            };
+            match consumptions.iter_mut().find(|(i, _)| *i == resource_index) {
+                Some((_, total)) => *total = total.saturating_add(amount),
+                None => consumptions.push((resource_index, amount)),
+            }
+        }
+        self.consumptions = consumptions;
+
+        let mut assignments: Vec<Assignment> = Vec::with_capacity(self.assignments.len());
+        for assignment in &self.assignments {
+            let Some(resource_index) = new_index(assignment.resource_index) else {
+                continue;
+            };
+            if assignments
+                .iter()
+                .all(|a| a.resource_index != resource_index)
+            {
+                assignments.push(Assignment {
+                    resource_index,
+                    ..*assignment
+                });
+            }
+        }
+        self.assignments = assignments;
     }
 
     /// Edits the name of the task.
@@ -336,43 +1081,953 @@ impl Task {
     /// assert!(task.completed());
     /// ```
     pub fn completed(&self) -> bool {
-        self.completed
+        self.status == TaskStatus::Completed
     }
 
-    /// Marks the task as completed.
+    /// Whether the task should have finished by now, but isn't completed.
+    /// It's false if the task has no finish date.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The moment to compare the planned dates against.
     ///
     /// # Example
     ///
     /// ```
+    /// use chrono::{Duration, Utc};
     /// use planter_core::task::Task;
     ///
+    /// let now = Utc::now();
+    /// let mut task = Task::new("Become world leader");
+    /// assert!(!task.is_overdue(now));
+    /// task.edit_finish(now - Duration::days(1)).unwrap();
+    /// assert!(task.is_overdue(now));
+    /// task.toggle_completed();
+    /// assert!(!task.is_overdue(now));
+    /// ```
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        !self.completed() && self.finish.is_some_and(|finish| finish < now)
+    }
+
+    /// Whether the task is planned to be ongoing now, and isn't completed.
+    /// It's false if the task is missing the start or the finish date.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The moment to compare the planned dates against.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::task::Task;
+    ///
+    /// let now = Utc::now();
+    /// let mut task = Task::new("Become world leader");
+    /// task.edit_start(now - Duration::days(1)).unwrap();
+    /// assert!(!task.is_in_progress(now));
+    /// task.edit_finish(now + Duration::days(1)).unwrap();
+    /// assert!(task.is_in_progress(now));
+    /// ```
+    pub fn is_in_progress(&self, now: DateTime<Utc>) -> bool {
+        match (self.start, self.finish) {
+            (Some(start), Some(finish)) => !self.completed() && start <= now && now <= finish,
+            _ => false,
+        }
+    }
+
+    /// Whether the task is planned to start after now, and isn't completed.
+    /// It's false if the task has no start date.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The moment to compare the planned dates against.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::task::Task;
+    ///
+    /// let now = Utc::now();
+    /// let mut task = Task::new("Become world leader");
+    /// assert!(!task.is_upcoming(now));
+    /// task.edit_start(now + Duration::days(1)).unwrap();
+    /// assert!(task.is_upcoming(now));
+    /// ```
+    pub fn is_upcoming(&self, now: DateTime<Utc>) -> bool {
+        !self.completed() && self.start.is_some_and(|start| start > now)
+    }
+
+    /// Marks the task as completed, or as not started if it was already completed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::{Task, TaskStatus};
+    ///
     /// let mut task = Task::new("Become world leader");
     /// assert!(!task.completed());
     /// task.toggle_completed();
     /// assert!(task.completed());
     /// task.toggle_completed();
     /// assert!(!task.completed());
+    /// assert_eq!(task.status(), TaskStatus::NotStarted);
+    /// ```
+    pub fn toggle_completed(&mut self) {
+        self.set_status(if self.completed() {
+            TaskStatus::NotStarted
+        } else {
+            TaskStatus::Completed
+        });
+    }
+
+    /// Returns the status of the task. It's `TaskStatus::NotStarted` by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::{Task, TaskStatus};
+    ///
+    /// let task = Task::new("Become world leader");
+    /// assert_eq!(task.status(), TaskStatus::NotStarted);
+    /// ```
+    pub fn status(&self) -> TaskStatus {
+        self.status
+    }
+
+    /// Sets the status of the task. Completing the task sets it to 100% complete, while
+    /// marking it as not started sets it back to 0%.
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - The new status of the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::{Task, TaskStatus};
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.set_status(TaskStatus::Blocked);
+    /// assert_eq!(task.status(), TaskStatus::Blocked);
+    /// assert!(!task.completed());
+    /// ```
+    pub fn set_status(&mut self, status: TaskStatus) {
+        self.status = status;
+        match status {
+            TaskStatus::NotStarted => self.percent_complete = 0,
+            TaskStatus::Completed => self.percent_complete = 100,
+            TaskStatus::InProgress | TaskStatus::Blocked => {}
+        }
+    }
+
+    /// Returns how much of the task is done, from 0 to 100.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// assert_eq!(task.percent_complete(), 0);
+    /// task.toggle_completed();
+    /// assert_eq!(task.percent_complete(), 100);
+    /// ```
+    pub fn percent_complete(&self) -> u8 {
+        self.percent_complete
+    }
+
+    /// Sets how much of the task is done. Values above 100 are clamped to 100.
+    /// The status follows: 100% completes the task, 0% marks it as not started, and anything
+    /// in between marks a not started or completed task as in progress.
+    ///
+    /// # Arguments
+    ///
+    /// * `percent_complete` - How much of the task is done, from 0 to 100.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::{Task, TaskStatus};
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.set_percent_complete(40);
+    /// assert_eq!(task.status(), TaskStatus::InProgress);
+    ///
+    /// task.set_percent_complete(150);
+    /// assert_eq!(task.percent_complete(), 100);
+    /// assert!(task.completed());
+    ///
+    /// task.set_percent_complete(0);
+    /// assert_eq!(task.status(), TaskStatus::NotStarted);
+    /// ```
+    pub fn set_percent_complete(&mut self, percent_complete: u8) {
+        self.percent_complete = percent_complete.min(100);
+        self.status = match (self.percent_complete, self.status) {
+            (0, _) => TaskStatus::NotStarted,
+            (100, _) => TaskStatus::Completed,
+            (_, TaskStatus::NotStarted | TaskStatus::Completed) => TaskStatus::InProgress,
+            (_, status) => status,
+        };
+    }
+
+    /// Sets a custom field on the task, replacing any previous value for the same key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The name of the field.
+    /// * `value` - The value of the field.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.set_custom_field("Cost center", "R&D");
+    /// assert_eq!(task.custom_field("Cost center"), Some("R&D"));
+    /// ```
+    pub fn set_custom_field(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.custom_fields.insert(key.into(), value.into());
+    }
+
+    /// Returns the value of a custom field, if it's set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// assert!(task.custom_field("Cost center").is_none());
+    /// task.set_custom_field("Cost center", "R&D");
+    /// assert_eq!(task.custom_field("Cost center"), Some("R&D"));
+    /// ```
+    pub fn custom_field(&self, key: &str) -> Option<&str> {
+        self.custom_fields.get(key).map(String::as_str)
+    }
+
+    /// Removes a custom field from the task, returning its value if it was set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.set_custom_field("Cost center", "R&D");
+    /// assert_eq!(task.remove_custom_field("Cost center").as_deref(), Some("R&D"));
+    /// assert!(task.custom_field("Cost center").is_none());
+    /// ```
+    pub fn remove_custom_field(&mut self, key: &str) -> Option<String> {
+        self.custom_fields.remove(key)
+    }
+
+    /// Returns all the custom fields of the task, sorted by key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.set_custom_field("Cost center", "R&D");
+    /// task.set_custom_field("Jira", "WD-42");
+    /// assert_eq!(task.custom_fields().len(), 2);
+    /// ```
+    pub fn custom_fields(&self) -> &BTreeMap<String, String> {
+        &self.custom_fields
+    }
+
+    /// Adds a tag to the task. Leading and trailing whitespace is trimmed.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag to add.
+    ///
+    /// # Returns
+    ///
+    /// Whether the tag was added: it isn't if the task already has it, or if it's blank.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// assert!(task.add_tag(" risk "));
+    /// assert!(!task.add_tag("risk"));
+    /// assert!(!task.add_tag("  "));
+    /// assert!(task.has_tag("risk"));
+    /// ```
+    pub fn add_tag(&mut self, tag: impl Into<String>) -> bool {
+        let tag = tag.into();
+        let tag = tag.trim();
+        !tag.is_empty() && self.tags.insert(tag.to_owned())
+    }
+
+    /// Removes a tag from the task. Leading and trailing whitespace is trimmed.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag to remove.
+    ///
+    /// # Returns
+    ///
+    /// Whether the task had the tag.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.add_tag("risk");
+    /// assert!(task.remove_tag("risk"));
+    /// assert!(!task.remove_tag("risk"));
+    /// ```
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        self.tags.remove(tag.trim())
+    }
+
+    /// Returns whether the task has a tag. Leading and trailing whitespace is trimmed.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag to look for.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// assert!(!task.has_tag("risk"));
+    /// task.add_tag("risk");
+    /// assert!(task.has_tag("risk"));
+    /// ```
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(tag.trim())
+    }
+
+    /// Returns all the tags of the task, sorted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.add_tag("risk");
+    /// task.add_tag("frontend");
+    /// assert_eq!(task.tags().iter().collect::<Vec<_>>(), vec!["frontend", "risk"]);
+    /// ```
+    pub fn tags(&self) -> &BTreeSet<String> {
+        &self.tags
+    }
+
+    /// Returns the duration of the task. It's None by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Utc, Duration};
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// assert!(task.duration().is_none());
+    ///
+    /// task.edit_duration(Duration::hours(1).try_into().unwrap());
+    /// assert!(task.duration().unwrap() == Duration::hours(1).try_into().unwrap());
+    /// ```
+    pub fn duration(&self) -> Option<PositiveDuration> {
+        self.duration
+    }
+
+    /// Returns the time between the start and the finish of a split task, pauses included.
+    /// For any other task, it's the same as its duration.
+    pub(crate) fn span(&self) -> Option<PositiveDuration> {
+        match (self.start, self.finish) {
+            (Some(start), Some(finish)) if !self.segments.is_empty() => {
+                (finish - start).try_into().ok()
+            }
+            _ => self.duration,
+        }
+    }
+
+    /// Returns the intervals the task is worked on, if it was split with [`Task::split_at`].
+    /// The task starts with the first segment and finishes with the last one, and its
+    /// duration is the sum of the segments.
+    ///
+    /// # Returns
+    ///
+    /// The segments in order, or an empty slice if the task wasn't split.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let task = Task::new("Become world leader");
+    /// assert!(task.segments().is_empty());
+    /// ```
+    pub fn segments(&self) -> &[(DateTime<Utc>, DateTime<Utc>)] {
+        &self.segments
+    }
+
+    /// Splits the task in two segments at `when`, so that the work after it can be paused
+    /// with [`Task::delay_segment`]. Splitting doesn't change the dates nor the duration.
+    ///
+    /// # Arguments
+    ///
+    /// * `when` - The time to split the task at.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the task has no start and finish, or if `when` isn't strictly
+    /// within one of its segments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::task::Task;
+    ///
+    /// let start = Utc::now();
+    /// let mut task = Task::new("Become world leader");
+    /// task.edit_start(start).unwrap();
+    /// task.edit_duration(Duration::hours(3).try_into().unwrap());
+    ///
+    /// task.split_at(start + Duration::hours(1)).unwrap();
+    /// assert_eq!(
+    ///     task.segments(),
+    ///     &[
+    ///         (start, start + Duration::hours(1)),
+    ///         (start + Duration::hours(1), start + Duration::hours(3))
+    ///     ]
+    /// );
+    /// assert!(task.split_at(start + Duration::hours(1)).is_err());
+    /// ```
+    pub fn split_at(&mut self, when: DateTime<Utc>) -> anyhow::Result<()> {
+        let (Some(start), Some(finish)) = (self.start, self.finish) else {
+            bail!("Only tasks with a start and a finish can be split");
+        };
+        let mut segments = if self.segments.is_empty() {
+            vec![(start, finish)]
+        } else {
+            self.segments.clone()
+        };
+        let Some(i) = segments
+            .iter()
+            .position(|&(from, until)| from < when && when < until)
+        else {
+            bail!("The task isn't being worked on at {when}");
+        };
+        let until = segments[i].1;
+        segments[i].1 = when;
+        segments.insert(i + 1, (when, until));
+        self.segments = segments;
+        Ok(())
+    }
+
+    /// Pauses a split task before the segment with the given index, moving that segment and
+    /// the ones after it later. The duration stays the same, while the finish moves by `delay`.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the segment to delay. The first segment can't be delayed,
+    ///   use [`Task::edit_start`] to move the whole task instead.
+    /// * `delay` - How long the pause lasts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's no segment with the given index after the first one, or
+    /// if the finish would be past the latest representable date. The task is left untouched
+    /// in both cases.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::task::Task;
+    ///
+    /// let start = Utc::now();
+    /// let mut task = Task::new("Become world leader");
+    /// task.edit_start(start).unwrap();
+    /// task.edit_duration(Duration::hours(3).try_into().unwrap());
+    /// task.split_at(start + Duration::hours(1)).unwrap();
+    ///
+    /// // Waiting a day for approval.
+    /// task.delay_segment(1, Duration::days(1).try_into().unwrap()).unwrap();
+    /// assert_eq!(task.finish(), Some(start + Duration::days(1) + Duration::hours(3)));
+    /// assert_eq!(task.duration().unwrap(), Duration::hours(3).try_into().unwrap());
+    /// ```
+    pub fn delay_segment(&mut self, index: usize, delay: PositiveDuration) -> anyhow::Result<()> {
+        if index == 0 || index >= self.segments.len() {
+            bail!("The task has no segment {index} to delay");
+        }
+        self.shift_segments(index, *delay)
+    }
+
+    /// Moves the dates of the task by `offset`, keeping its duration and its segments.
+    /// Constraints, deadlines and baselines don't move. The task is left untouched on error.
+    pub(crate) fn shift(&mut self, offset: Duration) -> anyhow::Result<()> {
+        let shift = |date: DateTime<Utc>| {
+            date.checked_add_signed(offset)
+                .context("The dates would be out of range")
+        };
+        let start = self.start.map(shift).transpose()?;
+        let finish = self.finish.map(shift).transpose()?;
+        let segments = self
+            .segments
+            .iter()
+            .map(|&(start, finish)| Ok((shift(start)?, shift(finish)?)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        self.start = start;
+        self.finish = finish;
+        self.segments = segments;
+        Ok(())
+    }
+
+    /// Moves the segments from the one with index `from` onwards by `offset`, updating the
+    /// start and the finish of the task. The task is left untouched on error.
+    fn shift_segments(&mut self, from: usize, offset: Duration) -> anyhow::Result<()> {
+        let shifted = self.segments[from..]
+            .iter()
+            .map(|&(start, finish)| {
+                Some((
+                    start.checked_add_signed(offset)?,
+                    finish.checked_add_signed(offset)?,
+                ))
+            })
+            .collect::<Option<Vec<_>>>()
+            .context("The segments would be out of range")?;
+        self.segments.truncate(from);
+        self.segments.extend(shifted);
+        self.start = self.segments.first().map(|&(start, _)| start);
+        self.finish = self.segments.last().map(|&(_, finish)| finish);
+        Ok(())
+    }
+
+    /// Edits the effort of the task, the amount of work it requires, like 16 person-hours.
+    /// Unlike the duration, it doesn't depend on how many people work on the task.
+    ///
+    /// # Arguments
+    ///
+    /// * `effort` - The new effort of the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.edit_effort(Duration::hours(16).try_into().unwrap());
+    /// assert_eq!(task.effort().unwrap(), Duration::hours(16).try_into().unwrap());
+    /// assert!(task.duration().is_none());
+    /// ```
+    pub fn edit_effort(&mut self, effort: PositiveDuration) {
+        self.effort = Some(effort);
+    }
+
+    /// Returns the effort of the task. It's None by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let task = Task::new("Become world leader");
+    /// assert!(task.effort().is_none());
+    /// ```
+    pub fn effort(&self) -> Option<PositiveDuration> {
+        self.effort
+    }
+
+    /// Sets the contingency of the task, kept apart from its duration so that the estimate
+    /// isn't inflated. Scheduling only accounts for it if the project includes buffers.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - The contingency of the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.edit_duration(Duration::hours(8).try_into().unwrap());
+    /// task.edit_buffer(Duration::hours(2).try_into().unwrap());
+    /// assert_eq!(task.buffer().unwrap(), Duration::hours(2).try_into().unwrap());
+    /// assert_eq!(task.duration().unwrap(), Duration::hours(8).try_into().unwrap());
+    /// ```
+    pub fn edit_buffer(&mut self, buffer: PositiveDuration) {
+        self.buffer = Some(buffer);
+    }
+
+    /// Removes the contingency of the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.edit_buffer(Duration::hours(2).try_into().unwrap());
+    /// task.remove_buffer();
+    /// assert!(task.buffer().is_none());
+    /// ```
+    pub fn remove_buffer(&mut self) {
+        self.buffer = None;
+    }
+
+    /// Returns the contingency of the task. It's None by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let task = Task::new("Become world leader");
+    /// assert!(task.buffer().is_none());
+    /// ```
+    pub fn buffer(&self) -> Option<PositiveDuration> {
+        self.buffer
+    }
+
+    /// Returns the duration of the task with its contingency on top. Milestones last zero
+    /// regardless of their buffer.
+    ///
+    /// # Returns
+    ///
+    /// The duration plus the buffer, just the duration if there's no buffer, or None if
+    /// there's no duration or if the sum would exceed the maximum duration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.edit_duration(Duration::hours(8).try_into().unwrap());
+    /// assert_eq!(task.effective_duration(), task.duration());
+    ///
+    /// task.edit_buffer(Duration::hours(2).try_into().unwrap());
+    /// assert_eq!(task.effective_duration().unwrap(), Duration::hours(10).try_into().unwrap());
     /// ```
-    pub fn toggle_completed(&mut self) {
-        self.completed = !self.completed;
+    pub fn effective_duration(&self) -> Option<PositiveDuration> {
+        match self.buffer {
+            Some(buffer) if !self.milestone => self.duration?.checked_add(buffer).ok(),
+            _ => self.duration,
+        }
     }
 
-    /// Returns the duration of the task. It's None by default.
+    /// Stores the current start, finish and duration of the task as its baseline, replacing
+    /// the previous one if any, to compare them later with the actual dates.
     ///
     /// # Example
     ///
     /// ```
-    /// use chrono::{Utc, Duration};
+    /// use chrono::{Duration, Utc};
     /// use planter_core::task::Task;
     ///
+    /// let start = Utc::now();
     /// let mut task = Task::new("Become world leader");
-    /// assert!(task.duration().is_none());
+    /// task.edit_start(start).unwrap();
+    /// task.edit_duration(Duration::hours(2).try_into().unwrap());
+    /// task.set_baseline();
     ///
-    /// task.edit_duration(Duration::hours(1).try_into().unwrap());
-    /// assert!(task.duration().unwrap() == Duration::hours(1).try_into().unwrap());
+    /// assert_eq!(task.baseline_start(), Some(start));
+    /// assert_eq!(task.baseline_finish(), Some(start + Duration::hours(2)));
+    /// assert_eq!(task.baseline_duration(), Some(Duration::hours(2).try_into().unwrap()));
     /// ```
-    pub fn duration(&self) -> Option<PositiveDuration> {
-        self.duration
+    pub fn set_baseline(&mut self) {
+        self.baseline_start = self.start;
+        self.baseline_finish = self.finish;
+        self.baseline_duration = self.duration;
+    }
+
+    /// Returns the start of the task when the baseline was set. It's None by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let task = Task::new("Become world leader");
+    /// assert!(task.baseline_start().is_none());
+    /// ```
+    pub fn baseline_start(&self) -> Option<DateTime<Utc>> {
+        self.baseline_start
+    }
+
+    /// Returns the finish of the task when the baseline was set. It's None by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let task = Task::new("Become world leader");
+    /// assert!(task.baseline_finish().is_none());
+    /// ```
+    pub fn baseline_finish(&self) -> Option<DateTime<Utc>> {
+        self.baseline_finish
+    }
+
+    /// Returns the duration of the task when the baseline was set. It's None by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let task = Task::new("Become world leader");
+    /// assert!(task.baseline_duration().is_none());
+    /// ```
+    pub fn baseline_duration(&self) -> Option<PositiveDuration> {
+        self.baseline_duration
+    }
+
+    /// Returns how late the task finishes compared to its baseline. It's negative when the
+    /// task finishes early.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Duration)` - The finish of the task minus its baseline finish.
+    /// * `None` - If the task or its baseline has no finish.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::task::Task;
+    ///
+    /// let start = Utc::now();
+    /// let mut task = Task::new("Become world leader");
+    /// task.edit_start(start).unwrap();
+    /// task.edit_finish(start + Duration::hours(2)).unwrap();
+    /// assert!(task.schedule_variance().is_none());
+    ///
+    /// task.set_baseline();
+    /// task.edit_finish(start + Duration::hours(3)).unwrap();
+    /// assert_eq!(task.schedule_variance(), Some(Duration::hours(1)));
+    /// ```
+    pub fn schedule_variance(&self) -> Option<Duration> {
+        Some(self.finish? - self.baseline_finish?)
+    }
+
+    /// Assigns the task to a phase of the project, replacing the previous one if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `phase` - The phase the task belongs to.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Phase, task::Task};
+    ///
+    /// let mut task = Task::new("Draw the master plan");
+    /// task.set_phase(Phase::Design);
+    /// assert_eq!(task.phase(), Some(Phase::Design));
+    /// ```
+    pub fn set_phase(&mut self, phase: Phase) {
+        self.phase = Some(phase);
+    }
+
+    /// Removes the task from its phase.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Phase, task::Task};
+    ///
+    /// let mut task = Task::new("Draw the master plan");
+    /// task.set_phase(Phase::Design);
+    /// task.remove_phase();
+    /// assert!(task.phase().is_none());
+    /// ```
+    pub fn remove_phase(&mut self) {
+        self.phase = None;
+    }
+
+    /// Returns the phase of the project the task belongs to. It's None by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let task = Task::new("Draw the master plan");
+    /// assert!(task.phase().is_none());
+    /// ```
+    pub fn phase(&self) -> Option<Phase> {
+        self.phase
+    }
+
+    /// Binds the task to a date, replacing the previous constraint if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `constraint` - The new constraint of the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Utc;
+    /// use planter_core::task::{Task, TaskConstraint};
+    ///
+    /// let now = Utc::now();
+    /// let mut task = Task::new("Become world leader");
+    /// task.set_constraint(TaskConstraint::StartNoEarlierThan(now));
+    /// assert_eq!(task.constraint(), Some(TaskConstraint::StartNoEarlierThan(now)));
+    /// ```
+    pub fn set_constraint(&mut self, constraint: TaskConstraint) {
+        self.constraint = Some(constraint);
+    }
+
+    /// Removes the constraint of the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Utc;
+    /// use planter_core::task::{Task, TaskConstraint};
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.set_constraint(TaskConstraint::MustStartOn(Utc::now()));
+    /// task.remove_constraint();
+    /// assert!(task.constraint().is_none());
+    /// ```
+    pub fn remove_constraint(&mut self) {
+        self.constraint = None;
+    }
+
+    /// Returns the constraint of the task. It's None by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let task = Task::new("Become world leader");
+    /// assert!(task.constraint().is_none());
+    /// ```
+    pub fn constraint(&self) -> Option<TaskConstraint> {
+        self.constraint
+    }
+
+    /// Sets the date the task should be finished by. Unlike a constraint, a deadline
+    /// is a target: missing it is a warning rather than an inconsistency.
+    ///
+    /// # Arguments
+    ///
+    /// * `deadline` - The new deadline of the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Utc;
+    /// use planter_core::task::Task;
+    ///
+    /// let deadline = Utc::now();
+    /// let mut task = Task::new("Become world leader");
+    /// task.set_deadline(deadline);
+    /// assert_eq!(task.deadline(), Some(deadline));
+    /// ```
+    pub fn set_deadline(&mut self, deadline: DateTime<Utc>) {
+        self.deadline = Some(deadline);
+    }
+
+    /// Removes the deadline of the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Utc;
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("Become world leader");
+    /// task.set_deadline(Utc::now());
+    /// task.remove_deadline();
+    /// assert!(task.deadline().is_none());
+    /// ```
+    pub fn remove_deadline(&mut self) {
+        self.deadline = None;
+    }
+
+    /// Returns the deadline of the task. It's None by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let task = Task::new("Become world leader");
+    /// assert!(task.deadline().is_none());
+    /// ```
+    pub fn deadline(&self) -> Option<DateTime<Utc>> {
+        self.deadline
+    }
+
+    /// Returns whether the task is a milestone. It's false by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let mut task = Task::new("World domination achieved");
+    /// assert!(!task.is_milestone());
+    ///
+    /// task.set_milestone(true);
+    /// assert!(task.is_milestone());
+    /// ```
+    pub fn is_milestone(&self) -> bool {
+        self.milestone
+    }
+
+    /// Turns the task into a milestone, or back into a regular task. A milestone lasts zero,
+    /// and its start and finish times are the same: setting it keeps the start time, or the
+    /// finish time if there's no start. Turning a milestone back into a regular task keeps
+    /// its zero duration until it's edited.
+    ///
+    /// # Arguments
+    ///
+    /// * `milestone` - Whether the task is a milestone.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::task::Task;
+    ///
+    /// let start = Utc::now();
+    /// let mut task = Task::new("World domination achieved");
+    /// task.edit_start(start).unwrap();
+    /// task.edit_duration(Duration::hours(2).try_into().unwrap());
+    ///
+    /// task.set_milestone(true);
+    /// assert!(task.duration().unwrap().is_zero());
+    /// assert_eq!(task.finish(), Some(start));
+    /// ```
+    pub fn set_milestone(&mut self, milestone: bool) {
+        self.milestone = milestone;
+        if milestone {
+            self.segments.clear();
+            let date = self.start.or(self.finish);
+            self.start = date;
+            self.finish = date;
+            self.duration = Some(PositiveDuration::default());
+        }
     }
 }
 
@@ -391,6 +2046,8 @@ pub mod test_utils {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use chrono::Duration;
     use proptest::prelude::*;
 
@@ -460,6 +2117,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn custom_fields_can_be_set_replaced_and_removed() {
+        let mut task = Task::new("World domination");
+
+        task.set_custom_field("Jira", "WD-1");
+        task.set_custom_field("Jira", "WD-42");
+        assert_eq!(task.custom_field("Jira"), Some("WD-42"));
+        assert_eq!(task.custom_fields().len(), 1);
+
+        assert_eq!(task.remove_custom_field("Jira"), Some("WD-42".to_owned()));
+        assert!(task.remove_custom_field("Jira").is_none());
+        assert!(task.custom_fields().is_empty());
+    }
+
     #[test]
     fn edit_start_returns_error_when_too_far_apart() {
         let milliseconds = MAX_DURATION + 1;
@@ -483,4 +2154,278 @@ mod tests {
 
         assert!(task.edit_finish(finish).is_err());
     }
+
+    #[test]
+    fn completed_follows_the_status() {
+        let mut task = Task::new("World domination");
+
+        for status in TaskStatus::ALL {
+            task.set_status(status);
+            assert_eq!(task.completed(), status == TaskStatus::Completed);
+        }
+
+        // Toggling an unfinished task completes it, whatever its status was.
+        task.set_status(TaskStatus::Blocked);
+        task.toggle_completed();
+        assert_eq!(task.status(), TaskStatus::Completed);
+        task.toggle_completed();
+        assert_eq!(task.status(), TaskStatus::NotStarted);
+    }
+
+    #[test]
+    fn far_apart_dates_are_rejected_without_side_effects() {
+        let now = Utc::now();
+        let mut task = Task::new("Become world leader");
+        task.edit_finish(now).unwrap();
+        assert!(task.edit_start(DateTime::<Utc>::MIN_UTC).is_err());
+        assert_eq!(task.start(), None);
+        assert_eq!(task.finish(), Some(now));
+        assert_eq!(task.duration(), None);
+
+        let mut task = Task::new("Become world leader");
+        task.edit_start(now).unwrap();
+        assert!(task.edit_finish(DateTime::<Utc>::MAX_UTC).is_err());
+        assert_eq!(task.start(), Some(now));
+        assert_eq!(task.finish(), None);
+
+        let mut task = Task::new("Become world leader");
+        task.edit_duration(Duration::days(2).try_into().unwrap());
+        assert!(task.edit_start(DateTime::<Utc>::MAX_UTC).is_err());
+        assert_eq!(task.start(), None);
+
+        let mut task = Task::new("Become world leader");
+        task.edit_start(DateTime::<Utc>::MAX_UTC - Duration::days(1))
+            .unwrap();
+        task.edit_duration(Duration::days(2).try_into().unwrap());
+        assert_eq!(task.finish(), Some(DateTime::<Utc>::MAX_UTC));
+    }
+
+    #[test]
+    fn start_after_finish_pushes_the_finish() {
+        let now = Utc::now();
+        let mut task = Task::new("Become world leader");
+        task.edit_finish(now).unwrap();
+        task.edit_start(now + Duration::hours(1)).unwrap();
+
+        assert_eq!(task.finish(), Some(now + Duration::hours(1)));
+        assert_eq!(*task.duration().unwrap(), Duration::zero());
+    }
+
+    #[test]
+    fn milestones_stay_zero_length() {
+        let start = Utc::now();
+        let mut task = Task::new("World domination achieved");
+        task.edit_finish(start + Duration::hours(3)).unwrap();
+        task.set_milestone(true);
+        assert_eq!(task.start(), Some(start + Duration::hours(3)));
+
+        task.edit_duration(Duration::hours(2).try_into().unwrap());
+        assert!(task.duration().unwrap().is_zero());
+        task.edit_start(start).unwrap();
+        assert_eq!(task.finish(), Some(start));
+        task.edit_finish(start + Duration::hours(1)).unwrap();
+        assert_eq!(task.start(), Some(start + Duration::hours(1)));
+        assert!(task.duration().unwrap().is_zero());
+
+        task.set_milestone(false);
+        task.edit_duration(Duration::hours(2).try_into().unwrap());
+        assert_eq!(task.finish(), Some(start + Duration::hours(3)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn tasks_round_trip_through_json() {
+        let mut task = Task::new("Become world leader");
+        task.edit_description("With a crowbar");
+        task.edit_start(Utc::now()).unwrap();
+        task.edit_duration(Duration::hours(2).try_into().unwrap());
+        task.edit_effort(Duration::hours(4).try_into().unwrap());
+        task.set_status(TaskStatus::InProgress);
+        task.set_custom_field("Jira", "WD-42");
+        task.set_constraint(TaskConstraint::StartNoEarlierThan(Utc::now()));
+        task.add_resource(0);
+
+        let json = serde_json::to_string(&task).unwrap();
+        assert_eq!(serde_json::from_str::<Task>(&json).unwrap(), task);
+    }
+
+    #[test]
+    fn assignments_reject_invalid_units() {
+        let mut task = Task::new("Become world leader");
+        for units in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            assert!(task.assign_resource(0, units).is_err());
+        }
+        assert!(task.assignments().is_empty());
+        assert!(task.unassign_resource(0).is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialized_assignments_reject_invalid_units() {
+        let mut task = Task::new("Become world leader");
+        task.assign_resource(0, 0.5).unwrap();
+        let json = serde_json::to_string(&task).unwrap();
+        assert_eq!(serde_json::from_str::<Task>(&json).unwrap(), task);
+
+        for units in ["0.0", "-1.0", "null"] {
+            let invalid = json.replace(r#""units":0.5"#, &format!(r#""units":{units}"#));
+            assert_ne!(invalid, json);
+            assert!(serde_json::from_str::<Task>(&invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn tags_are_trimmed_and_deduplicated() {
+        let mut task = Task::new("Become world leader");
+        for tag in ["risk", " risk", "risk\t", "", " ", "frontend"] {
+            task.add_tag(tag);
+        }
+
+        assert_eq!(task.tags().len(), 2);
+        assert!(task.has_tag(" frontend "));
+        assert!(task.remove_tag("risk "));
+        assert_eq!(task.tags().iter().collect::<Vec<_>>(), vec!["frontend"]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialized_ids_are_kept_and_not_handed_out_again() {
+        let task = Task::new("Become world leader");
+        let json = serde_json::to_string(&task)
+            .unwrap()
+            .replace(&format!(r#""id":{}"#, task.id()), r#""id":4000000000"#);
+
+        let deserialized: Task = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.id(), 4_000_000_000);
+        assert!(Task::new("Profit").id() > 4_000_000_000);
+    }
+
+    #[test]
+    fn equal_tasks_hash_the_same() {
+        let mut task = Task::new("Become world leader");
+        task.assign_resource(0, 0.5).unwrap();
+        task.add_tag("risk");
+        let mut copy = Task::new("Become world leader");
+        copy.assign_resource(0, 0.5).unwrap();
+        copy.add_tag("risk");
+
+        let mut tasks = HashSet::new();
+        assert!(tasks.insert(task.clone()));
+        assert!(!tasks.insert(copy));
+        assert!(tasks.insert(Task::new("Profit")));
+        task.edit_description("Step by step");
+        assert!(tasks.insert(task));
+        assert_eq!(tasks.len(), 3);
+    }
+
+    #[test]
+    fn split_tasks_keep_their_working_time() {
+        let start = Utc::now();
+        let hours = Duration::hours;
+        let mut task = Task::new("Get the plan approved");
+        assert!(task.split_at(start).is_err());
+        task.edit_start(start).unwrap();
+        task.edit_duration(hours(4).try_into().unwrap());
+
+        task.split_at(start + hours(1)).unwrap();
+        task.split_at(start + hours(3)).unwrap();
+        assert!(task.delay_segment(0, hours(1).try_into().unwrap()).is_err());
+        task.delay_segment(2, hours(10).try_into().unwrap())
+            .unwrap();
+        task.delay_segment(1, hours(2).try_into().unwrap()).unwrap();
+        assert_eq!(
+            task.segments(),
+            &[
+                (start, start + hours(1)),
+                (start + hours(3), start + hours(5)),
+                (start + hours(15), start + hours(16)),
+            ]
+        );
+        assert_eq!(*task.duration().unwrap(), hours(4));
+        assert_eq!(task.finish(), Some(start + hours(16)));
+        assert!(task.split_at(start + hours(2)).is_err());
+
+        // Moving the task keeps the pauses, editing the duration joins the segments.
+        task.edit_start(start + hours(1)).unwrap();
+        assert_eq!(task.segments()[2], (start + hours(16), start + hours(17)));
+        assert_eq!(*task.duration().unwrap(), hours(4));
+        task.edit_duration(hours(4).try_into().unwrap());
+        assert!(task.segments().is_empty());
+        assert_eq!(task.finish(), Some(start + hours(5)));
+    }
+
+    #[test]
+    fn tasks_have_at_most_one_accountable_stakeholder() {
+        let mut task = Task::new("Approve the budget");
+        task.assign_raci(0, Raci::Responsible).unwrap();
+        task.assign_raci(1, Raci::Accountable).unwrap();
+        // Being accountable again is fine, someone else isn't.
+        task.assign_raci(1, Raci::Accountable).unwrap();
+        assert!(task.assign_raci(0, Raci::Accountable).is_err());
+        assert_eq!(task.raci()[0], (0, Raci::Responsible));
+
+        task.assign_raci(1, Raci::Informed).unwrap();
+        task.assign_raci(0, Raci::Accountable).unwrap();
+        assert_eq!(task.raci(), &[(0, Raci::Accountable), (1, Raci::Informed)]);
+
+        task.reindex_responsibilities(|i| i.checked_sub(1));
+        assert_eq!(task.raci(), &[(0, Raci::Informed)]);
+    }
+
+    #[test]
+    fn calendar_durations_skip_nights_and_weekends() {
+        let calendar = Calendar::default();
+        let friday = chrono::NaiveDate::from_ymd_opt(2025, 6, 6).unwrap();
+        let monday = chrono::NaiveDate::from_ymd_opt(2025, 6, 9).unwrap();
+        let at = |day: chrono::NaiveDate, hour| day.and_hms_opt(hour, 0, 0).unwrap().and_utc();
+        let two_days = || Duration::hours(16).try_into().unwrap();
+
+        let mut task = Task::new("Become world leader");
+        task.edit_duration_with_calendar(two_days(), &calendar);
+        assert_eq!(task.finish(), None);
+
+        task.edit_start(at(friday, 13)).unwrap();
+        task.edit_duration_with_calendar(two_days(), &calendar);
+        assert_eq!(task.finish(), Some(at(monday + Duration::days(1), 13)));
+
+        task.set_milestone(true);
+        task.edit_duration_with_calendar(two_days(), &calendar);
+        assert_eq!(task.finish(), Some(at(friday, 13)));
+    }
+
+    #[test]
+    fn a_task_is_upcoming_then_in_progress_then_overdue() {
+        let start = Utc::now();
+        let finish = start + Duration::hours(8);
+        let mut task = Task::new("Become world leader");
+        task.edit_start(start).unwrap();
+        task.edit_finish(finish).unwrap();
+
+        let state = |task: &Task, now| {
+            (
+                task.is_upcoming(now),
+                task.is_in_progress(now),
+                task.is_overdue(now),
+            )
+        };
+        assert_eq!(
+            state(&task, start - Duration::hours(1)),
+            (true, false, false)
+        );
+        assert_eq!(state(&task, start), (false, true, false));
+        assert_eq!(state(&task, finish), (false, true, false));
+        assert_eq!(
+            state(&task, finish + Duration::hours(1)),
+            (false, false, true)
+        );
+
+        task.toggle_completed();
+        for now in [
+            start - Duration::hours(1),
+            start,
+            finish + Duration::hours(1),
+        ] {
+            assert_eq!(state(&task, now), (false, false, false));
+        }
+    }
 }
@@ -0,0 +1,307 @@
+use anyhow::{Context, bail};
+use chrono::{DateTime, Utc};
+
+use super::{Task, TaskStatus};
+use crate::duration::PositiveDuration;
+
+/// Builds a [`Task`] field by field. Unlike the `edit_*` methods of a task, the order the dates
+/// and the duration are set in doesn't matter: they are reconciled once, when building it.
+#[derive(Debug, Default)]
+pub struct TaskBuilder {
+    /// The name of the task.
+    name: String,
+    /// The description of the task.
+    description: String,
+    /// The start time of the task.
+    start: Option<DateTime<Utc>>,
+    /// The finish time of the task.
+    finish: Option<DateTime<Utc>>,
+    /// The duration of the task.
+    duration: Option<PositiveDuration>,
+    /// The progress state of the task.
+    status: TaskStatus,
+}
+
+impl TaskBuilder {
+    /// Sets the name of the task.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let task = Task::builder().name("Find a crowbar").build().unwrap();
+    /// assert_eq!(task.name(), "Find a crowbar");
+    /// ```
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets the description of the task.
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - The description of the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::Task;
+    ///
+    /// let task = Task::builder().description("Any crowbar will do").build().unwrap();
+    /// assert_eq!(task.description(), "Any crowbar will do");
+    /// ```
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Sets the start time of the task.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The start time of the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::task::Task;
+    ///
+    /// let start = Utc::now();
+    /// let task = Task::builder()
+    ///     .duration(Duration::hours(2).try_into().unwrap())
+    ///     .start(start)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(task.finish(), Some(start + Duration::hours(2)));
+    /// ```
+    pub fn start(mut self, start: DateTime<Utc>) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Sets the finish time of the task.
+    ///
+    /// # Arguments
+    ///
+    /// * `finish` - The finish time of the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::task::Task;
+    ///
+    /// let finish = Utc::now();
+    /// let task = Task::builder()
+    ///     .finish(finish)
+    ///     .duration(Duration::hours(2).try_into().unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(task.start(), Some(finish - Duration::hours(2)));
+    /// ```
+    pub fn finish(mut self, finish: DateTime<Utc>) -> Self {
+        self.finish = Some(finish);
+        self
+    }
+
+    /// Sets the duration of the task.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The duration of the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use planter_core::task::Task;
+    ///
+    /// let task = Task::builder()
+    ///     .duration(Duration::hours(2).try_into().unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(*task.duration().unwrap(), Duration::hours(2));
+    /// ```
+    pub fn duration(mut self, duration: PositiveDuration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Sets the status of the task.
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - The progress state of the task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::task::{Task, TaskStatus};
+    ///
+    /// let task = Task::builder().status(TaskStatus::Completed).build().unwrap();
+    /// assert!(task.completed());
+    /// ```
+    pub fn status(mut self, status: TaskStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Builds the task. Any two of start, finish and duration determine the third one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the task finishes before it starts, if start, finish and duration
+    /// are all set and don't match, or if the dates are out of range or too far apart.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::task::Task;
+    ///
+    /// let start = Utc::now();
+    /// let task = Task::builder()
+    ///     .name("Find a crowbar")
+    ///     .finish(start + Duration::hours(3))
+    ///     .start(start)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(*task.duration().unwrap(), Duration::hours(3));
+    ///
+    /// let inconsistent = Task::builder()
+    ///     .start(start)
+    ///     .finish(start + Duration::hours(3))
+    ///     .duration(Duration::hours(1).try_into().unwrap())
+    ///     .build();
+    /// assert!(inconsistent.is_err());
+    /// ```
+    pub fn build(self) -> anyhow::Result<Task> {
+        let (start, finish, duration) = match (self.start, self.finish, self.duration) {
+            (Some(start), Some(finish), duration) => {
+                if finish < start {
+                    bail!("The task finishes before it starts");
+                }
+                let span: PositiveDuration = (finish - start)
+                    .try_into()
+                    .context("Start time and finish time were too far apart")?;
+                if duration.is_some_and(|duration| duration != span) {
+                    bail!("The duration doesn't match the time between start and finish");
+                }
+                (Some(start), Some(finish), Some(span))
+            }
+            (Some(start), None, Some(duration)) => {
+                let finish = start
+                    .checked_add_signed(*duration)
+                    .context("The finish time would be out of range")?;
+                (Some(start), Some(finish), Some(duration))
+            }
+            (None, Some(finish), Some(duration)) => {
+                let start = finish
+                    .checked_sub_signed(*duration)
+                    .context("The start time would be out of range")?;
+                (Some(start), Some(finish), Some(duration))
+            }
+            dates => dates,
+        };
+
+        let mut task = Task::new(self.name);
+        task.description = self.description;
+        task.start = start;
+        task.finish = finish;
+        task.duration = duration;
+        task.set_status(self.status);
+        Ok(task)
+    }
+}
+
+impl Task {
+    /// Returns a builder for a new task, without name, dates nor duration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use planter_core::task::{Task, TaskStatus};
+    ///
+    /// let start = Utc::now();
+    /// let task = Task::builder()
+    ///     .name("Find a crowbar")
+    ///     .start(start)
+    ///     .duration(Duration::hours(2).try_into().unwrap())
+    ///     .status(TaskStatus::InProgress)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(task.finish(), Some(start + Duration::hours(2)));
+    /// assert_eq!(task.status(), TaskStatus::InProgress);
+    /// ```
+    pub fn builder() -> TaskBuilder {
+        TaskBuilder::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, Utc};
+
+    use crate::task::{Task, TaskStatus};
+
+    #[test]
+    fn any_two_of_start_finish_and_duration_give_the_third() {
+        let start = Utc::now();
+        let finish = start + Duration::hours(2);
+        let duration = || Duration::hours(2).try_into().unwrap();
+
+        for builder in [
+            Task::builder().start(start).finish(finish),
+            Task::builder().finish(finish).duration(duration()),
+            Task::builder().duration(duration()).start(start),
+            Task::builder()
+                .start(start)
+                .duration(duration())
+                .finish(finish),
+        ] {
+            let task = builder.build().unwrap();
+            assert_eq!(task.start(), Some(start));
+            assert_eq!(task.finish(), Some(finish));
+            assert_eq!(task.duration(), Some(duration()));
+        }
+
+        let task = Task::builder().finish(finish).build().unwrap();
+        assert_eq!((task.start(), task.duration()), (None, None));
+        assert_eq!(Task::builder().build().unwrap(), Task::default());
+        assert_eq!(
+            Task::builder()
+                .status(TaskStatus::Completed)
+                .build()
+                .unwrap()
+                .percent_complete(),
+            100
+        );
+    }
+
+    #[test]
+    fn inconsistent_dates_are_rejected() {
+        let start = Utc::now();
+        for builder in [
+            Task::builder()
+                .start(start)
+                .finish(start - Duration::hours(1)),
+            Task::builder()
+                .start(start)
+                .finish(start + Duration::hours(1))
+                .duration(Duration::hours(2).try_into().unwrap()),
+            Task::builder()
+                .start(DateTime::<Utc>::MAX_UTC)
+                .duration(Duration::hours(1).try_into().unwrap()),
+        ] {
+            assert!(builder.build().is_err());
+        }
+    }
+}
@@ -0,0 +1,429 @@
+use thiserror::Error;
+
+use crate::{project::Project, resources::Resource};
+
+/// How serious a [`ValidationIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Something worth knowing, that doesn't need to be acted upon.
+    Info,
+    /// Something that is probably a mistake, but doesn't prevent working with the project.
+    Warning,
+    /// Something that makes the project inconsistent and needs to be fixed.
+    Error,
+}
+
+/// A problem found while validating a [`Project`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// The subtask relationships loop back to a task that was already visited.
+    /// The tasks forming the loop are listed in order.
+    #[error("Tasks {0:?} are subtasks of each other")]
+    SubtaskCycle(Vec<usize>),
+    /// The task with the given index has no duration.
+    #[error("Task {0} has no duration")]
+    MissingDuration(usize),
+    /// The task with the given index has no personnel assigned to it.
+    #[error("Task {0} has no personnel assigned")]
+    UnstaffedTask(usize),
+    /// The project has no start date, so it can't be scheduled.
+    #[error("The project has no start date")]
+    MissingStartDate,
+    /// The dates of the task with the given index break its constraint.
+    #[error("Task {0} breaks its constraint")]
+    ConstraintViolated(usize),
+    /// The task with the given index finishes after its deadline.
+    #[error("Task {0} misses its deadline")]
+    MissedDeadline(usize),
+    /// The task with the given index finishes before it starts.
+    #[error("Task {0} finishes before it starts")]
+    FinishBeforeStart(usize),
+    /// A subtask relationship references a task that doesn't exist.
+    #[error("Task {subtask} can't be a subtask of task {task}, one of them doesn't exist")]
+    DanglingSubtask {
+        /// The index of the parent task.
+        task: usize,
+        /// The index of the subtask.
+        subtask: usize,
+    },
+    /// The task with the given index has no dates and no time relationships, so nothing
+    /// places it in time.
+    #[error("Task {0} has no dates and no relationships")]
+    IsolatedTask(usize),
+    /// The material resource with the given index has a quantity, but no cost per unit.
+    #[error("Resource {0} has a quantity but no cost")]
+    UnpricedResource(usize),
+    /// A task uses a resource outside of the resource's availability windows.
+    #[error("Task {task} uses resource {resource} when it isn't available")]
+    ResourceUnavailable {
+        /// The index of the resource.
+        resource: usize,
+        /// The index of the task.
+        task: usize,
+    },
+}
+
+impl ValidationIssue {
+    /// Returns how serious the issue is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::validation::{Severity, ValidationIssue};
+    ///
+    /// assert_eq!(ValidationIssue::SubtaskCycle(vec![0, 1]).severity(), Severity::Error);
+    /// assert_eq!(ValidationIssue::UnstaffedTask(0).severity(), Severity::Warning);
+    /// ```
+    pub fn severity(&self) -> Severity {
+        match self {
+            ValidationIssue::SubtaskCycle(_)
+            | ValidationIssue::ConstraintViolated(_)
+            | ValidationIssue::FinishBeforeStart(_)
+            | ValidationIssue::DanglingSubtask { .. }
+            | ValidationIssue::ResourceUnavailable { .. } => Severity::Error,
+            ValidationIssue::MissingDuration(_)
+            | ValidationIssue::UnstaffedTask(_)
+            | ValidationIssue::MissedDeadline(_)
+            | ValidationIssue::IsolatedTask(_)
+            | ValidationIssue::UnpricedResource(_) => Severity::Warning,
+            ValidationIssue::MissingStartDate => Severity::Info,
+        }
+    }
+}
+
+impl Project {
+    /// Checks the whole project and returns every issue found, whatever its [`Severity`],
+    /// instead of stopping at the first one. Run it after scheduling, to find the tasks whose
+    /// dates break their constraint or miss their deadline.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task, validation::ValidationIssue};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Find a crowbar"));
+    ///
+    /// let issues = project.validate();
+    /// assert!(issues.contains(&ValidationIssue::MissingDuration(0)));
+    /// assert!(issues.contains(&ValidationIssue::UnstaffedTask(0)));
+    /// assert!(issues.contains(&ValidationIssue::MissingStartDate));
+    /// ```
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        if self.start_date().is_none() {
+            issues.push(ValidationIssue::MissingStartDate);
+        }
+        issues.extend(
+            self.subtask_cycles()
+                .into_iter()
+                .map(ValidationIssue::SubtaskCycle),
+        );
+        issues.extend(
+            self.subtask_pairs()
+                .filter(|&(task, subtask)| {
+                    !self.contains_task(task) || !self.contains_task(subtask)
+                })
+                .map(|(task, subtask)| ValidationIssue::DanglingSubtask { task, subtask }),
+        );
+        for (i, task) in self.tasks().enumerate() {
+            if task.duration().is_none() {
+                issues.push(ValidationIssue::MissingDuration(i));
+            }
+            if !task.assignments().iter().any(|assignment| {
+                matches!(
+                    self.resources().get(assignment.resource_index()),
+                    Some(Resource::Personnel { .. })
+                )
+            }) {
+                issues.push(ValidationIssue::UnstaffedTask(i));
+            }
+            if let (Some(constraint), Some(start), Some(finish)) =
+                (task.constraint(), task.start(), task.finish())
+                && !constraint.is_satisfied_by(start, finish)
+            {
+                issues.push(ValidationIssue::ConstraintViolated(i));
+            }
+            if let (Some(deadline), Some(finish)) = (task.deadline(), task.finish())
+                && finish > deadline
+            {
+                issues.push(ValidationIssue::MissedDeadline(i));
+            }
+            if let (Some(start), Some(finish)) = (task.start(), task.finish())
+                && finish < start
+            {
+                issues.push(ValidationIssue::FinishBeforeStart(i));
+            }
+            if task.start().is_none()
+                && task.finish().is_none()
+                && self.predecessors_indices(i).next().is_none()
+                && self.successors_indices(i).next().is_none()
+            {
+                issues.push(ValidationIssue::IsolatedTask(i));
+            }
+        }
+        for (i, resource) in self.resources().iter().enumerate() {
+            if let Resource::Material(material) = resource
+                && material.quantity().is_some_and(|quantity| quantity > 0)
+                && material.cost_per_unit().is_none()
+            {
+                issues.push(ValidationIssue::UnpricedResource(i));
+            }
+        }
+        issues.extend(
+            self.unavailable_uses()
+                .into_iter()
+                .map(|(resource, task)| ValidationIssue::ResourceUnavailable { resource, task }),
+        );
+
+        issues
+    }
+
+    /// Same as [`Project::validate`]: checks the whole project and returns every issue found,
+    /// whatever its [`Severity`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Find a crowbar"));
+    ///
+    /// assert_eq!(project.validate_all(), project.validate());
+    /// ```
+    pub fn validate_all(&self) -> Vec<ValidationIssue> {
+        self.validate()
+    }
+
+    /// Checks the whole project and returns only the issues with [`Severity::Error`],
+    /// the ones that make the project inconsistent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use planter_core::{project::Project, task::Task};
+    ///
+    /// let mut project = Project::new("World domination");
+    /// project.add_task(Task::new("Find a crowbar"));
+    /// project.add_task(Task::new("Find a stimpack"));
+    /// assert!(project.validate_strict().is_empty());
+    ///
+    /// project.add_subtask(0, 1);
+    /// project.add_subtask(1, 0);
+    /// assert_eq!(project.validate_strict().len(), 1);
+    /// ```
+    pub fn validate_strict(&self) -> Vec<ValidationIssue> {
+        self.validate()
+            .into_iter()
+            .filter(|issue| issue.severity() == Severity::Error)
+            .collect()
+    }
+
+    /// Finds the loops in the subtask hierarchy, each one listed once.
+    fn subtask_cycles(&self) -> Vec<Vec<usize>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Visit {
+            New,
+            InProgress,
+            Done,
+        }
+
+        let count = self.task_count();
+        let mut state = vec![Visit::New; count];
+        let mut cycles = Vec::new();
+
+        for root in 0..count {
+            if state[root] != Visit::New {
+                continue;
+            }
+            // Iterative depth first search, keeping the current path to report cycles.
+            let mut path = vec![root];
+            let mut pending = vec![self.subtasks(root)];
+            state[root] = Visit::InProgress;
+
+            while let Some(children) = pending.last_mut() {
+                let Some(child) = children.pop() else {
+                    pending.pop();
+                    if let Some(done) = path.pop() {
+                        state[done] = Visit::Done;
+                    }
+                    continue;
+                };
+                match state.get(child) {
+                    Some(Visit::New) => {
+                        state[child] = Visit::InProgress;
+                        path.push(child);
+                        pending.push(self.subtasks(child));
+                    }
+                    Some(Visit::InProgress) => {
+                        let start = path.iter().position(|&i| i == child).unwrap_or_default();
+                        cycles.push(path[start..].to_vec());
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        cycles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::{
+        person::Person,
+        project::{Project, TimeRelationship},
+        resources::{Material, Resource},
+        task::{Task, TaskConstraint},
+    };
+
+    use super::{Severity, ValidationIssue};
+
+    #[test]
+    fn subtask_cycles_are_errors() {
+        let mut project = Project::new("World domination");
+        project.add_task(Task::new("Find a crowbar"));
+        project.add_task(Task::new("Find a stimpack"));
+        project.add_task(Task::new("Profit"));
+        project.add_subtask(0, 1);
+        project.add_subtask(1, 2);
+        project.add_subtask(2, 0);
+
+        let issues = project.validate_strict();
+        assert_eq!(issues, vec![ValidationIssue::SubtaskCycle(vec![0, 1, 2])]);
+        assert_eq!(issues[0].severity(), Severity::Error);
+
+        // Every other issue comes with the cycle when asking for all of them.
+        let issues = project.validate();
+        assert!(issues.contains(&ValidationIssue::SubtaskCycle(vec![0, 1, 2])));
+        assert!(issues.contains(&ValidationIssue::MissingStartDate));
+        assert!(issues.contains(&ValidationIssue::MissingDuration(2)));
+        assert_eq!(issues, project.validate_all());
+    }
+
+    #[test]
+    fn unstaffed_tasks_are_warnings() {
+        let mut project = Project::new("World domination");
+        let person = project.add_resource(Resource::Personnel {
+            person: Person::new("Sebastiano", "Giordano").unwrap(),
+            hourly_rate: None,
+            availability: Vec::new(),
+        });
+        let crowbar = project.add_resource(Resource::Material(Material::new("Crowbar")));
+
+        let mut unstaffed = Task::new("Find a crowbar");
+        unstaffed.add_resource(crowbar);
+        project.add_task(unstaffed);
+        let mut staffed = Task::new("Find a stimpack");
+        staffed.add_resource(person);
+        project.add_task(staffed);
+        let mut part_time = Task::new("Find a map");
+        part_time.assign_resource(person, 0.5).unwrap();
+        project.add_task(part_time);
+
+        let issues = project.validate_all();
+        assert!(issues.contains(&ValidationIssue::UnstaffedTask(0)));
+        assert!(!issues.contains(&ValidationIssue::UnstaffedTask(1)));
+        assert!(!issues.contains(&ValidationIssue::UnstaffedTask(2)));
+        assert_eq!(
+            ValidationIssue::UnstaffedTask(0).severity(),
+            Severity::Warning
+        );
+        assert!(project.validate_strict().is_empty());
+    }
+
+    #[test]
+    fn scheduled_dates_are_checked_against_constraints_and_deadlines() {
+        let as_of = Utc::now();
+        let mut project = Project::new("World domination");
+        for name in ["Find a crowbar", "Find a stimpack"] {
+            let mut task = Task::new(name);
+            task.edit_duration(Duration::hours(2).try_into().unwrap());
+            task.edit_start(as_of - Duration::days(1)).unwrap();
+            task.set_constraint(TaskConstraint::StartNoLaterThan(as_of));
+            task.set_deadline(as_of + Duration::hours(1));
+            project.add_task(task);
+        }
+        project
+            .add_time_relationship(0, 1, TimeRelationship::FinishToStart)
+            .unwrap();
+        let issues = project.validate_all();
+        assert!(!issues.contains(&ValidationIssue::ConstraintViolated(0)));
+        assert!(!issues.contains(&ValidationIssue::MissedDeadline(0)));
+
+        project.roll_forward(as_of).unwrap();
+
+        let issues = project.validate_all();
+        assert!(!issues.contains(&ValidationIssue::ConstraintViolated(0)));
+        assert!(issues.contains(&ValidationIssue::MissedDeadline(0)));
+        assert!(issues.contains(&ValidationIssue::ConstraintViolated(1)));
+        assert_eq!(
+            project.validate_strict(),
+            vec![ValidationIssue::ConstraintViolated(1)]
+        );
+    }
+
+    #[test]
+    fn dangling_subtasks_isolated_tasks_and_unpriced_resources_are_reported() {
+        let mut project = Project::new("World domination");
+        for name in ["Find a crowbar", "Find a stimpack"] {
+            project.add_task(Task::new(name));
+        }
+        project
+            .add_time_relationship(0, 1, TimeRelationship::FinishToStart)
+            .unwrap();
+        let mut dated = Task::new("Dated");
+        dated.edit_start(Utc::now()).unwrap();
+        project.add_task(dated);
+        project.add_task(Task::new("Isolated"));
+        project.add_subtask(1, 4);
+
+        let mut crowbars = Material::new("Crowbar");
+        crowbars.update_quantity(3);
+        project.add_resource(Resource::Material(crowbars.clone()));
+        crowbars.update_cost_per_unit(10);
+        project.add_resource(Resource::Material(crowbars));
+
+        let issues = project.validate_all();
+        assert!(issues.contains(&ValidationIssue::DanglingSubtask {
+            task: 1,
+            subtask: 4
+        }));
+        assert!(!issues.contains(&ValidationIssue::IsolatedTask(0)));
+        assert!(!issues.contains(&ValidationIssue::IsolatedTask(2)));
+        assert!(issues.contains(&ValidationIssue::IsolatedTask(3)));
+        assert!(issues.contains(&ValidationIssue::UnpricedResource(0)));
+        assert!(!issues.contains(&ValidationIssue::UnpricedResource(1)));
+        assert_eq!(
+            project.validate_strict(),
+            vec![ValidationIssue::DanglingSubtask {
+                task: 1,
+                subtask: 4
+            }]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn tasks_finishing_before_they_start_are_errors() {
+        let start = Utc::now();
+        let mut task = Task::new("Find a crowbar");
+        task.edit_start(start).unwrap();
+        task.edit_finish(start + Duration::hours(1)).unwrap();
+        let json = serde_json::to_string(&task).unwrap().replace(
+            &serde_json::to_string(&(start + Duration::hours(1))).unwrap(),
+            &serde_json::to_string(&(start - Duration::hours(1))).unwrap(),
+        );
+        let mut project = Project::new("World domination");
+        project.add_task(serde_json::from_str(&json).unwrap());
+
+        assert_eq!(
+            project.validate_strict(),
+            vec![ValidationIssue::FinishBeforeStart(0)]
+        );
+    }
+}
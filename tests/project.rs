@@ -6,11 +6,13 @@ use planter_core::{
     person::Person,
     project::{Project, TimeRelationship},
     resources::{Consumable, Material, NonConsumable, Resource},
-    stakeholders::Stakeholder,
+    risk::Risk,
+    stakeholders::{Engagement, Level, Stakeholder},
     task::Task,
 };
 
 #[test]
+#[allow(clippy::panic_in_result_fn)]
 /// The standard workflow when creating a project involves initializing it with
 /// a name, optionally a description, and a start date. The project is kept
 /// mutable and the user can add/remove tasks, resources, stakeholders, and
@@ -71,11 +73,12 @@ fn test_project() -> anyhow::Result<()> {
     project.add_resource(Resource::Personnel {
         person: Person::new("Sebastiano", "Giordano").context("Failed to parse a name.")?,
         hourly_rate: None,
+        availability: Vec::new(),
     });
     assert_eq!(project.resources().len(), 3);
 
     // Remove a resource from the project
-    project.rm_resource(1);
+    project.rm_resource(1)?;
     assert_eq!(project.resources().len(), 2);
 
     // Add stakeholders to the project
@@ -83,12 +86,55 @@ fn test_project() -> anyhow::Result<()> {
     project.add_stakeholder(Stakeholder::Individual {
         person,
         description: Some("She could try to stop me".to_owned()),
+        influence: Level::High,
+        interest: Level::High,
+        current_engagement: Engagement::Resistant,
+        desired_engagement: Engagement::Neutral,
     });
     project.add_stakeholder(Stakeholder::Organization {
         name: "Acme".to_owned(),
         description: Some("They might decide to buy me more stimpacks".to_owned()),
+        influence: Level::Medium,
+        interest: Level::Low,
+        current_engagement: Engagement::Neutral,
+        desired_engagement: Engagement::Neutral,
     });
     assert_eq!(project.stakeholders().len(), 2);
+    let grid = project.stakeholder_grid();
+    assert_eq!(grid[Level::High as usize][Level::High as usize].len(), 1);
+    assert_eq!(grid[Level::Medium as usize][Level::Low as usize].len(), 1);
+    assert_eq!(
+        project.engagement_gaps(),
+        vec![(0, Engagement::Resistant, Engagement::Neutral)]
+    );
+    let acme = project.stakeholders()[1].clone();
+    assert_eq!(
+        grid[Level::Medium as usize][Level::Low as usize],
+        vec![&acme]
+    );
+
+    // Keep a risk register, with risks owned by the stakeholders
+    project.add_risk(Risk::new("Margherita finds out", Level::Low, Level::High).with_owner(0));
+    project.add_risk(Risk::new("Acme goes bankrupt", Level::High, Level::High).with_owner(1));
+    project.add_risk(Risk::new("Stimpacks run out", Level::Medium, Level::Medium));
+    let by_severity: Vec<&str> = project
+        .risks_by_severity()
+        .iter()
+        .map(|risk| risk.description())
+        .collect();
+    assert_eq!(
+        by_severity,
+        vec![
+            "Acme goes bankrupt",
+            "Stimpacks run out",
+            "Margherita finds out"
+        ]
+    );
+    project.rm_stakeholder(0)?;
+    assert_eq!(project.risks()[0].owner(), None);
+    assert_eq!(project.risks()[1].owner(), Some(0));
+    project.rm_risk(2)?;
+    assert_eq!(project.risks().len(), 2);
 
     Ok(())
 }